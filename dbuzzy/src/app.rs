@@ -1,62 +1,291 @@
-use std::{collections::HashMap, time::Duration};
+use std::{
+    future::Future,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
+use anyhow::Context;
 use crossterm::event::{Event, EventStream};
 use duzzy_editor::Editor;
-use duzzy_lib::{DuzzyWidget, EventOutcome};
+use duzzy_lib::{
+    colors,
+    event::{Event as InputEvent, Input, Modifiers},
+    DuzzyWidget, EventOutcome,
+};
 use futures_util::StreamExt;
-use ratatui::{backend::Backend, buffer::Buffer, layout::Rect, widgets::Widget, Terminal};
+use ratatui::{
+    backend::Backend,
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Borders, Tabs, Widget},
+    Terminal,
+};
+use tokio::{sync::mpsc, task::JoinHandle};
 
 use crate::{
     config::Config,
-    widgets::{AppEventOutcome, AppWidgetData, AppWidgetName, ConnListWidget, DbTreeWidget},
+    db::{
+        self, tree::TreeItem, CancelHandle, Column, Connection, ConnectionConfig, CsvImport,
+        DbPool, EditBatch, ExportFormat, ImportProgress, ImportSummary, Placeholders, Plan,
+        QueryResult, RowCount, ServerInfo, TableInfo, TablePage, TableRef, Transaction,
+        TransactionState,
+    },
+    history::{History, HistoryEntry},
+    layout::{self, PaneLayout},
+    snippets::Snippets,
+    widgets::{
+        AppEventOutcome, AppWidgetData, AppWidgetName, ConfirmWidget, ConnFormWidget,
+        ConnListWidget, DbTreeWidget, ErrorWidget, HistoryWidget, ImportWidget, PasswordWidget,
+        PlanWidget, PromptKind, PromptWidget, ResultGridWidget, SnippetsWidget, TableDataWidget,
+        PAGE_SIZE,
+    },
 };
 
+// @note: tab colours of connections without `color`
+const PALETTE: [Color; 6] = [
+    Color::LightGreen,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::LightRed,
+    Color::LightYellow,
+];
+
+// @note: the value of a query parameter that is bound as null, like in COPY, so an empty
+// value is an empty string
+const NULL_MARKER: &str = "\\N";
+
+// @note: results of background tasks, tagged with the id of their session
+pub enum AppMessage {
+    // @note: tagged with the table, so stale pages of a closed data view are dropped
+    Table(usize, TableRef, TableMessage),
+    Exported(usize, PathBuf, anyhow::Result<u64>),
+    Import(usize, TableRef, ImportMessage),
+    // @note: tagged with the item, the tree can change while it is loaded
+    Tree(usize, Arc<TreeItem>, TreeMessage),
+    Query(usize, Box<Queried>),
+    // @note: the connection of a running query is acquired, tagged with the start of the query
+    Cancellable(usize, Instant, Box<dyn CancelHandle>),
+    Explained(usize, Box<Explained>),
+    // @note: a statement run by a background task
+    History(HistoryEntry),
+    // @note: the open transaction, back from a table task that used it
+    Transaction(usize, Transaction),
+    Controlled(usize, Box<Controlled>),
+    Tested(anyhow::Result<ServerInfo>),
+    // @note: tagged with the start, so a cancelled attempt is dropped
+    Connected(Instant, Box<ConnectionConfig>, anyhow::Result<Opened>),
+}
+
+pub struct Queried {
+    sql: String,
+    values: Option<Vec<Option<String>>>,
+    result: anyhow::Result<QueryResult>,
+    // @note: the open transaction goes with the query and comes back
+    tx: Option<Transaction>,
+}
+
+// @note: BEGIN, COMMIT, ROLLBACK or SAVEPOINT, with the transaction that is still open
+pub struct Controlled {
+    result: anyhow::Result<()>,
+    tx: Option<Transaction>,
+}
+
+pub struct Explained {
+    sql: String,
+    analyze: bool,
+    result: anyhow::Result<Plan>,
+    tx: Option<Transaction>,
+}
+
+pub struct Opened {
+    pool: DbPool,
+    tree: Box<DbTreeWidget>,
+}
+
+// @note: an open connection with its own tree, editor and results, shown as a tab
+struct Session {
+    id: usize,
+    config: Box<ConnectionConfig>,
+    pool: DbPool,
+    tree: Box<DbTreeWidget>,
+    editor: Box<Editor>,
+    results: Box<ResultGridWidget>,
+    table_data: Option<Box<TableDataWidget>>,
+    import: Option<Box<ImportWidget>>,
+    snippets: Option<Box<SnippetsWidget>>,
+    plan: Option<Box<PlanWidget>>,
+    // @note: the statement of the rows in the grid, run again to stream an export
+    shown: Option<(String, Option<Vec<Option<String>>>)>,
+    tx: Option<Transaction>,
+    running: Option<Running>,
+    // @note: restored when switching back to the tab
+    focus: AppWidgetName,
+}
+
+struct Connecting {
+    started: Instant,
+    task: JoinHandle<()>,
+}
+
+struct Running {
+    started: Instant,
+    // @note: `None` for the drivers without cancel requests, the task is aborted then
+    cancel: Option<Box<dyn CancelHandle>>,
+    // @note: of the transaction, that is in the task
+    tx_state: Option<TransactionState>,
+    task: JoinHandle<()>,
+}
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+// @note: the history entries of the statements run by background tasks go back as messages
+struct Recorder {
+    sender: mpsc::UnboundedSender<AppMessage>,
+    conn: String,
+}
+
+impl Recorder {
+    fn record(&self, sql: &str, started: Instant, rows: Result<u64, &anyhow::Error>) {
+        let entry = HistoryEntry::with_rows(sql, self.conn.clone(), started.elapsed(), rows);
+        self.sender.send(AppMessage::History(entry)).ok();
+    }
+}
+
+pub enum TableMessage {
+    Info(anyhow::Result<TableInfo>),
+    Page(anyhow::Result<TablePage>),
+    Count(anyhow::Result<RowCount>),
+    Committed(anyhow::Result<u64>),
+}
+
+pub enum TreeMessage {
+    Children(anyhow::Result<Vec<Arc<TreeItem>>>),
+    Ddl(anyhow::Result<String>),
+}
+
+pub enum ImportMessage {
+    Columns(anyhow::Result<Vec<Column>>),
+    Progress(ImportProgress),
+    Finished(anyhow::Result<ImportSummary>),
+}
+
+enum AppEvent {
+    Input(Event),
+    Message(AppMessage),
+    Tick,
+}
+
 pub struct App {
+    config: Config,
     focus: AppWidgetName,
-    editor: Box<Editor>,
-    widgets: HashMap<AppWidgetName, Box<dyn DuzzyWidget<Outcome = AppEventOutcome>>>,
+    sessions: Vec<Session>,
+    active: usize,
+    next_session_id: usize,
+    layout: PaneLayout,
+    history: History,
+    history_picker: Option<Box<HistoryWidget>>,
+    prompt: Option<Box<PromptWidget>>,
+    password: Option<Box<PasswordWidget>>,
+    conn_form: Option<Box<ConnFormWidget>>,
+    conn_list: Box<ConnListWidget>,
+    connecting: Option<Connecting>,
+    error: Option<Box<ErrorWidget>>,
+    confirm: Option<Box<ConfirmWidget>>,
+    sender: mpsc::UnboundedSender<AppMessage>,
+    receiver: mpsc::UnboundedReceiver<AppMessage>,
 }
 
 impl App {
-    pub fn new(config: &'static Config) -> Self {
-        let mut widgets: HashMap<AppWidgetName, Box<dyn DuzzyWidget<Outcome = AppEventOutcome>>> =
-            HashMap::new();
-
-        widgets.insert(
-            AppWidgetName::ConnectionList,
-            Box::new(ConnListWidget::new(config.conn.as_slice())),
-        );
+    pub fn new(config: Config, history: History) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
 
         Self {
-            widgets,
-            editor: Box::new(Editor::new_scratch()),
+            conn_list: Box::new(ConnListWidget::new(config.conn.clone(), 0)),
+            config,
+            sessions: vec![],
+            active: 0,
+            next_session_id: 0,
+            layout: PaneLayout::default(),
+            history,
+            history_picker: None,
+            prompt: None,
+            password: None,
+            conn_form: None,
+            connecting: None,
+            error: None,
+            confirm: None,
+            sender,
+            receiver,
             focus: AppWidgetName::ConnectionList,
         }
     }
 
+    // @note: starts connecting straight away, from the connection list
+    pub async fn connect(&mut self, config: ConnectionConfig) -> anyhow::Result<()> {
+        self.apply(AppWidgetData::Connect(Box::new(config))).await
+    }
+
     pub async fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> anyhow::Result<()> {
         let mut reader = EventStream::new();
+        // @note: redraws the spinners
+        let mut tick = tokio::time::interval(Duration::from_millis(100));
 
         self.draw(terminal)?;
 
         loop {
-            let Some(Ok(event)) = reader.next().await else {
-                tokio::time::sleep(Duration::from_millis(100)).await;
-                continue;
+            let event = tokio::select! {
+                event = reader.next() => match event {
+                    Some(Ok(event)) => AppEvent::Input(event),
+                    _ => {
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                        continue;
+                    }
+                },
+                Some(message) = self.receiver.recv() => AppEvent::Message(message),
+                _ = tick.tick(), if self.connecting.is_some() || self.is_running() => AppEvent::Tick,
+            };
+
+            let event = match event {
+                AppEvent::Input(event) => event,
+                AppEvent::Message(message) => {
+                    self.receive(message);
+                    self.draw(terminal)?;
+                    continue;
+                }
+                AppEvent::Tick => {
+                    self.draw(terminal)?;
+                    continue;
+                }
             };
 
             match self.handle_event(event) {
                 AppEventOutcome::Outcome(event) => match event {
                     EventOutcome::Render => self.draw(terminal)?,
                     EventOutcome::Ignore => continue,
-                    EventOutcome::Exit => return Ok(()),
+                    EventOutcome::Exit => {
+                        if !self.confirm_rollback(AppWidgetData::Exit) {
+                            return Ok(());
+                        }
+                        self.draw(terminal)?;
+                    }
                 },
-                AppEventOutcome::Focus(name) => self.focus = name,
-                // @todo: show error widget
-                #[allow(clippy::redundant_pattern_matching)]
+                AppEventOutcome::Apply(AppWidgetData::Exit) => return Ok(()),
+                AppEventOutcome::Focus(name) => {
+                    self.set_focus(name);
+                    self.draw(terminal)?;
+                }
                 AppEventOutcome::Apply(data) => {
-                    if let Err(_) = self.apply(data).await {
-                        // @todo: error widget
+                    self.draw(terminal)?;
+                    if let Err(e) = self.apply(data).await {
+                        self.show_error(&e);
                     }
                     self.draw(terminal)?;
                 }
@@ -72,31 +301,1494 @@ impl App {
     fn handle_event(&mut self, event: Event) -> AppEventOutcome {
         let input = event.into();
 
-        if self.focus == AppWidgetName::Editor {
-            return self.editor.input(input).into();
+        if let Some(error) = self.error.as_mut() {
+            let outcome = error.input(input);
+            if matches!(outcome, AppEventOutcome::Focus(_)) {
+                self.error = None;
+            }
+            return outcome;
         }
 
-        self.focused().input(input)
+        if let Some(confirm) = self.confirm.as_mut() {
+            let outcome = confirm.input(input);
+            if !matches!(outcome, AppEventOutcome::Outcome(_)) {
+                self.confirm = None;
+            }
+            return outcome;
+        }
+
+        // @note: alt + number switches the tabs, alt + 0 opens the connection list
+        if let Input {
+            event: InputEvent::Char(ch),
+            modifiers: Modifiers { alt: true, .. },
+        } = input
+        {
+            match ch {
+                '0' => return AppEventOutcome::Focus(AppWidgetName::ConnectionList),
+                '1'..='9' => {
+                    let index = ch as usize - '1' as usize;
+                    return AppEventOutcome::Apply(AppWidgetData::SwitchSession(index));
+                }
+                'w' if !self.sessions.is_empty() && self.focus.is_session() => {
+                    return AppEventOutcome::Apply(AppWidgetData::CloseSession(false));
+                }
+                's' if self.focus.is_session() => {
+                    return AppEventOutcome::Apply(AppWidgetData::ShowSnippets);
+                }
+                'b' if self.focus.is_session() => {
+                    return AppEventOutcome::Apply(AppWidgetData::BeginTransaction);
+                }
+                'c' if self.focus.is_session() => {
+                    return AppEventOutcome::Apply(AppWidgetData::CommitTransaction);
+                }
+                'r' if self.focus.is_session() => {
+                    return AppEventOutcome::Apply(AppWidgetData::RollbackTransaction);
+                }
+                'p' if self.focus.is_session() => {
+                    return AppEventOutcome::Apply(AppWidgetData::NewSavepoint);
+                }
+                'z' if self.focus.is_session() => {
+                    self.layout.toggle_zoom();
+                    return EventOutcome::Render.into();
+                }
+                _ => {}
+            }
+        }
+
+        // @note: tab or ctrl + w cycles the panes, unless the editor takes them as text,
+        // alt + arrows resize the panes
+        if let Some(session) = self
+            .sessions
+            .get(self.active)
+            .filter(|_| self.focus.is_session())
+        {
+            match input {
+                Input {
+                    event: InputEvent::Char('c'),
+                    modifiers: Modifiers { ctr: true, .. },
+                } if session.running.is_some() => {
+                    return AppEventOutcome::Apply(AppWidgetData::CancelQuery);
+                }
+                Input {
+                    event: InputEvent::Tab,
+                    ..
+                }
+                | Input {
+                    event: InputEvent::Char('w'),
+                    modifiers: Modifiers { ctr: true, .. },
+                } if !session.is_typing() => {
+                    let panes = session.panes();
+                    return AppEventOutcome::Focus(layout::next_pane(self.focus, &panes));
+                }
+                Input {
+                    event: event @ (InputEvent::Left | InputEvent::Right),
+                    modifiers: Modifiers { alt: true, .. },
+                } => {
+                    self.layout.resize_tree(event == InputEvent::Right);
+                    return EventOutcome::Render.into();
+                }
+                Input {
+                    event: event @ (InputEvent::Up | InputEvent::Down),
+                    modifiers: Modifiers { alt: true, .. },
+                } => {
+                    self.layout.resize_editor(event == InputEvent::Down);
+                    return EventOutcome::Render.into();
+                }
+                _ => {}
+            }
+        }
+
+        match self.focus {
+            name if name.is_session() => match self.sessions.get_mut(self.active) {
+                Some(session) => session.input(input),
+                None => EventOutcome::Ignore.into(),
+            },
+            AppWidgetName::Password => match self.password.as_mut() {
+                Some(password) => password.input(input),
+                None => EventOutcome::Ignore.into(),
+            },
+            AppWidgetName::ConnectionForm => match self.conn_form.as_mut() {
+                Some(conn_form) => conn_form.input(input),
+                None => EventOutcome::Ignore.into(),
+            },
+            AppWidgetName::History => match self.history_picker.as_mut() {
+                Some(history_picker) => history_picker.input(input),
+                None => EventOutcome::Ignore.into(),
+            },
+            AppWidgetName::Prompt => match self.prompt.as_mut() {
+                Some(prompt) => prompt.input(input),
+                None => EventOutcome::Ignore.into(),
+            },
+            _ => self.conn_list.input(input),
+        }
+    }
+
+    fn set_focus(&mut self, name: AppWidgetName) {
+        self.focus = name;
+
+        if name.is_session() {
+            if let Some(session) = self.sessions.get_mut(self.active) {
+                session.focus = name;
+            }
+        }
+    }
+
+    fn session(&self) -> anyhow::Result<&Session> {
+        self.sessions
+            .get(self.active)
+            .context("no active connection")
+    }
+
+    fn session_mut(&mut self) -> anyhow::Result<&mut Session> {
+        self.sessions
+            .get_mut(self.active)
+            .context("no active connection")
     }
 
     async fn apply(&mut self, data: AppWidgetData) -> anyhow::Result<()> {
         match data {
-            AppWidgetData::Connection(pool) => {
-                self.widgets.insert(
-                    AppWidgetName::DatabaseTree,
-                    Box::new(DbTreeWidget::new(&pool).await?),
-                );
+            AppWidgetData::Connect(config) => {
+                let mut config = Box::new(config.resolve()?);
+                let sender = self.sender.clone();
+                let started = Instant::now();
+
+                self.cancel_connect();
+                self.conn_list
+                    .set_connecting(Some((config.to_string(), started)));
+                self.password = None;
+                self.focus = AppWidgetName::ConnectionList;
+
+                let task = tokio::spawn(async move {
+                    let session = open_session(&mut config).await;
+                    sender
+                        .send(AppMessage::Connected(started, config, session))
+                        .ok();
+                });
+
+                self.connecting = Some(Connecting { started, task });
+            }
+            AppWidgetData::CancelConnect => self.cancel_connect(),
+            AppWidgetData::EditConnection(index) => {
+                let config = match index {
+                    Some(index) => self.config.conn.get(index).context("no such connection")?,
+                    None => &ConnectionConfig::default(),
+                };
+
+                self.conn_form = Some(Box::new(ConnFormWidget::new(index, config.clone())));
+                self.focus = AppWidgetName::ConnectionForm;
+            }
+            AppWidgetData::SaveConnection(index, config) => {
+                match self.config.save_conn(index, *config) {
+                    Ok(index) => {
+                        self.reload_connections(index);
+                        self.conn_form = None;
+                        self.focus = AppWidgetName::ConnectionList;
+                    }
+                    Err(e) => {
+                        if let Some(conn_form) = self.conn_form.as_mut() {
+                            conn_form.set_error(format!("{e:#}"));
+                        }
+                    }
+                }
+            }
+            AppWidgetData::TestConnection(config) => {
+                let conn_form = self.conn_form.as_mut().context("no connection form")?;
+                conn_form.set_testing();
+
+                let sender = self.sender.clone();
+
+                tokio::spawn(async move {
+                    let info = test_connection(*config).await;
+                    sender.send(AppMessage::Tested(info)).ok();
+                });
+            }
+            AppWidgetData::DeleteConnection(index) => {
+                self.config.remove_conn(index)?;
+                self.reload_connections(index);
+            }
+            AppWidgetData::SwitchSession(index) => {
+                if let Some(session) = self.sessions.get(index) {
+                    self.active = index;
+                    self.focus = session.focus;
+                }
+            }
+            AppWidgetData::CloseSession(force) => {
+                let session = self.session()?;
+                if !force && session.tx_state().is_some() {
+                    self.confirm_rollback(AppWidgetData::CloseSession(true));
+                    return Ok(());
+                }
+
+                self.sessions.remove(self.active);
+                self.active = self.active.min(self.sessions.len().saturating_sub(1));
+                self.focus = self
+                    .sessions
+                    .get(self.active)
+                    .map_or(AppWidgetName::ConnectionList, |session| session.focus);
+            }
+            AppWidgetData::Exit => {}
+            AppWidgetData::Query(sql) => self.start_query(sql)?,
+            AppWidgetData::BeginTransaction => {
+                let sender = self.sender.clone();
+                let session = self.session_mut()?;
+                session.ensure_idle()?;
+                anyhow::ensure!(session.tx.is_none(), "a transaction is already open");
+
+                let pool = session.pool.clone();
+                let recorder = session.recorder(&sender);
+
+                session.spawn_control(None, None, sender, async move {
+                    let started = Instant::now();
+                    let tx = Transaction::begin(&pool).await;
+                    recorder.record("BEGIN", started, tx.as_ref().map(|_| 0));
+
+                    match tx {
+                        Ok(tx) => Controlled {
+                            result: Ok(()),
+                            tx: Some(tx),
+                        },
+                        Err(e) => Controlled {
+                            result: Err(e),
+                            tx: None,
+                        },
+                    }
+                });
+            }
+            AppWidgetData::CommitTransaction | AppWidgetData::RollbackTransaction => {
+                let sender = self.sender.clone();
+                let session = self.session_mut()?;
+                session.ensure_idle()?;
+                let mut tx = session.tx.take().context("no open transaction")?;
+
+                let recorder = session.recorder(&sender);
+                let commit = matches!(data, AppWidgetData::CommitTransaction);
+                let tx_state = Some(tx.state());
+                let cancel = tx.cancel_handle();
+
+                session.spawn_control(tx_state, cancel, sender, async move {
+                    let started = Instant::now();
+                    let (sql, result) = match commit {
+                        true => ("COMMIT", tx.commit().await),
+                        false => ("ROLLBACK", tx.rollback().await),
+                    };
+                    recorder.record(sql, started, result.as_ref().map(|()| 0));
+
+                    Controlled { result, tx: None }
+                });
+            }
+            AppWidgetData::NewSavepoint => {
+                let session = self.session()?;
+                session.ensure_idle()?;
+                let tx = session.tx.as_ref().context("no open transaction")?;
+
+                let name = format!("sp{}", tx.savepoints() + 1);
+                let fields = vec![("name".to_owned(), name)];
+                let prompt =
+                    PromptWidget::new("Savepoint", PromptKind::Savepoint, fields, self.focus);
+
+                self.prompt = Some(Box::new(prompt));
+                self.focus = AppWidgetName::Prompt;
+            }
+            AppWidgetData::CancelQuery => {
+                let session = self.session_mut()?;
+                let running = session.running.as_ref().context("no query is running")?;
+
+                // @note: the task gets the error of the cancelled query
+                match running.cancel.as_ref() {
+                    Some(cancel) => cancel.cancel().await?,
+                    None => {
+                        session.running = None;
+                        session.results.set_error("query cancelled");
+                    }
+                }
+            }
+            AppWidgetData::Explain(sql, analyze) => {
+                let sender = self.sender.clone();
+                self.session_mut()?.spawn_explain(sql, analyze, sender)?;
+            }
+            AppWidgetData::ShowHistory => {
+                self.session()?;
+                self.history_picker = Some(Box::new(HistoryWidget::new(self.history.entries())));
+                self.focus = AppWidgetName::History;
+            }
+            AppWidgetData::LoadQuery(sql) => {
+                self.session_mut()?.editor.open_text(&sql);
+                self.set_focus(AppWidgetName::Editor);
+            }
+            AppWidgetData::RerunQuery(sql) => {
+                self.set_focus(AppWidgetName::ResultGrid);
+                self.start_query(sql)?;
+            }
+            AppWidgetData::ShowSnippets => {
+                let session = self.session_mut()?;
+                let snippets = Snippets::open()?.load(&session.conn_name())?;
+
+                session.snippets = Some(Box::new(SnippetsWidget::new(snippets)));
+                self.set_focus(AppWidgetName::Snippets);
+            }
+            AppWidgetData::NewSnippet(sql) => {
+                let fields = vec![
+                    ("name".to_owned(), String::new()),
+                    ("tags".to_owned(), String::new()),
+                    ("scope".to_owned(), "global".to_owned()),
+                ];
+
+                let kind = PromptKind::SaveSnippet(sql);
+                let prompt = PromptWidget::new("Save snippet", kind, fields, self.focus);
+
+                self.prompt = Some(Box::new(prompt));
+                self.focus = AppWidgetName::Prompt;
+            }
+            AppWidgetData::OpenSnippet(path) => {
+                self.session_mut()?.editor.open_file(path)?;
+                self.set_focus(AppWidgetName::Editor);
+            }
+            AppWidgetData::Prompted(kind, values) => {
+                let focus = self.prompt.as_ref().map_or(self.focus, |p| p.focus());
+
+                match kind {
+                    PromptKind::SaveSnippet(sql) => {
+                        let session = self.session_mut()?;
+                        let [name, tags, scope] = values.as_slice() else {
+                            anyhow::bail!("name, tags and scope are expected");
+                        };
+
+                        let conn = match scope.trim() {
+                            "global" => None,
+                            "connection" => Some(session.conn_name()),
+                            scope => anyhow::bail!(
+                                "unknown scope `{scope}`, `global` or `connection` are expected"
+                            ),
+                        };
+
+                        let tags = tags
+                            .split(',')
+                            .map(str::trim)
+                            .filter(|tag| !tag.is_empty())
+                            .map(ToOwned::to_owned)
+                            .collect::<Vec<_>>();
+
+                        let path =
+                            Snippets::open()?.save(conn.as_deref(), name.trim(), &tags, &sql)?;
+                        session
+                            .results
+                            .set_message(format!("saved {}", path.display()));
+
+                        self.prompt = None;
+                        self.set_focus(focus);
+                    }
+                    PromptKind::QueryParams(sql) => {
+                        let values = values
+                            .into_iter()
+                            .map(|value| (value != NULL_MARKER).then_some(value))
+                            .collect();
+
+                        self.prompt = None;
+                        self.set_focus(focus);
+                        self.run_query(sql, Some(values))?;
+                    }
+                    PromptKind::Savepoint => {
+                        let sender = self.sender.clone();
+                        let name = values.first().map_or("", |name| name.trim()).to_owned();
+                        let session = self.session_mut()?;
+                        session.ensure_idle()?;
+                        let mut tx = session.tx.take().context("no open transaction")?;
+
+                        let sql = format!("SAVEPOINT {}", session.pool.kind().quote_ident(&name));
+                        let recorder = session.recorder(&sender);
+                        let tx_state = Some(tx.state());
+                        let cancel = tx.cancel_handle();
+
+                        session.spawn_control(tx_state, cancel, sender, async move {
+                            let started = Instant::now();
+                            let result = tx.savepoint(&name).await;
+                            recorder.record(&sql, started, result.as_ref().map(|()| 0));
+
+                            Controlled {
+                                result,
+                                tx: Some(tx),
+                            }
+                        });
+
+                        self.prompt = None;
+                        self.set_focus(focus);
+                    }
+                }
+            }
+            AppWidgetData::Export(path) => {
+                let sender = self.sender.clone();
+                let format = ExportFormat::from_path(&path, &self.config.export);
+
+                let session = self.session_mut()?;
+                let pool = session.pool.clone();
+                let id = session.id;
+                let recorder = session.recorder(&sender);
+
+                let result = session.results.result().context("nothing to export")?;
+                let format = match format {
+                    Ok(format) => format,
+                    Err(e) => {
+                        session.results.set_message(e);
+                        return Ok(());
+                    }
+                };
+
+                // @note: another connection doesn't see the changes of the open transaction,
+                // so the rows of the grid are written then
+                let shown = session
+                    .shown
+                    .clone()
+                    .filter(|_| session.tx_state().is_none());
+
+                tokio::spawn(async move {
+                    let rows = export_query(&pool, shown, &recorder, result, format, &path).await;
+                    sender.send(AppMessage::Exported(id, path, rows)).ok();
+                });
+            }
+            AppWidgetData::ExpandTree(index) | AppWidgetData::ShowDdl(index) => {
+                let sender = self.sender.clone();
+                let session = self.session_mut()?;
+                let item = session.tree.item(index).context("no item selected")?;
+                let pool = session.pool.clone();
+                let id = session.id;
+
+                tokio::spawn(async move {
+                    let message = match data {
+                        AppWidgetData::ExpandTree(_) => {
+                            TreeMessage::Children(load_children(&pool, &item).await)
+                        }
+                        _ => TreeMessage::Ddl(load_ddl(&pool, &item).await),
+                    };
+                    sender.send(AppMessage::Tree(id, item, message)).ok();
+                });
+            }
+            AppWidgetData::OpenTable(table) => {
+                let sender = self.sender.clone();
+                let session = self.session_mut()?;
+                let pool = session.pool.clone();
+                let id = session.id;
+                let recorder = session.recorder(&sender);
+                let loaded = table.clone();
+
+                session.spawn_with_tx(sender.clone(), |tx| async move {
+                    let info = load_table_info(&pool, tx.as_ref(), &loaded).await;
+                    let page = match info.as_ref() {
+                        Ok(info) => Some(
+                            load_table_page(&pool, tx.as_ref(), &recorder, &loaded, info, 0).await,
+                        ),
+                        Err(_) => None,
+                    };
+
+                    let info = TableMessage::Info(info);
+                    sender
+                        .send(AppMessage::Table(id, loaded.clone(), info))
+                        .ok();
+
+                    if let Some(page) = page {
+                        let page = TableMessage::Page(page);
+                        sender.send(AppMessage::Table(id, loaded, page)).ok();
+                    }
+
+                    tx
+                })?;
+
+                let table_data = TableDataWidget::new(table, session.pool.kind());
+                session.table_data = Some(Box::new(table_data));
+                self.set_focus(AppWidgetName::TableData);
+            }
+            AppWidgetData::TablePage(page) => {
+                let sender = self.sender.clone();
+                self.session_mut()?.fetch_table_page(page, &sender)?;
+            }
+            AppWidgetData::CountRows => {
+                let sender = self.sender.clone();
+                let session = self.session_mut()?;
+                let pool = session.pool.clone();
+                let id = session.id;
+                let recorder = session.recorder(&sender);
+
+                let table_data = session.table_data.as_ref().context("no table opened")?;
+                let table = table_data.table().clone();
+
+                session.spawn_with_tx(sender.clone(), |tx| async move {
+                    let count = count_table_rows(&pool, tx.as_ref(), &recorder, &table).await;
+                    let count = TableMessage::Count(count);
+                    sender.send(AppMessage::Table(id, table, count)).ok();
+
+                    tx
+                })?;
+            }
+            AppWidgetData::CommitEdits => {
+                let sender = self.sender.clone();
+                let session = self.session_mut()?;
+                let pool = session.pool.clone();
+                let id = session.id;
+                let recorder = session.recorder(&sender);
+
+                let table_data = session.table_data.as_ref().context("no table opened")?;
+                let table = table_data.table().clone();
+                let edits = table_data.edits().clone();
+                let info = table_data.info().cloned();
+                let page = table_data.page();
+
+                // @note: the page is reloaded, to show what was committed
+                session.spawn_with_tx(sender.clone(), |tx| async move {
+                    let affected = commit_edits(&pool, tx.as_ref(), &recorder, &edits).await;
+                    let committed = affected.is_ok();
+
+                    let affected = TableMessage::Committed(affected);
+                    sender
+                        .send(AppMessage::Table(id, table.clone(), affected))
+                        .ok();
+
+                    if let (true, Some(info)) = (committed, info) {
+                        let page =
+                            load_table_page(&pool, tx.as_ref(), &recorder, &table, &info, page)
+                                .await;
+                        let page = TableMessage::Page(page);
+                        sender.send(AppMessage::Table(id, table, page)).ok();
+                    }
+
+                    tx
+                })?;
+            }
+            AppWidgetData::ImportTable(table) => {
+                let sender = self.sender.clone();
+                let session = self.session_mut()?;
+                session.ensure_no_tx()?;
+                let pool = session.pool.clone();
+                let id = session.id;
+
+                session.import = Some(Box::new(ImportWidget::new(table.clone())));
+                self.set_focus(AppWidgetName::Import);
+
+                tokio::spawn(async move {
+                    let columns = load_table_columns(&pool, &table).await;
+                    let columns = ImportMessage::Columns(columns);
+                    sender.send(AppMessage::Import(id, table, columns)).ok();
+                });
+            }
+            AppWidgetData::StartImport(import) => {
+                let sender = self.sender.clone();
+                let session = self.session()?;
+                session.ensure_no_tx()?;
+                let pool = session.pool.clone();
+                let id = session.id;
+                let recorder = session.recorder(&sender);
+
+                tokio::spawn(async move {
+                    let summary = import_csv(&pool, id, &import, &sender, &recorder).await;
+                    let summary = ImportMessage::Finished(summary);
+                    sender
+                        .send(AppMessage::Import(id, import.table, summary))
+                        .ok();
+                });
+            }
+        };
+
+        Ok(())
+    }
+
+    // @note: asks for the values first, when the statement has placeholders
+    fn start_query(&mut self, sql: String) -> anyhow::Result<()> {
+        let params = Placeholders::parse(&sql).params().to_vec();
+
+        if params.is_empty() {
+            return self.run_query(sql, None);
+        }
+
+        let fields = params.into_iter().map(|p| (p, String::new())).collect();
+        let kind = PromptKind::QueryParams(sql);
+        let title = format!("Parameters, {NULL_MARKER} is null");
+        let prompt = PromptWidget::new(title, kind, fields, self.focus);
+
+        self.prompt = Some(Box::new(prompt));
+        self.focus = AppWidgetName::Prompt;
+
+        Ok(())
+    }
+
+    fn run_query(
+        &mut self,
+        sql: String,
+        values: Option<Vec<Option<String>>>,
+    ) -> anyhow::Result<()> {
+        let sender = self.sender.clone();
+        self.session_mut()?.spawn_query(sql, values, sender)
+    }
+
+    fn is_running(&self) -> bool {
+        self.sessions
+            .iter()
+            .any(|session| session.running.is_some())
+    }
+
+    fn queried(&mut self, id: usize, queried: Queried) {
+        let Some(session) = self.sessions.iter_mut().find(|s| s.id == id) else {
+            return;
+        };
+
+        // @note: a query cancelled by aborting its task
+        let Some(running) = session.running.take() else {
+            return;
+        };
+
+        let Queried {
+            sql,
+            values,
+            result,
+            tx,
+        } = queried;
+        let elapsed = running.started.elapsed();
+        let entry = HistoryEntry::new(&sql, session.config.to_string(), elapsed, &result);
+
+        session.shown = None;
+
+        match result {
+            Ok(result) => {
+                if result.affected.is_none() {
+                    session.shown = Some((sql, values));
+                }
+                session.results.set_result(result);
+            }
+            Err(e) => session.results.set_error(e),
+        }
+
+        session.tx = tx;
+        self.record(entry);
+    }
+
+    fn explained(&mut self, id: usize, explained: Explained) {
+        let active = self.sessions.get(self.active).map(|s| s.id) == Some(id);
+
+        let Some(session) = self.sessions.iter_mut().find(|s| s.id == id) else {
+            return;
+        };
+
+        // @note: cancelled by aborting its task
+        let Some(running) = session.running.take() else {
+            return;
+        };
+
+        let Explained {
+            sql,
+            analyze,
+            result,
+            tx,
+        } = explained;
+        session.tx = tx;
+
+        let sql = match analyze {
+            true => format!("EXPLAIN ANALYZE {sql}"),
+            false => format!("EXPLAIN {sql}"),
+        };
+        let rows = result.as_ref().map(|plan| plan.nodes().len() as u64);
+        let entry = HistoryEntry::with_rows(
+            &sql,
+            session.config.to_string(),
+            running.started.elapsed(),
+            rows,
+        );
+
+        match result {
+            Ok(plan) => {
+                let title = match analyze {
+                    true => "Plan, analyzed",
+                    false => "Plan",
+                };
+                session.plan = Some(Box::new(PlanWidget::new(title, plan)));
+                session.results.clear();
+                session.focus = AppWidgetName::Plan;
+
+                if active && self.focus.is_session() {
+                    self.focus = AppWidgetName::Plan;
+                }
+            }
+            Err(e) => session.results.set_error(e),
+        }
+
+        self.record(entry);
+    }
+
+    fn receive_tree(&mut self, id: usize, item: &Arc<TreeItem>, message: TreeMessage) {
+        let active = self.sessions.get(self.active).map(|s| s.id) == Some(id);
+
+        let Some(session) = self.sessions.iter_mut().find(|s| s.id == id) else {
+            return;
+        };
+
+        match message {
+            TreeMessage::Children(Ok(children)) => session.tree.set_children(item, children),
+            TreeMessage::Children(Err(e)) => self.show_error(&e),
+            TreeMessage::Ddl(ddl) => {
+                match ddl {
+                    Ok(ddl) => session.editor.open_text(&ddl),
+                    Err(e) => session.results.set_error(e),
+                }
+
+                session.focus = AppWidgetName::Editor;
+                if active && self.focus.is_session() {
+                    self.focus = AppWidgetName::Editor;
+                }
+            }
+        }
+    }
+
+    fn controlled(&mut self, id: usize, controlled: Controlled) {
+        // @note: aborted by Ctrl-C
+        let Some(session) = self.sessions.iter_mut().find(|s| s.id == id) else {
+            return;
+        };
+
+        session.running = None;
+        session.tx = controlled.tx;
+
+        if let Err(e) = controlled.result {
+            self.show_error(&e);
+        }
+    }
+
+    fn record(&mut self, entry: HistoryEntry) {
+        if let Err(e) = self.history.push(entry) {
+            self.show_error(&e.context("failed to write the query history"));
+        }
+    }
+
+    fn reload_connections(&mut self, selected: usize) {
+        let mut conn_list = ConnListWidget::new(self.config.conn.clone(), selected);
+        conn_list.set_connecting(self.conn_list.connecting().cloned());
+        *self.conn_list = conn_list;
+    }
 
+    fn cancel_connect(&mut self) {
+        if let Some(connecting) = self.connecting.take() {
+            connecting.task.abort();
+        }
+
+        self.conn_list.set_connecting(None);
+    }
+
+    fn connected(
+        &mut self,
+        started: Instant,
+        mut config: Box<ConnectionConfig>,
+        opened: anyhow::Result<Opened>,
+    ) {
+        if self.connecting.as_ref().map(|c| c.started) != Some(started) {
+            return;
+        }
+
+        self.connecting = None;
+        self.conn_list.set_connecting(None);
+
+        match opened {
+            Ok(opened) => {
+                let session = Session::new(self.next_session_id, config, opened);
+                self.next_session_id += 1;
+
+                self.sessions.push(session);
+                self.active = self.sessions.len() - 1;
                 self.focus = AppWidgetName::DatabaseTree;
             }
+            Err(e) if db::is_auth_error(&e) => {
+                // @note: no message on the first prompt, the password was just missing
+                let message = config
+                    .password
+                    .take()
+                    .filter(|password| !password.is_empty())
+                    .map(|_| e.root_cause().to_string());
+
+                self.password = Some(Box::new(PasswordWidget::new(config, message)));
+                self.focus = AppWidgetName::Password;
+            }
+            Err(e) => self.show_error(&e.context(format!("failed to connect to {config}"))),
+        }
+    }
+
+    // @note: asks before `data` drops open transactions, `false` when there are none
+    fn confirm_rollback(&mut self, data: AppWidgetData) -> bool {
+        let sessions = match data {
+            AppWidgetData::Exit => self.sessions.iter().collect(),
+            _ => self
+                .sessions
+                .get(self.active)
+                .into_iter()
+                .collect::<Vec<_>>(),
         };
 
+        let names = sessions
+            .into_iter()
+            .filter(|session| session.tx_state().is_some())
+            .map(Session::conn_name)
+            .collect::<Vec<_>>();
+
+        if names.is_empty() {
+            return false;
+        }
+
+        let message = format!("Open transaction on {}, roll it back?", names.join(", "));
+        self.confirm = Some(Box::new(ConfirmWidget::new(message, data, self.focus)));
+
+        true
+    }
+
+    fn show_error(&mut self, e: &anyhow::Error) {
+        self.error = Some(Box::new(ErrorWidget::new(e, self.focus)));
+    }
+
+    fn receive(&mut self, message: AppMessage) {
+        match message {
+            AppMessage::Table(id, table, message) => {
+                if let Some(session) = self.sessions.iter_mut().find(|s| s.id == id) {
+                    session.receive_table(&table, message);
+                }
+            }
+            AppMessage::Exported(id, path, rows) => {
+                if let Some(session) = self.sessions.iter_mut().find(|s| s.id == id) {
+                    match rows {
+                        Ok(rows) => session
+                            .results
+                            .set_message(format!("exported {rows} rows to {}", path.display())),
+                        Err(e) => session.results.set_message(e),
+                    }
+                }
+            }
+            AppMessage::Import(id, table, message) => {
+                if let Some(session) = self.sessions.iter_mut().find(|s| s.id == id) {
+                    session.receive_import(&table, message);
+                }
+            }
+            AppMessage::Tree(id, item, message) => self.receive_tree(id, &item, message),
+            AppMessage::Query(id, queried) => self.queried(id, *queried),
+            AppMessage::Cancellable(id, started, cancel) => {
+                let running = self
+                    .sessions
+                    .iter_mut()
+                    .find(|s| s.id == id)
+                    .and_then(|s| s.running.as_mut())
+                    .filter(|running| running.started == started);
+
+                if let Some(running) = running {
+                    running.cancel = Some(cancel);
+                }
+            }
+            AppMessage::Explained(id, explained) => self.explained(id, *explained),
+            AppMessage::History(entry) => self.record(entry),
+            AppMessage::Transaction(id, tx) => {
+                if let Some(session) = self.sessions.iter_mut().find(|s| s.id == id) {
+                    session.running = None;
+                    session.tx = Some(tx);
+                }
+            }
+            AppMessage::Controlled(id, controlled) => self.controlled(id, *controlled),
+            AppMessage::Connected(started, config, session) => {
+                self.connected(started, config, session);
+            }
+            AppMessage::Tested(info) => {
+                if let Some(conn_form) = self.conn_form.as_mut() {
+                    conn_form.set_tested(info);
+                }
+            }
+        }
+    }
+}
+
+impl Session {
+    fn new(id: usize, config: Box<ConnectionConfig>, opened: Opened) -> Self {
+        Self {
+            id,
+            config,
+            pool: opened.pool,
+            tree: opened.tree,
+            editor: Box::new(Editor::new_scratch()),
+            results: Box::default(),
+            table_data: None,
+            import: None,
+            snippets: None,
+            plan: None,
+            shown: None,
+            tx: None,
+            running: None,
+            focus: AppWidgetName::DatabaseTree,
+        }
+    }
+
+    fn color(&self) -> Color {
+        self.config
+            .color
+            .as_deref()
+            .and_then(|color| color.parse().ok())
+            .unwrap_or(PALETTE[self.id % PALETTE.len()])
+    }
+
+    fn input(&mut self, input: Input) -> AppEventOutcome {
+        match self.focus {
+            AppWidgetName::Editor => self.editor_input(input),
+            AppWidgetName::ResultGrid => self.results.input(input),
+            AppWidgetName::TableData => match self.table_data.as_mut() {
+                Some(table_data) => table_data.input(input),
+                None => EventOutcome::Ignore.into(),
+            },
+            AppWidgetName::Import => match self.import.as_mut() {
+                Some(import) => import.input(input),
+                None => EventOutcome::Ignore.into(),
+            },
+            AppWidgetName::Snippets => match self.snippets.as_mut() {
+                Some(snippets) => snippets.input(input),
+                None => EventOutcome::Ignore.into(),
+            },
+            AppWidgetName::Plan => match self.plan.as_mut() {
+                Some(plan) => plan.input(input),
+                None => EventOutcome::Ignore.into(),
+            },
+            _ => self.tree.input(input),
+        }
+    }
+
+    // @note: the panes in the order of Tab, with the open overlays
+    fn panes(&self) -> Vec<AppWidgetName> {
+        [
+            (AppWidgetName::DatabaseTree, true),
+            (AppWidgetName::Snippets, self.snippets.is_some()),
+            (AppWidgetName::Editor, true),
+            (AppWidgetName::ResultGrid, true),
+            (AppWidgetName::TableData, self.table_data.is_some()),
+            (AppWidgetName::Import, self.import.is_some()),
+            (AppWidgetName::Plan, self.plan.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(name, open)| open.then_some(name))
+        .collect()
+    }
+
+    fn is_typing(&self) -> bool {
+        self.focus == AppWidgetName::Editor && self.editor.is_insert()
+    }
+
+    fn editor_input(&mut self, input: Input) -> AppEventOutcome {
+        let Input {
+            event: InputEvent::Char(ch),
+            modifiers: Modifiers { ctr: true, .. },
+        } = input
+        else {
+            return self.editor.input(input).into();
+        };
+
+        match ch {
+            'e' => {
+                let sql = self
+                    .editor
+                    .selected_text()
+                    .unwrap_or_else(|| self.editor.text());
+
+                AppEventOutcome::Apply(AppWidgetData::Query(sql))
+            }
+            'g' => match self.editor.select_statement() {
+                Some(sql) => AppEventOutcome::Apply(AppWidgetData::Query(sql)),
+                None => EventOutcome::Ignore.into(),
+            },
+            // @note: EXPLAIN and EXPLAIN ANALYZE of the selection or the statement under the cursor
+            'x' | 'a' => match self
+                .editor
+                .selected_text()
+                .or_else(|| self.editor.select_statement())
+            {
+                Some(sql) => AppEventOutcome::Apply(AppWidgetData::Explain(sql, ch == 'a')),
+                None => EventOutcome::Ignore.into(),
+            },
+            'p' => AppEventOutcome::Apply(AppWidgetData::ShowHistory),
+            's' => {
+                let sql = self
+                    .editor
+                    .selected_text()
+                    .unwrap_or_else(|| self.editor.text());
+
+                AppEventOutcome::Apply(AppWidgetData::NewSnippet(sql))
+            }
+            'r' if !self.results.is_empty() => AppEventOutcome::Focus(AppWidgetName::ResultGrid),
+            _ => self.editor.input(input).into(),
+        }
+    }
+
+    fn fetch_table_page(
+        &mut self,
+        page: usize,
+        sender: &mpsc::UnboundedSender<AppMessage>,
+    ) -> anyhow::Result<()> {
+        let pool = self.pool.clone();
+        let id = self.id;
+        let recorder = self.recorder(sender);
+
+        let table_data = self.table_data.as_ref().context("no table opened")?;
+        let table = table_data.table().clone();
+        let info = table_data.info().context("table is not loaded")?.clone();
+
+        let sender = sender.clone();
+        self.spawn_with_tx(sender.clone(), |tx| async move {
+            let page = load_table_page(&pool, tx.as_ref(), &recorder, &table, &info, page).await;
+            let page = TableMessage::Page(page);
+            sender.send(AppMessage::Table(id, table, page)).ok();
+
+            tx
+        })
+    }
+
+    // @note: table views go through the open transaction, so they see its changes and don't
+    // wait on its locks, the task gets it like a query does and sends it back
+    fn spawn_with_tx<F>(
+        &mut self,
+        sender: mpsc::UnboundedSender<AppMessage>,
+        task: impl FnOnce(Option<Transaction>) -> F,
+    ) -> anyhow::Result<()>
+    where
+        F: Future<Output = Option<Transaction>> + Send + 'static,
+    {
+        if self.tx_state().is_some() {
+            self.ensure_idle()?;
+        }
+
+        let tx = self.tx.take();
+        let tx_state = tx.as_ref().map(Transaction::state);
+        let cancel = tx.as_ref().and_then(Transaction::cancel_handle);
+
+        let task = task(tx);
+        let id = self.id;
+
+        let task = tokio::spawn(async move {
+            if let Some(tx) = task.await {
+                sender.send(AppMessage::Transaction(id, tx)).ok();
+            }
+        });
+
+        if tx_state.is_some() {
+            self.running = Some(Running {
+                started: Instant::now(),
+                cancel,
+                tx_state,
+                task,
+            });
+        }
+
         Ok(())
     }
 
-    fn focused(&mut self) -> &mut Box<dyn DuzzyWidget<Outcome = AppEventOutcome>> {
-        self.widgets.get_mut(&self.focus).expect("should focus")
+    fn ensure_no_tx(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.tx_state().is_none(),
+            "the import commits on its own, finish the transaction first"
+        );
+        Ok(())
     }
+
+    fn receive_import(&mut self, table: &TableRef, message: ImportMessage) {
+        let Some(import) = self
+            .import
+            .as_mut()
+            .filter(|import| import.table() == table)
+        else {
+            return;
+        };
+
+        match message {
+            ImportMessage::Columns(Ok(columns)) => import.set_columns(columns),
+            ImportMessage::Columns(Err(e)) => import.set_error(e),
+            ImportMessage::Progress(progress) => import.set_progress(progress),
+            ImportMessage::Finished(summary) => import.set_finished(summary),
+        }
+    }
+
+    fn receive_table(&mut self, table: &TableRef, message: TableMessage) {
+        let Some(table_data) = self
+            .table_data
+            .as_mut()
+            .filter(|table_data| table_data.table() == table)
+        else {
+            return;
+        };
+
+        match message {
+            TableMessage::Info(Ok(info)) => table_data.set_info(info),
+            TableMessage::Page(Ok(page)) => table_data.set_page(page),
+            TableMessage::Count(Ok(count)) => table_data.set_count(count),
+            TableMessage::Info(Err(e)) | TableMessage::Page(Err(e)) => table_data.set_error(e),
+            TableMessage::Count(Err(e)) => table_data.set_commit_error(e),
+            TableMessage::Committed(Ok(affected)) => table_data.set_committed(affected),
+            TableMessage::Committed(Err(e)) => table_data.set_commit_error(e),
+        }
+    }
+
+    fn spawn_query(
+        &mut self,
+        sql: String,
+        values: Option<Vec<Option<String>>>,
+        sender: mpsc::UnboundedSender<AppMessage>,
+    ) -> anyhow::Result<()> {
+        let id = self.id;
+
+        self.spawn_running(sender.clone(), move |mut tx, conn| async move {
+            let result = match (tx.as_mut(), conn, values.as_ref()) {
+                (_, Err(e), _) => Err(e),
+                (Some(tx), _, Some(values)) => tx.fetch_params(&sql, values).await,
+                (Some(tx), _, None) => tx.fetch(&sql).await,
+                (None, Ok(Some(conn)), Some(values)) => conn.fetch_params(&sql, values).await,
+                (None, Ok(Some(conn)), None) => conn.fetch(&sql).await,
+                (None, Ok(None), _) => Err(anyhow::anyhow!("no connection")),
+            };
+
+            let tx = tx.filter(|tx| !tx.is_finished());
+            let queried = Box::new(Queried {
+                sql,
+                values,
+                result,
+                tx,
+            });
+            sender.send(AppMessage::Query(id, queried)).ok();
+        })
+    }
+
+    // @note: EXPLAIN ANALYZE runs the statement, so it can be cancelled like a query
+    fn spawn_explain(
+        &mut self,
+        sql: String,
+        analyze: bool,
+        sender: mpsc::UnboundedSender<AppMessage>,
+    ) -> anyhow::Result<()> {
+        let id = self.id;
+
+        self.spawn_running(sender.clone(), move |tx, conn| async move {
+            let result = match (tx.as_ref(), conn) {
+                (_, Err(e)) => Err(e),
+                (Some(tx), _) => tx.explain(&sql, analyze).await,
+                (None, Ok(Some(conn))) => Plan::fetch(conn.as_ref(), &sql, analyze, false).await,
+                (None, Ok(None)) => Err(anyhow::anyhow!("no connection")),
+            };
+
+            let tx = tx.filter(|tx| !tx.is_finished());
+            let explained = Box::new(Explained {
+                sql,
+                analyze,
+                result,
+                tx,
+            });
+            sender.send(AppMessage::Explained(id, explained)).ok();
+        })
+    }
+
+    // @note: on the pinned client while a transaction is open, on a client of its own
+    // otherwise, so it can be cancelled, the result comes as a message. The client is
+    // acquired by the task, a busy pool would hang the app, Ctrl-C aborts the wait
+    fn spawn_running<F>(
+        &mut self,
+        sender: mpsc::UnboundedSender<AppMessage>,
+        task: impl FnOnce(Option<Transaction>, anyhow::Result<Option<Box<dyn Connection>>>) -> F
+            + Send
+            + 'static,
+    ) -> anyhow::Result<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.ensure_idle()?;
+
+        let tx = self.tx.take();
+        let tx_state = tx.as_ref().map(Transaction::state);
+        let cancel = tx.as_ref().and_then(Transaction::cancel_handle);
+
+        let pool = self.pool.clone();
+        let id = self.id;
+        let started = Instant::now();
+
+        let task = tokio::spawn(async move {
+            let conn = match tx.is_none() {
+                true => pool.acquire().await.map(Some),
+                false => Ok(None),
+            };
+
+            if let Some(cancel) = conn
+                .as_ref()
+                .ok()
+                .and_then(|conn| conn.as_ref()?.cancel_handle())
+            {
+                sender
+                    .send(AppMessage::Cancellable(id, started, cancel))
+                    .ok();
+            }
+
+            task(tx, conn).await;
+        });
+
+        self.results.set_running(started);
+        self.running = Some(Running {
+            started,
+            cancel,
+            tx_state,
+            task,
+        });
+
+        Ok(())
+    }
+
+    // @note: BEGIN, COMMIT, ROLLBACK and SAVEPOINT wait on the server like the queries do,
+    // the transaction comes back with `Controlled`
+    fn spawn_control<F>(
+        &mut self,
+        tx_state: Option<TransactionState>,
+        cancel: Option<Box<dyn CancelHandle>>,
+        sender: mpsc::UnboundedSender<AppMessage>,
+        task: F,
+    ) where
+        F: Future<Output = Controlled> + Send + 'static,
+    {
+        let id = self.id;
+
+        let task = tokio::spawn(async move {
+            let controlled = Box::new(task.await);
+            sender.send(AppMessage::Controlled(id, controlled)).ok();
+        });
+
+        self.running = Some(Running {
+            started: Instant::now(),
+            cancel,
+            tx_state,
+            task,
+        });
+    }
+
+    fn ensure_idle(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.running.is_none(),
+            "a query is running, Ctrl-C cancels it"
+        );
+        Ok(())
+    }
+
+    const fn tx_state(&self) -> Option<TransactionState> {
+        match (self.tx.as_ref(), self.running.as_ref()) {
+            (Some(tx), _) => Some(tx.state()),
+            (None, Some(running)) => running.tx_state,
+            (None, None) => None,
+        }
+    }
+
+    fn recorder(&self, sender: &mpsc::UnboundedSender<AppMessage>) -> Recorder {
+        Recorder {
+            sender: sender.clone(),
+            conn: self.config.to_string(),
+        }
+    }
+
+    // @note: the folder of the snippets of the connection
+    fn conn_name(&self) -> String {
+        self.config
+            .name
+            .clone()
+            .unwrap_or_else(|| self.config.to_string())
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, layout: &PaneLayout) {
+        for (name, pane) in layout.split(area, self.focus) {
+            match name {
+                AppWidgetName::TableData => {
+                    if let Some(table_data) = self.table_data.as_mut() {
+                        table_data.render(pane, buf);
+                    }
+                }
+                AppWidgetName::Import => {
+                    if let Some(import) = self.import.as_mut() {
+                        import.render(pane, buf);
+                    }
+                }
+                AppWidgetName::Snippets => {
+                    if let Some(snippets) = self.snippets.as_mut() {
+                        snippets.render(pane, buf);
+                    }
+                }
+                AppWidgetName::Plan => {
+                    if let Some(plan) = self.plan.as_mut() {
+                        plan.render(pane, buf);
+                    }
+                }
+                AppWidgetName::Editor => {
+                    let block = Block::default()
+                        .title("Editor")
+                        .borders(Borders::ALL)
+                        .fg(colors::ENERGY_YELLOW);
+
+                    let inner = block.inner(pane);
+                    block.render(pane, buf);
+                    self.editor.render(inner, buf);
+                }
+                AppWidgetName::ResultGrid => self.results.render(pane, buf),
+                _ => self.tree.render(pane, buf),
+            }
+
+            // @note: dim the panes without focus
+            if name != self.focus {
+                buf.set_style(pane, Style::default().dim());
+            }
+        }
+    }
+}
+
+async fn open_session(config: &mut ConnectionConfig) -> anyhow::Result<Opened> {
+    config.password = db::resolve_password(config).await?;
+
+    let pool = db::connect(config).await?;
+    let tree = DbTreeWidget::new(&pool).await?;
+
+    Ok(Opened {
+        pool,
+        tree: Box::new(tree),
+    })
+}
+
+async fn test_connection(config: ConnectionConfig) -> anyhow::Result<ServerInfo> {
+    let mut config = config.resolve()?;
+    config.password = db::resolve_password(&config).await?;
+
+    db::ping(&config).await
+}
+
+async fn load_table_info(
+    pool: &DbPool,
+    tx: Option<&Transaction>,
+    table: &TableRef,
+) -> anyhow::Result<TableInfo> {
+    match tx {
+        Some(tx) => tx.table_info(table).await,
+        None => pool.acquire().await?.table_info(table).await,
+    }
+}
+
+async fn count_table_rows(
+    pool: &DbPool,
+    tx: Option<&Transaction>,
+    recorder: &Recorder,
+    table: &TableRef,
+) -> anyhow::Result<RowCount> {
+    let sql = table.count_query(pool.kind());
+    let started = Instant::now();
+
+    let count = match tx {
+        Some(tx) => tx.count_rows(table).await,
+        None => table.count(pool.acquire().await?.as_ref()).await,
+    };
+
+    let rows = count.as_ref().map(|_| 1);
+    recorder.record(&sql, started, rows);
+    count
+}
+
+async fn load_children(pool: &DbPool, item: &Arc<TreeItem>) -> anyhow::Result<Vec<Arc<TreeItem>>> {
+    pool.acquire().await?.children(item).await
+}
+
+async fn load_ddl(pool: &DbPool, item: &Arc<TreeItem>) -> anyhow::Result<String> {
+    pool.acquire().await?.ddl(item).await
+}
+
+async fn load_table_columns(pool: &DbPool, table: &TableRef) -> anyhow::Result<Vec<Column>> {
+    pool.acquire().await?.table_columns(table).await
+}
+
+async fn load_table_page(
+    pool: &DbPool,
+    tx: Option<&Transaction>,
+    recorder: &Recorder,
+    table: &TableRef,
+    info: &TableInfo,
+    page: usize,
+) -> anyhow::Result<TablePage> {
+    let sql = table.page_query(pool.kind(), info, page, PAGE_SIZE);
+    let started = Instant::now();
+
+    let page = match tx {
+        Some(tx) => tx.fetch_page(table, info, page, PAGE_SIZE).await,
+        None => {
+            let conn = pool.acquire().await?;
+            table.fetch_page(conn.as_ref(), info, page, PAGE_SIZE).await
+        }
+    };
+
+    let rows = page.as_ref().map(|page| page.result.rows.len() as u64);
+    recorder.record(&sql, started, rows);
+    page
+}
+
+// @note: the statement is run again on a connection of its own and streamed to the file,
+// the rows of the grid are written for the drivers without row streams
+async fn export_query(
+    pool: &DbPool,
+    shown: Option<(String, Option<Vec<Option<String>>>)>,
+    recorder: &Recorder,
+    result: Arc<QueryResult>,
+    format: ExportFormat,
+    path: &Path,
+) -> anyhow::Result<u64> {
+    if let Some((sql, values)) = shown {
+        let conn = pool.acquire().await?;
+        let mut out = std::io::BufWriter::new(std::fs::File::create(path)?);
+        let started = Instant::now();
+
+        if let Some(rows) = conn
+            .export(&sql, values.as_deref(), format.clone(), &mut out)
+            .await
+        {
+            recorder.record(&sql, started, rows.as_ref().copied());
+            return rows;
+        }
+    }
+
+    let path = path.to_owned();
+    tokio::task::spawn_blocking(move || export_result(&result, format, &path)).await?
+}
+
+fn export_result(result: &QueryResult, format: ExportFormat, path: &Path) -> anyhow::Result<u64> {
+    let file = std::fs::File::create(path)?;
+    Ok(db::export(result, format, std::io::BufWriter::new(file))?)
+}
+
+async fn commit_edits(
+    pool: &DbPool,
+    tx: Option<&Transaction>,
+    recorder: &Recorder,
+    edits: &EditBatch,
+) -> anyhow::Result<u64> {
+    let started = Instant::now();
+
+    let (sql, affected) = match tx {
+        Some(tx) => (edits.statements().join("\n"), tx.commit_edits(edits).await),
+        None => {
+            let conn = pool.acquire().await?;
+            (edits.preview(), edits.commit(conn.as_ref()).await)
+        }
+    };
+
+    recorder.record(&sql, started, affected.as_ref().copied());
+    affected
+}
+
+async fn import_csv(
+    pool: &DbPool,
+    id: usize,
+    import: &CsvImport,
+    sender: &mpsc::UnboundedSender<AppMessage>,
+    recorder: &Recorder,
+) -> anyhow::Result<ImportSummary> {
+    let conn = pool.acquire().await?;
+    let started = Instant::now();
+
+    let summary = import
+        .run(conn.as_ref(), |progress| {
+            let progress = ImportMessage::Progress(progress);
+            sender
+                .send(AppMessage::Import(id, import.table.clone(), progress))
+                .ok();
+        })
+        .await;
+
+    let rows = summary.as_ref().map(|summary| summary.rows);
+    recorder.record(&import.statement(pool.kind()), started, rows);
+    summary
 }
 
 impl Widget for &mut App {
@@ -104,11 +1796,98 @@ impl Widget for &mut App {
     where
         Self: Sized,
     {
-        // @note: draw widgets based on currently focused one
-        if self.focus == AppWidgetName::Editor {
-            self.editor.render(area, buf);
+        self.render_focused(area, buf);
+
+        if let Some(confirm) = self.confirm.as_mut() {
+            confirm.render(area, buf);
+        }
+
+        if let Some(error) = self.error.as_mut() {
+            error.render(area, buf);
+        }
+    }
+}
+
+impl App {
+    fn render_focused(&mut self, area: Rect, buf: &mut Buffer) {
+        let area = if self.sessions.is_empty() {
+            area
         } else {
-            self.focused().render(area, buf);
+            let [tabs_area, area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+            self.render_tabs(tabs_area, buf);
+            area
+        };
+
+        let shows_session = self.shows_session();
+
+        // @note: the password and the form are drawn over the connection list
+        let popup: Option<&mut dyn DuzzyWidget<Outcome = AppEventOutcome>> = match self.focus {
+            AppWidgetName::Password => self.password.as_deref_mut().map(|w| w as _),
+            AppWidgetName::ConnectionForm => self.conn_form.as_deref_mut().map(|w| w as _),
+            AppWidgetName::History => self.history_picker.as_deref_mut().map(|w| w as _),
+            AppWidgetName::Prompt => self.prompt.as_deref_mut().map(|w| w as _),
+            _ => None,
+        };
+
+        let session = self.sessions.get_mut(self.active);
+
+        match session {
+            Some(session) if shows_session => session.render(area, buf, &self.layout),
+            _ => self.conn_list.render(area, buf),
+        }
+
+        if let Some(popup) = popup {
+            popup.render(area, buf);
+        }
+    }
+
+    // @note: the history and the prompts are drawn over the tab
+    const fn shows_session(&self) -> bool {
+        self.focus.is_session()
+            || matches!(self.focus, AppWidgetName::History | AppWidgetName::Prompt)
+    }
+
+    fn render_tabs(&self, area: Rect, buf: &mut Buffer) {
+        let titles = self
+            .sessions
+            .iter()
+            .enumerate()
+            .map(|(i, session)| {
+                Line::from(format!("{} {}", i + 1, session.config)).fg(session.color())
+            })
+            .chain(std::iter::once(Line::from("0 +")));
+
+        let selected = if self.shows_session() {
+            self.active
+        } else {
+            self.sessions.len()
+        };
+
+        // @note: the state of the transaction of the active tab on the right
+        let state = self
+            .sessions
+            .get(self.active)
+            .filter(|_| self.shows_session())
+            .and_then(Session::tx_state)
+            .map(|state| match state {
+                TransactionState::Active => Line::from(" IN TRANSACTION ").black().on_yellow(),
+                TransactionState::Failed => Line::from(" FAILED TRANSACTION ").white().on_red(),
+            });
+
+        let [tabs_area, state_area] = Layout::horizontal([
+            Constraint::Min(0),
+            Constraint::Length(state.as_ref().map_or(0, |state| state.width() as u16)),
+        ])
+        .areas(area);
+
+        Tabs::new(titles)
+            .select(selected)
+            .highlight_style(Style::default().reversed())
+            .render(tabs_area, buf);
+
+        if let Some(state) = state {
+            state.render(state_area, buf);
         }
     }
 }
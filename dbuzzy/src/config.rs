@@ -1,19 +1,181 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
 use serde::Deserialize;
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table};
 
-use crate::db::ConnectionConfig;
+use crate::db::{ConnectionConfig, DriverKind, ExportConfig, SslMode};
 
 #[derive(Debug, Default, Deserialize)]
 pub struct Config {
+    // @note: as written in the file, `url` is resolved on connect
     pub conn: Vec<ConnectionConfig>,
+    #[serde(default)]
+    pub export: ExportConfig,
 }
 
 impl Config {
     pub fn from_toml() -> anyhow::Result<Self> {
+        let config: Self = duzzy_lib::read_toml(Self::path()?)?;
+
+        for conn in &config.conn {
+            conn.clone().resolve()?;
+        }
+
+        Ok(config)
+    }
+
+    // @note: `None` appends a new connection
+    pub fn save_conn(
+        &mut self,
+        index: Option<usize>,
+        conn: ConnectionConfig,
+    ) -> anyhow::Result<usize> {
+        let path = Self::path()?;
+        let content = upsert_conn(&read_config(&path)?, index, &conn)?;
+        std::fs::write(&path, content)?;
+
+        match index {
+            Some(index) => {
+                *self.conn.get_mut(index).context("no such connection")? = conn;
+                Ok(index)
+            }
+            None => {
+                self.conn.push(conn);
+                Ok(self.conn.len() - 1)
+            }
+        }
+    }
+
+    pub fn remove_conn(&mut self, index: usize) -> anyhow::Result<()> {
+        anyhow::ensure!(index < self.conn.len(), "no such connection");
+
+        let path = Self::path()?;
+        let content = remove_conn(&read_config(&path)?, index)?;
+        std::fs::write(&path, content)?;
+
+        self.conn.remove(index);
+        Ok(())
+    }
+
+    fn path() -> anyhow::Result<PathBuf> {
         let mut path = duzzy_lib::ensure_config_dir(std::env!("CARGO_PKG_NAME"))?;
         path.push("config");
         path.set_extension("toml");
+        Ok(path)
+    }
+}
+
+fn read_config(path: &PathBuf) -> anyhow::Result<String> {
+    match std::fs::read_to_string(path) {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        content => Ok(content?),
+    }
+}
+
+// @note: `toml_edit` keeps the comments and the layout of the file
+fn upsert_conn(
+    content: &str,
+    index: Option<usize>,
+    conn: &ConnectionConfig,
+) -> anyhow::Result<String> {
+    let mut doc = content.parse::<DocumentMut>()?;
+
+    let conns = doc
+        .entry("conn")
+        .or_insert(Item::ArrayOfTables(ArrayOfTables::new()))
+        .as_array_of_tables_mut()
+        .context("`conn` should be an array of tables")?;
+
+    let table = match index {
+        Some(index) => conns.get_mut(index).context("no such connection")?,
+        None => {
+            conns.push(Table::new());
+            conns.iter_mut().last().context("no such connection")?
+        }
+    };
+
+    write_conn(table, conn);
+
+    Ok(doc.to_string())
+}
+
+fn remove_conn(content: &str, index: usize) -> anyhow::Result<String> {
+    let mut doc = content.parse::<DocumentMut>()?;
+
+    let conns = doc
+        .get_mut("conn")
+        .and_then(Item::as_array_of_tables_mut)
+        .filter(|conns| index < conns.len())
+        .context("no such connection")?;
+
+    conns.remove(index);
 
-        duzzy_lib::read_toml(path)
+    Ok(doc.to_string())
+}
+
+// @note: only the fields of the form, the rest of the table is kept as is
+fn write_conn(table: &mut Table, conn: &ConnectionConfig) {
+    let url_key = if table.contains_key("dsn") {
+        "dsn"
+    } else {
+        "url"
+    };
+
+    set_key(table, "name", conn.name.as_deref().map(Into::into));
+    set_key(table, "color", conn.color.as_deref().map(Into::into));
+    set_key(table, url_key, conn.url.as_deref().map(Into::into));
+    set_key(
+        table,
+        "driver",
+        (conn.driver != DriverKind::default()).then(|| conn.driver.scheme().into()),
+    );
+    set_key(
+        table,
+        "host",
+        Some(conn.host.as_str())
+            .filter(|h| !h.is_empty())
+            .map(Into::into),
+    );
+    set_key(table, "port", conn.port.map(|port| i64::from(port).into()));
+    set_key(table, "dbname", conn.dbname.as_deref().map(Into::into));
+    set_key(
+        table,
+        "user",
+        Some(conn.user.as_str())
+            .filter(|u| !u.is_empty())
+            .map(Into::into),
+    );
+    set_key(
+        table,
+        "path",
+        conn.path
+            .as_ref()
+            .map(|path| path.to_string_lossy().as_ref().into()),
+    );
+    set_key(
+        table,
+        "sslmode",
+        (conn.sslmode != SslMode::default()).then(|| conn.sslmode.as_str().into()),
+    );
+}
+
+fn set_key(table: &mut Table, key: &str, value: Option<toml_edit::Value>) {
+    match value {
+        Some(value) => match table.get_mut(key).and_then(Item::as_value_mut) {
+            // @note: keeps the comments around the value
+            Some(old) => {
+                let decor = old.decor().clone();
+                *old = value;
+                *old.decor_mut() = decor;
+            }
+            None => {
+                table.insert(key, toml_edit::value(value));
+            }
+        },
+        None => {
+            table.remove(key);
+        }
     }
 }
 
@@ -21,7 +183,8 @@ impl Config {
 mod tests {
     use std::io::Write;
 
-    use super::Config;
+    use super::*;
+    use crate::db::CsvQuote;
 
     #[test]
     fn test_from_toml() -> anyhow::Result<()> {
@@ -31,6 +194,28 @@ mod tests {
             host = "localhost"
             port = 5432
             dbname = "bar"
+            sslmode = "verify-full"
+            sslrootcert = "/tmp/root.crt"
+            statement_timeout = 5000
+
+            [conn.ssh]
+            host = "bastion.local"
+            user = "deploy"
+            key = "~/.ssh/id_ed25519"
+            jump = ["edge.local"]
+
+            [[conn]]
+            driver = "sqlite"
+            path = "/tmp/bar.db"
+
+            [[conn]]
+            name = "baz"
+            color = "cyan"
+            url = "postgres://foo@localhost/baz?sslmode=disable"
+
+            [export]
+            delimiter = ";"
+            quote = "non_numeric"
         "#;
 
         let mut filepath = duzzy_lib::ensure_config_dir(std::env!("CARGO_PKG_NAME"))?;
@@ -43,16 +228,96 @@ mod tests {
             writer.write_all(content.as_bytes())?;
         }
 
-        let cfg: Config = duzzy_lib::read_toml(&filepath)?;
+        let mut cfg: Config = duzzy_lib::read_toml(&filepath)?;
 
-        assert_eq!(cfg.conn.len(), 1);
+        assert_eq!(cfg.conn.len(), 3);
         assert_eq!(&cfg.conn[0].host, "localhost");
         assert_eq!(&cfg.conn[0].user, "foo");
-        assert_eq!(cfg.conn[0].port, 5432);
+        assert_eq!(cfg.conn[0].port, Some(5432));
         assert_eq!(cfg.conn[0].dbname.as_deref(), Some("bar"));
+        assert_eq!(cfg.conn[0].driver, DriverKind::Postgres);
+        assert_eq!(cfg.conn[0].sslmode, SslMode::VerifyFull);
+        assert_eq!(cfg.conn[0].statement_timeout, Some(5000));
+
+        let ssh = cfg.conn[0].ssh.as_ref().unwrap();
+        assert_eq!(&ssh.host, "bastion.local");
+        assert_eq!(ssh.user.as_deref(), Some("deploy"));
+        assert_eq!(ssh.jump, vec!["edge.local".to_owned()]);
+        assert!(cfg.conn[1].ssh.is_none());
+        assert_eq!(cfg.conn[1].sslmode, SslMode::Prefer);
+        assert_eq!(cfg.conn[1].driver, DriverKind::Sqlite);
+        assert_eq!(cfg.conn[1].to_string(), "sqlite:///tmp/bar.db");
+
+        let conn = cfg.conn.pop().unwrap().resolve()?;
+        assert_eq!(conn.to_string(), "baz/baz");
+        assert_eq!(conn.color.as_deref(), Some("cyan"));
+        assert_eq!(conn.sslmode, SslMode::Disable);
+
+        assert_eq!(cfg.export.delimiter, ';');
+        assert_eq!(cfg.export.quote, CsvQuote::NonNumeric);
 
         std::fs::remove_file(filepath)?;
 
         Ok(())
     }
+
+    #[test]
+    fn test_edit_conn() -> anyhow::Result<()> {
+        let content = r#"# my connections
+
+# local one
+[[conn]]
+name = "local" # dev
+host = "localhost"
+user = "foo"
+
+[conn.ssh]
+host = "bastion"
+
+[[conn]]
+url = "postgres://bar@db/bar"
+
+[export]
+delimiter = ";"
+"#;
+
+        let mut conn = ConnectionConfig {
+            name: Some("renamed".to_owned()),
+            host: "db.local".to_owned(),
+            port: Some(5433),
+            sslmode: SslMode::Require,
+            ..Default::default()
+        };
+
+        let content = upsert_conn(content, Some(0), &conn)?;
+        assert!(content.starts_with("# my connections\n\n# local one\n[[conn]]\n"));
+        assert!(content.contains("name = \"renamed\" # dev\n"));
+        assert!(content.contains("port = 5433\n"));
+        assert!(content.contains("sslmode = \"require\"\n"));
+        assert!(!content.contains("user = \"foo\""));
+        assert!(content.contains("[conn.ssh]\nhost = \"bastion\"\n"));
+
+        conn.name = Some("new".to_owned());
+        conn.driver = DriverKind::Sqlite;
+        conn.path = Some("/tmp/new.db".into());
+
+        let content = upsert_conn(&content, None, &conn)?;
+        let content = remove_conn(&content, 1)?;
+
+        let cfg: Config = toml::from_str(&content)?;
+        assert_eq!(cfg.conn.len(), 2);
+        assert_eq!(cfg.conn[0].name.as_deref(), Some("renamed"));
+        assert_eq!(
+            cfg.conn[0].ssh.as_ref().map(|s| s.host.as_str()),
+            Some("bastion")
+        );
+        assert_eq!(cfg.conn[1].name.as_deref(), Some("new"));
+        assert_eq!(cfg.conn[1].driver, DriverKind::Sqlite);
+        assert_eq!(cfg.export.delimiter, ';');
+
+        assert!(remove_conn(&content, 2).is_err());
+        assert!(upsert_conn("", None, &conn)?.starts_with("[[conn]]\n"));
+
+        Ok(())
+    }
 }
@@ -1,63 +1,113 @@
-use deadpool_postgres::Runtime;
+use std::{net::IpAddr, path::PathBuf};
+
+use anyhow::Context;
 use serde::Deserialize;
 
-#[derive(Debug, Deserialize)]
+use super::{driver::DriverKind, ssh::SshConfig};
+
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct ConnectionConfig {
     pub name: Option<String>,
+    // @note: tab colour, a name like `cyan` or `#rrggbb`
+    pub color: Option<String>,
+    // @note: a connection url or a libpq `key=value` string, its options win over the fields
+    #[serde(alias = "dsn")]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub driver: DriverKind,
+    #[serde(default)]
     pub host: String,
-    pub port: u16,
+    // @note: ip to connect to, while `host` is still used for TLS
+    pub hostaddr: Option<IpAddr>,
+    pub port: Option<u16>,
     pub dbname: Option<String>,
+    #[serde(default)]
     pub user: String,
     pub password: Option<String>,
+    // @note: name of the env var with the password, `PGPASSWORD` or `MYSQL_PWD` by default
+    pub password_env: Option<String>,
+    // @note: shell command, that prints the password
+    pub password_command: Option<String>,
+    // @note: database file of file based drivers
+    pub path: Option<PathBuf>,
+    #[serde(default)]
+    pub sslmode: SslMode,
+    pub sslrootcert: Option<PathBuf>,
+    pub sslcert: Option<PathBuf>,
+    pub sslkey: Option<PathBuf>,
+    // @note: seconds
+    pub connect_timeout: Option<u64>,
+    // @note: milliseconds, set on every connection taken from the pool
+    pub statement_timeout: Option<u64>,
+    // @note: shown by the server in `pg_stat_activity`, the name by default
+    pub application_name: Option<String>,
+    pub pool_size: Option<usize>,
+    pub ssh: Option<SshConfig>,
 }
 
-impl std::fmt::Display for ConnectionConfig {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut name = self.name.as_ref().map_or_else(
-            || format!("postgres://{}:@{}:{}", &self.user, &self.host, self.port),
-            |n| n.to_owned(),
-        );
+impl ConnectionConfig {
+    pub fn from_url(url: &str) -> anyhow::Result<Self> {
+        Self {
+            url: Some(url.to_owned()),
+            ..Default::default()
+        }
+        .resolve()
+    }
 
-        if let Some(db_name) = self.dbname.as_ref() {
-            name = format!("{}/{}", name, db_name);
+    // @note: fills the fields from `url`, that is consumed, so a prompted password is kept
+    pub fn resolve(mut self) -> anyhow::Result<Self> {
+        if let Some(url) = self.url.take() {
+            super::url::apply(&mut self, &url)
+                .with_context(|| format!("invalid connection url `{url}`"))?;
         }
 
-        write!(f, "{}", name)
+        Ok(self)
     }
 }
 
-// @todo: `postgres` feature
-impl From<&ConnectionConfig> for deadpool_postgres::Config {
-    fn from(conf: &ConnectionConfig) -> Self {
-        Self {
-            user: Some(conf.user.to_owned()),
-            password: conf.password.to_owned(),
-            dbname: conf.dbname.to_owned(),
-            application_name: conf.name.to_owned(),
-            host: Some(conf.host.to_owned()),
-            port: Some(conf.port),
-            connect_timeout: Some(std::time::Duration::from_secs(5)),
-            keepalives: Some(true),
-            ..Default::default()
+// @note: same modes and default as libpq
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SslMode {
+    Disable,
+    #[default]
+    Prefer,
+    Require,
+    VerifyCa,
+    VerifyFull,
+}
+
+impl SslMode {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Disable => "disable",
+            Self::Prefer => "prefer",
+            Self::Require => "require",
+            Self::VerifyCa => "verify-ca",
+            Self::VerifyFull => "verify-full",
         }
     }
 }
 
-#[derive(Clone)]
-pub struct PgPool {
-    inner: deadpool_postgres::Pool,
-}
+impl std::fmt::Display for ConnectionConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let scheme = self.driver.scheme();
 
-impl PgPool {
-    pub fn create(config: &ConnectionConfig) -> super::DbResult<Self> {
-        let pg_conf: deadpool_postgres::Config = config.into();
-        let pool = pg_conf.create_pool(Some(Runtime::Tokio1), tokio_postgres::NoTls)?;
+        let mut name = self.name.as_ref().map_or_else(
+            || match self.path.as_ref() {
+                Some(path) => format!("{scheme}://{}", path.display()),
+                None => {
+                    let port = self.port.map(|p| format!(":{p}")).unwrap_or_default();
+                    format!("{scheme}://{}:@{}{port}", &self.user, &self.host)
+                }
+            },
+            |n| n.to_owned(),
+        );
 
-        Ok(Self { inner: pool })
-    }
+        if let Some(db_name) = self.dbname.as_ref() {
+            name = format!("{}/{}", name, db_name);
+        }
 
-    pub async fn acquire(&self) -> super::DbResult<deadpool_postgres::Client> {
-        let client = self.inner.get().await?;
-        Ok(client)
+        write!(f, "{}", name)
     }
 }
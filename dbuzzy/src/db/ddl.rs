@@ -0,0 +1,649 @@
+use anyhow::Context;
+use deadpool_postgres::{Client, GenericClient};
+
+use super::{
+    quote_ident, quote_literal,
+    tree::{TreeGroup, TreeItem, TreeItemKind},
+    TableRef,
+};
+
+// @note: rebuilds the CREATE statement of a tree node from the catalogs
+pub async fn object_ddl(client: &Client, item: &TreeItem) -> anyhow::Result<String> {
+    let schema = item
+        .schema_name()
+        .with_context(|| format!("no DDL for {}", item.name))?;
+    let object = TableRef::new(schema.to_owned(), item.name.to_owned());
+
+    match &item.kind {
+        TreeItemKind::Schema { .. } => Ok(format!("CREATE SCHEMA {};", quote_ident(schema))),
+        TreeItemKind::Table { .. } => table_ddl(client, &object).await,
+        TreeItemKind::Column { group, .. } => {
+            let table = group.parent().context("column should have a table")?;
+            let table = TableRef::new(schema.to_owned(), table.name.to_owned());
+
+            let column = column_ddls(client, &table.qualified_name())
+                .await?
+                .into_iter()
+                .find(|column| column.name == item.name)
+                .with_context(|| format!("{table} has no column {}", item.name))?;
+
+            Ok(format!(
+                "ALTER TABLE {} ADD COLUMN {};",
+                table.qualified_name(),
+                column.definition()
+            ))
+        }
+        TreeItemKind::Object { definition, group } => {
+            let kind = group.group_kind().context("object should be in a group")?;
+            let table = || {
+                let table = group.parent().context("object should have a table")?;
+                anyhow::Ok(TableRef::new(schema.to_owned(), table.name.to_owned()))
+            };
+
+            match kind {
+                TreeGroup::Views => view_ddl(client, &object, false).await,
+                TreeGroup::MaterializedViews => view_ddl(client, &object, true).await,
+                TreeGroup::Functions => function_ddl(client, schema, &item.name).await,
+                TreeGroup::Sequences => sequence_ddl(client, &object).await,
+                TreeGroup::Types => type_ddl(client, &object).await,
+                TreeGroup::Extensions => Ok(format!(
+                    "CREATE EXTENSION IF NOT EXISTS {} WITH SCHEMA {} VERSION {};",
+                    quote_ident(&item.name),
+                    quote_ident(schema),
+                    quote_literal(definition)
+                )),
+                // @note: the tree already holds `pg_get_*def` of table level objects
+                TreeGroup::Indexes | TreeGroup::Triggers => Ok(format!("{definition};")),
+                TreeGroup::Constraints | TreeGroup::ForeignKeys => Ok(format!(
+                    "ALTER TABLE {} ADD CONSTRAINT {} {definition};",
+                    table()?.qualified_name(),
+                    quote_ident(&item.name)
+                )),
+                TreeGroup::Tables | TreeGroup::Columns => {
+                    anyhow::bail!("unexpected object in {}", kind.as_ref())
+                }
+            }
+        }
+        TreeItemKind::Database | TreeItemKind::Group { .. } => {
+            anyhow::bail!("no DDL for {}", item.name)
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct ColumnDdl {
+    name: String,
+    data_type: String,
+    collation: Option<String>,
+    nullable: bool,
+    default: Option<String>,
+    // @note: 'a' always, 'd' by default
+    identity: Option<char>,
+    generated: bool,
+    comment: Option<String>,
+}
+
+impl ColumnDdl {
+    fn definition(&self) -> String {
+        let mut definition = format!("{} {}", quote_ident(&self.name), self.data_type);
+
+        if let Some(collation) = self.collation.as_deref() {
+            definition.push_str(&format!(" COLLATE {}", quote_ident(collation)));
+        }
+
+        match (self.default.as_deref(), self.identity) {
+            (Some(expr), _) if self.generated => {
+                definition.push_str(&format!(" GENERATED ALWAYS AS ({expr}) STORED"));
+            }
+            (_, Some('a')) => definition.push_str(" GENERATED ALWAYS AS IDENTITY"),
+            (_, Some('d')) => definition.push_str(" GENERATED BY DEFAULT AS IDENTITY"),
+            (Some(expr), _) => definition.push_str(&format!(" DEFAULT {expr}")),
+            _ => (),
+        }
+
+        if !self.nullable {
+            definition.push_str(" NOT NULL");
+        }
+
+        definition
+    }
+}
+
+#[derive(Debug, Default)]
+struct TableDdl {
+    name: String,
+    columns: Vec<ColumnDdl>,
+    // @note: (name, definition) pairs
+    constraints: Vec<(String, String)>,
+    partition_key: Option<String>,
+    // @note: (parent, bound) of a partition
+    partition_of: Option<(String, String)>,
+    indexes: Vec<String>,
+    triggers: Vec<String>,
+    comment: Option<String>,
+}
+
+impl TableDdl {
+    fn to_sql(&self) -> String {
+        let mut sql = match &self.partition_of {
+            Some((parent, bound)) => {
+                format!("CREATE TABLE {} PARTITION OF {parent} {bound}", self.name)
+            }
+            None => {
+                let lines = self
+                    .columns
+                    .iter()
+                    .map(ColumnDdl::definition)
+                    .chain(self.constraints.iter().map(|(name, definition)| {
+                        format!("CONSTRAINT {} {definition}", quote_ident(name))
+                    }))
+                    .map(|line| format!("    {line}"))
+                    .collect::<Vec<_>>();
+
+                format!("CREATE TABLE {} (\n{}\n)", self.name, lines.join(",\n"))
+            }
+        };
+
+        if let Some(key) = self.partition_key.as_deref() {
+            sql.push_str(&format!(" PARTITION BY {key}"));
+        }
+        sql.push(';');
+
+        let mut statements = vec![sql];
+        statements.extend(self.indexes.iter().map(|index| format!("{index};")));
+        statements.extend(self.triggers.iter().map(|trigger| format!("{trigger};")));
+
+        if let Some(comment) = self.comment.as_deref() {
+            statements.push(format!(
+                "COMMENT ON TABLE {} IS {};",
+                self.name,
+                quote_literal(comment)
+            ));
+        }
+
+        statements.extend(self.columns.iter().filter_map(|column| {
+            Some(format!(
+                "COMMENT ON COLUMN {}.{} IS {};",
+                self.name,
+                quote_ident(&column.name),
+                quote_literal(column.comment.as_deref()?)
+            ))
+        }));
+
+        statements.join("\n\n")
+    }
+}
+
+async fn table_ddl(client: &Client, table: &TableRef) -> anyhow::Result<String> {
+    let qualified_name = table.qualified_name();
+
+    let stmt = client
+        .prepare_cached(
+            r#"
+                SELECT
+                    c.relkind::text AS kind,
+                    pg_get_partkeydef(c.oid) AS partition_key,
+                    (
+                        SELECT quote_ident(pn.nspname) || '.' || quote_ident(p.relname)
+                        FROM pg_inherits i
+                        JOIN pg_class p ON p.oid = i.inhparent
+                        JOIN pg_namespace pn ON pn.oid = p.relnamespace
+                        WHERE c.relispartition AND i.inhrelid = c.oid
+                    ) AS parent,
+                    pg_get_expr(c.relpartbound, c.oid) AS bound,
+                    obj_description(c.oid, 'pg_class') AS comment
+                FROM pg_class c
+                JOIN pg_namespace n ON n.oid = c.relnamespace
+                WHERE n.nspname = $1 AND c.relname = $2
+            "#,
+        )
+        .await?;
+
+    let row = client
+        .query_opt(&stmt, &[&table.schema, &table.name])
+        .await?
+        .with_context(|| format!("{table} does not exist"))?;
+
+    if row.try_get::<_, String>("kind")? == "f" {
+        anyhow::bail!("DDL of foreign tables is not supported");
+    }
+
+    let partition_of = match (row.try_get("parent")?, row.try_get("bound")?) {
+        (Some(parent), Some(bound)) => Some((parent, bound)),
+        _ => None,
+    };
+
+    let mut ddl = TableDdl {
+        name: qualified_name.to_owned(),
+        partition_key: row.try_get("partition_key")?,
+        comment: row.try_get("comment")?,
+        partition_of,
+        ..Default::default()
+    };
+
+    ddl.columns = column_ddls(client, &qualified_name).await?;
+
+    let stmt = client
+        .prepare_cached(
+            r#"
+                SELECT conname AS name, pg_get_constraintdef(oid) AS definition
+                FROM pg_constraint
+                WHERE conrelid = $1::text::regclass AND conislocal
+                ORDER BY
+                    CASE contype WHEN 'p' THEN 0 WHEN 'u' THEN 1 WHEN 'f' THEN 3 ELSE 2 END,
+                    conname
+            "#,
+        )
+        .await?;
+
+    for row in client.query(&stmt, &[&qualified_name]).await? {
+        ddl.constraints
+            .push((row.try_get("name")?, row.try_get("definition")?));
+    }
+
+    // @note: indexes backing constraints come with the constraints
+    let stmt = client
+        .prepare_cached(
+            r#"
+                SELECT pg_get_indexdef(x.indexrelid) AS definition
+                FROM pg_index x
+                JOIN pg_class i ON i.oid = x.indexrelid
+                WHERE x.indrelid = $1::text::regclass AND NOT EXISTS (
+                    SELECT 1 FROM pg_constraint con WHERE con.conindid = x.indexrelid
+                )
+                ORDER BY i.relname
+            "#,
+        )
+        .await?;
+
+    for row in client.query(&stmt, &[&qualified_name]).await? {
+        ddl.indexes.push(row.try_get("definition")?);
+    }
+
+    let stmt = client
+        .prepare_cached(
+            r#"
+                SELECT pg_get_triggerdef(oid) AS definition
+                FROM pg_trigger
+                WHERE tgrelid = $1::text::regclass AND NOT tgisinternal
+                ORDER BY tgname
+            "#,
+        )
+        .await?;
+
+    for row in client.query(&stmt, &[&qualified_name]).await? {
+        ddl.triggers.push(row.try_get("definition")?);
+    }
+
+    Ok(ddl.to_sql())
+}
+
+async fn column_ddls(client: &Client, qualified_name: &str) -> anyhow::Result<Vec<ColumnDdl>> {
+    let stmt = client
+        .prepare_cached(
+            r#"
+                SELECT
+                    a.attname AS name,
+                    format_type(a.atttypid, a.atttypmod) AS data_type,
+                    (
+                        SELECT co.collname FROM pg_collation co
+                        WHERE co.oid = a.attcollation AND a.attcollation <> t.typcollation
+                    ) AS collation,
+                    NOT a.attnotnull AS nullable,
+                    pg_get_expr(d.adbin, d.adrelid) AS "default",
+                    a.attidentity::text AS identity,
+                    a.attgenerated::text AS generated,
+                    col_description(a.attrelid, a.attnum) AS comment
+                FROM pg_attribute a
+                JOIN pg_type t ON t.oid = a.atttypid
+                LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+                WHERE a.attrelid = $1::text::regclass AND a.attnum > 0 AND NOT a.attisdropped
+                ORDER BY a.attnum
+            "#,
+        )
+        .await?;
+
+    let mut columns = vec![];
+
+    for row in client.query(&stmt, &[&qualified_name]).await? {
+        let identity: String = row.try_get("identity")?;
+        let generated: String = row.try_get("generated")?;
+
+        columns.push(ColumnDdl {
+            name: row.try_get("name")?,
+            data_type: row.try_get("data_type")?,
+            collation: row.try_get("collation")?,
+            nullable: row.try_get("nullable")?,
+            default: row.try_get("default")?,
+            identity: identity.chars().next(),
+            generated: !generated.is_empty(),
+            comment: row.try_get("comment")?,
+        });
+    }
+
+    Ok(columns)
+}
+
+async fn view_ddl(client: &Client, view: &TableRef, materialized: bool) -> anyhow::Result<String> {
+    let row = client
+        .query_one(
+            "SELECT pg_get_viewdef($1::text::regclass, true) AS definition",
+            &[&view.qualified_name()],
+        )
+        .await?;
+
+    let definition: String = row.try_get("definition")?;
+    let definition = definition.trim_end().trim_end_matches(';');
+
+    Ok(match materialized {
+        true => format!(
+            "CREATE MATERIALIZED VIEW {} AS\n{definition}\nWITH DATA;",
+            view.qualified_name()
+        ),
+        false => format!(
+            "CREATE OR REPLACE VIEW {} AS\n{definition};",
+            view.qualified_name()
+        ),
+    })
+}
+
+async fn function_ddl(client: &Client, schema: &str, signature: &str) -> anyhow::Result<String> {
+    let stmt = client
+        .prepare_cached(
+            r#"
+                SELECT p.prokind::text AS kind, p.oid
+                FROM pg_proc p
+                JOIN pg_namespace n ON n.oid = p.pronamespace
+                WHERE n.nspname = $1
+                    AND p.proname || '(' || pg_get_function_identity_arguments(p.oid) || ')' = $2
+            "#,
+        )
+        .await?;
+
+    let row = client
+        .query_opt(&stmt, &[&schema, &signature])
+        .await?
+        .with_context(|| format!("{schema}.{signature} does not exist"))?;
+
+    // @note: `pg_get_functiondef` rejects aggregates
+    if row.try_get::<_, String>("kind")? == "a" {
+        anyhow::bail!("DDL of aggregates is not supported");
+    }
+
+    let oid: u32 = row.try_get("oid")?;
+    let row = client
+        .query_one("SELECT pg_get_functiondef($1) AS definition", &[&oid])
+        .await?;
+
+    let definition: String = row.try_get("definition")?;
+    Ok(format!("{};", definition.trim_end()))
+}
+
+#[derive(Debug)]
+struct SequenceDdl {
+    name: String,
+    data_type: String,
+    start: i64,
+    increment: i64,
+    min: i64,
+    max: i64,
+    cache: i64,
+    cycle: bool,
+    owned_by: Option<String>,
+}
+
+impl SequenceDdl {
+    fn to_sql(&self) -> String {
+        let mut sql = format!(
+            "CREATE SEQUENCE {} AS {}\n    INCREMENT BY {}\n    MINVALUE {}\n    MAXVALUE {}\n    START WITH {}\n    CACHE {}\n    {}CYCLE;",
+            self.name,
+            self.data_type,
+            self.increment,
+            self.min,
+            self.max,
+            self.start,
+            self.cache,
+            if self.cycle { "" } else { "NO " },
+        );
+
+        if let Some(owned_by) = self.owned_by.as_deref() {
+            sql.push_str(&format!(
+                "\n\nALTER SEQUENCE {} OWNED BY {owned_by};",
+                self.name
+            ));
+        }
+
+        sql
+    }
+}
+
+async fn sequence_ddl(client: &Client, sequence: &TableRef) -> anyhow::Result<String> {
+    let stmt = client
+        .prepare_cached(
+            r#"
+                SELECT
+                    format_type(s.seqtypid, NULL) AS data_type,
+                    s.seqstart AS start,
+                    s.seqincrement AS increment,
+                    s.seqmin AS min,
+                    s.seqmax AS max,
+                    s.seqcache AS cache,
+                    s.seqcycle AS cycle,
+                    (
+                        SELECT quote_ident(tn.nspname) || '.' || quote_ident(t.relname)
+                            || '.' || quote_ident(a.attname)
+                        FROM pg_depend d
+                        JOIN pg_class t ON t.oid = d.refobjid
+                        JOIN pg_namespace tn ON tn.oid = t.relnamespace
+                        JOIN pg_attribute a ON a.attrelid = t.oid AND a.attnum = d.refobjsubid
+                        WHERE d.classid = 'pg_class'::regclass AND d.objid = s.seqrelid
+                            AND d.deptype = 'a'
+                    ) AS owned_by
+                FROM pg_sequence s
+                WHERE s.seqrelid = $1::text::regclass
+            "#,
+        )
+        .await?;
+
+    let row = client
+        .query_one(&stmt, &[&sequence.qualified_name()])
+        .await?;
+
+    let ddl = SequenceDdl {
+        name: sequence.qualified_name(),
+        data_type: row.try_get("data_type")?,
+        start: row.try_get("start")?,
+        increment: row.try_get("increment")?,
+        min: row.try_get("min")?,
+        max: row.try_get("max")?,
+        cache: row.try_get("cache")?,
+        cycle: row.try_get("cycle")?,
+        owned_by: row.try_get("owned_by")?,
+    };
+
+    Ok(ddl.to_sql())
+}
+
+async fn type_ddl(client: &Client, ty: &TableRef) -> anyhow::Result<String> {
+    let name = ty.qualified_name();
+
+    let stmt = client
+        .prepare_cached(
+            r#"
+                SELECT
+                    t.typtype::text AS kind,
+                    format_type(t.typbasetype, t.typtypmod) AS base_type,
+                    t.typnotnull AS not_null,
+                    t.typdefault AS "default",
+                    ARRAY(
+                        SELECT e.enumlabel::text FROM pg_enum e
+                        WHERE e.enumtypid = t.oid ORDER BY e.enumsortorder
+                    ) AS labels,
+                    ARRAY(
+                        SELECT quote_ident(a.attname) || ' ' || format_type(a.atttypid, a.atttypmod)
+                        FROM pg_attribute a
+                        WHERE a.attrelid = t.typrelid AND a.attnum > 0 AND NOT a.attisdropped
+                        ORDER BY a.attnum
+                    ) AS attributes,
+                    ARRAY(
+                        SELECT 'CONSTRAINT ' || quote_ident(con.conname) || ' '
+                            || pg_get_constraintdef(con.oid)
+                        FROM pg_constraint con
+                        WHERE con.contypid = t.oid
+                        ORDER BY con.conname
+                    ) AS constraints
+                FROM pg_type t
+                JOIN pg_namespace n ON n.oid = t.typnamespace
+                WHERE n.nspname = $1 AND t.typname = $2
+            "#,
+        )
+        .await?;
+
+    let row = client
+        .query_opt(&stmt, &[&ty.schema, &ty.name])
+        .await?
+        .with_context(|| format!("{ty} does not exist"))?;
+
+    let kind: String = row.try_get("kind")?;
+    let sql = match kind.as_str() {
+        "e" => {
+            let labels: Vec<String> = row.try_get("labels")?;
+            let labels = labels
+                .iter()
+                .map(|label| quote_literal(label))
+                .collect::<Vec<_>>();
+
+            format!("CREATE TYPE {name} AS ENUM ({});", labels.join(", "))
+        }
+        "d" => {
+            let mut sql = format!(
+                "CREATE DOMAIN {name} AS {}",
+                row.try_get::<_, String>("base_type")?
+            );
+
+            if let Some(default) = row.try_get::<_, Option<String>>("default")? {
+                sql.push_str(&format!(" DEFAULT {default}"));
+            }
+            if row.try_get("not_null")? {
+                sql.push_str(" NOT NULL");
+            }
+
+            let constraints: Vec<String> = row.try_get("constraints")?;
+            for constraint in constraints.iter().filter(|c| !c.ends_with("NOT NULL")) {
+                sql.push_str(&format!("\n    {constraint}"));
+            }
+
+            sql.push(';');
+            sql
+        }
+        "c" => {
+            let attributes: Vec<String> = row.try_get("attributes")?;
+            format!(
+                "CREATE TYPE {name} AS (\n    {}\n);",
+                attributes.join(",\n    ")
+            )
+        }
+        other => anyhow::bail!("DDL of type kind {other:?} is not supported"),
+    };
+
+    Ok(sql)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_ddl() {
+        let ddl = TableDdl {
+            name: r#""foo"."bar""#.to_owned(),
+            columns: vec![
+                ColumnDdl {
+                    name: "id".to_owned(),
+                    data_type: "integer".to_owned(),
+                    identity: Some('a'),
+                    ..Default::default()
+                },
+                ColumnDdl {
+                    name: "name".to_owned(),
+                    data_type: "text".to_owned(),
+                    collation: Some("C".to_owned()),
+                    nullable: true,
+                    default: Some("'x'::text".to_owned()),
+                    ..Default::default()
+                },
+                ColumnDdl {
+                    name: "len".to_owned(),
+                    data_type: "integer".to_owned(),
+                    nullable: true,
+                    default: Some("length(name)".to_owned()),
+                    generated: true,
+                    ..Default::default()
+                },
+            ],
+            constraints: vec![("bar_pkey".to_owned(), "PRIMARY KEY (id)".to_owned())],
+            indexes: vec!["CREATE INDEX bar_name ON foo.bar USING btree (name)".to_owned()],
+            comment: Some("it's bar".to_owned()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            ddl.to_sql(),
+            r#"CREATE TABLE "foo"."bar" (
+    "id" integer GENERATED ALWAYS AS IDENTITY NOT NULL,
+    "name" text COLLATE "C" DEFAULT 'x'::text,
+    "len" integer GENERATED ALWAYS AS (length(name)) STORED,
+    CONSTRAINT "bar_pkey" PRIMARY KEY (id)
+);
+
+CREATE INDEX bar_name ON foo.bar USING btree (name);
+
+COMMENT ON TABLE "foo"."bar" IS 'it''s bar';"#
+        );
+    }
+
+    #[test]
+    fn test_partition_ddl() {
+        let ddl = TableDdl {
+            name: r#""foo"."bar_2024""#.to_owned(),
+            partition_of: Some((
+                "foo.bar".to_owned(),
+                "FOR VALUES FROM ('2024-01-01') TO ('2025-01-01')".to_owned(),
+            )),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            ddl.to_sql(),
+            r#"CREATE TABLE "foo"."bar_2024" PARTITION OF foo.bar FOR VALUES FROM ('2024-01-01') TO ('2025-01-01');"#
+        );
+    }
+
+    #[test]
+    fn test_sequence_ddl() {
+        let ddl = SequenceDdl {
+            name: r#""foo"."bar_id_seq""#.to_owned(),
+            data_type: "integer".to_owned(),
+            start: 1,
+            increment: 1,
+            min: 1,
+            max: 2147483647,
+            cache: 1,
+            cycle: false,
+            owned_by: Some("foo.bar.id".to_owned()),
+        };
+
+        assert_eq!(
+            ddl.to_sql(),
+            r#"CREATE SEQUENCE "foo"."bar_id_seq" AS integer
+    INCREMENT BY 1
+    MINVALUE 1
+    MAXVALUE 2147483647
+    START WITH 1
+    CACHE 1
+    NO CYCLE;
+
+ALTER SEQUENCE "foo"."bar_id_seq" OWNED BY foo.bar.id;"#
+        );
+    }
+}
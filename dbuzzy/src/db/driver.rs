@@ -0,0 +1,257 @@
+use std::{
+    io::Write,
+    net::Ipv4Addr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use super::{
+    quote_ident, quote_literal,
+    ssh::SshTunnel,
+    tree::{DatabaseTree, TreeItem},
+    Column, ConnectionConfig, CsvImport, ExportFormat, Plan, QueryResult, TableInfo, TableRef,
+};
+
+// @note: for a free connection of a busy pool, the task fails then instead of hanging
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+pub(super) const POOL_WAIT_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DriverKind {
+    #[default]
+    Postgres,
+    Sqlite,
+    Mysql,
+}
+
+impl DriverKind {
+    pub const fn scheme(&self) -> &'static str {
+        match self {
+            Self::Postgres => "postgres",
+            Self::Sqlite => "sqlite",
+            Self::Mysql => "mysql",
+        }
+    }
+
+    pub const fn version_query(&self) -> &'static str {
+        match self {
+            Self::Postgres | Self::Mysql => "SELECT version()",
+            Self::Sqlite => "SELECT 'SQLite ' || sqlite_version()",
+        }
+    }
+
+    pub const fn default_port(&self) -> Option<u16> {
+        match self {
+            Self::Postgres => Some(5432),
+            Self::Mysql => Some(3306),
+            Self::Sqlite => None,
+        }
+    }
+
+    // @note: mysql quotes with backticks, unless ANSI_QUOTES is on
+    pub fn quote_ident(&self, ident: &str) -> String {
+        match self {
+            Self::Mysql => format!("`{}`", ident.replace('`', "``")),
+            Self::Postgres | Self::Sqlite => quote_ident(ident),
+        }
+    }
+
+    // @note: a backslash escapes in mysql strings
+    pub fn quote_literal(&self, value: &str) -> String {
+        match self {
+            Self::Mysql => quote_literal(&value.replace('\\', "\\\\")),
+            Self::Postgres | Self::Sqlite => quote_literal(value),
+        }
+    }
+}
+
+#[async_trait]
+pub trait Driver: Send + Sync {
+    fn kind(&self) -> DriverKind;
+
+    async fn acquire(&self) -> anyhow::Result<Box<dyn Connection>>;
+}
+
+#[async_trait]
+pub trait Connection: Send + Sync {
+    fn kind(&self) -> DriverKind;
+
+    async fn fetch(&self, sql: &str) -> anyhow::Result<QueryResult>;
+
+    // @note: `values` of the `Placeholders` of `sql` in order, `None` is null
+    async fn fetch_params(
+        &self,
+        sql: &str,
+        values: &[Option<String>],
+    ) -> anyhow::Result<QueryResult>;
+
+    async fn load_tree(&self) -> anyhow::Result<DatabaseTree>;
+
+    // @note: children of a collapsed item, that were not loaded yet
+    async fn children(&self, item: &Arc<TreeItem>) -> anyhow::Result<Vec<Arc<TreeItem>>>;
+
+    // @note: the CREATE statement of a tree item
+    async fn ddl(&self, item: &Arc<TreeItem>) -> anyhow::Result<String>;
+
+    async fn table_info(&self, table: &TableRef) -> anyhow::Result<TableInfo>;
+
+    async fn table_columns(&self, table: &TableRef) -> anyhow::Result<Vec<Column>>;
+
+    // @note: the plan alone, `Plan::fetch` rolls back, what ANALYZE changed
+    async fn explain(&self, sql: &str, analyze: bool) -> anyhow::Result<Plan>;
+
+    // @note: a batch of a csv import, the inner error is a rejected row
+    async fn load_rows(
+        &self,
+        import: &CsvImport,
+        records: &[csv::StringRecord],
+    ) -> anyhow::Result<Result<u64, String>> {
+        match self.fetch(&import.insert_sql(self.kind(), records)).await {
+            Ok(result) => Ok(Ok(result.affected.unwrap_or_default())),
+            Err(e) => Ok(Err(e.root_cause().to_string())),
+        }
+    }
+
+    // @note: writes the rows of `sql` as the server sends them, so the whole result is never
+    // held, `None` for the drivers without row streams
+    async fn export(
+        &self,
+        _sql: &str,
+        _values: Option<&[Option<String>]>,
+        _format: ExportFormat,
+        _out: &mut (dyn Write + Send),
+    ) -> Option<anyhow::Result<u64>> {
+        None
+    }
+
+    // @note: `None` for the drivers without cancel requests
+    fn cancel_handle(&self) -> Option<Box<dyn CancelHandle>> {
+        None
+    }
+
+    // @note: closes the connection instead of giving it back to the pool,
+    // when it may be left inside a transaction
+    fn discard(self: Box<Self>) {}
+}
+
+// @note: cancels the statement running on a connection, from the outside
+#[async_trait]
+pub trait CancelHandle: Send + Sync {
+    async fn cancel(&self) -> anyhow::Result<()>;
+}
+
+pub type DbPool = Arc<dyn Driver>;
+
+// @note: runs the statements closing a block, when one fails after a failed block,
+// the error of the block is kept and the failure is attached to it
+pub(super) async fn close<T>(
+    conn: &dyn Connection,
+    statements: &[&str],
+    result: anyhow::Result<T>,
+) -> anyhow::Result<T> {
+    for sql in statements {
+        if let Err(e) = conn.fetch(sql).await {
+            return Err(match result {
+                Ok(_) => e,
+                Err(cause) => cause.context(format!("{sql} failed too: {e:#}")),
+            });
+        }
+    }
+
+    result
+}
+
+// @note: keeps the tunnel open as long as the pool is alive
+struct Tunneled {
+    pool: DbPool,
+    _tunnel: SshTunnel,
+}
+
+#[async_trait]
+impl Driver for Tunneled {
+    fn kind(&self) -> DriverKind {
+        self.pool.kind()
+    }
+
+    async fn acquire(&self) -> anyhow::Result<Box<dyn Connection>> {
+        self.pool.acquire().await
+    }
+}
+
+pub async fn connect(config: &ConnectionConfig) -> anyhow::Result<DbPool> {
+    let Some(ssh) = config.ssh.as_ref() else {
+        return create_pool(config);
+    };
+
+    let port = config
+        .port
+        .or(config.driver.default_port())
+        .context("ssh tunnel needs a network database")?;
+
+    let host = match config.host.as_str() {
+        "" => "localhost",
+        host => host,
+    };
+
+    let tunnel = SshTunnel::open(ssh, host, port).await?;
+
+    // @note: `host` stays for the TLS verification
+    let config = ConnectionConfig {
+        hostaddr: Some(Ipv4Addr::LOCALHOST.into()),
+        port: Some(tunnel.local_port()),
+        ssh: None,
+        ..config.clone()
+    };
+
+    Ok(Arc::new(Tunneled {
+        pool: create_pool(&config)?,
+        _tunnel: tunnel,
+    }))
+}
+
+#[derive(Debug)]
+pub struct ServerInfo {
+    pub version: String,
+    // @note: of opening the connection, with the tunnel and the TLS handshake
+    pub latency: Duration,
+}
+
+// @note: connects with a pool of its own, that is closed right after
+pub async fn ping(config: &ConnectionConfig) -> anyhow::Result<ServerInfo> {
+    let started = Instant::now();
+    let pool = connect(config).await?;
+    let connection = pool.acquire().await?;
+    let latency = started.elapsed();
+
+    let result = connection.fetch(config.driver.version_query()).await?;
+    let version = result
+        .rows
+        .first()
+        .and_then(|row| row.first())
+        .context("server version is empty")?
+        .to_string();
+
+    Ok(ServerInfo { version, latency })
+}
+
+fn create_pool(config: &ConnectionConfig) -> anyhow::Result<DbPool> {
+    match config.driver {
+        #[cfg(feature = "postgres")]
+        DriverKind::Postgres => Ok(Arc::new(super::PgPool::create(config)?)),
+        #[cfg(not(feature = "postgres"))]
+        DriverKind::Postgres => anyhow::bail!("dbuzzy is built without the `postgres` feature"),
+        #[cfg(feature = "sqlite")]
+        DriverKind::Sqlite => Ok(Arc::new(super::sqlite::SqlitePool::create(config)?)),
+        #[cfg(not(feature = "sqlite"))]
+        DriverKind::Sqlite => anyhow::bail!("dbuzzy is built without the `sqlite` feature"),
+        #[cfg(feature = "mysql")]
+        DriverKind::Mysql => Ok(Arc::new(super::mysql::MysqlPool::create(config)?)),
+        #[cfg(not(feature = "mysql"))]
+        DriverKind::Mysql => anyhow::bail!("dbuzzy is built without the `mysql` feature"),
+    }
+}
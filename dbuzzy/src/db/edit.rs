@@ -0,0 +1,306 @@
+use super::{
+    driver::{self, Connection},
+    DriverKind, TableRef, Value,
+};
+
+// @note: key column name and its current value, identifies a row
+pub type RowKey = Vec<(String, Value)>;
+
+// @note: `None` stands for NULL
+pub type CellChange = (String, Option<String>);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowEdit {
+    Update {
+        key: RowKey,
+        changes: Vec<CellChange>,
+    },
+    Insert {
+        values: Vec<CellChange>,
+    },
+    Delete {
+        key: RowKey,
+    },
+}
+
+impl RowEdit {
+    pub fn to_sql(&self, table: &TableRef, kind: DriverKind) -> String {
+        let table = table.quoted_name(kind);
+        let literal = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map_or_else(|| "NULL".to_owned(), |value| kind.quote_literal(value))
+        };
+
+        match self {
+            Self::Update { key, changes } => {
+                let set = changes
+                    .iter()
+                    .map(|(column, value)| {
+                        format!("{} = {}", kind.quote_ident(column), literal(value))
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                format!(
+                    "UPDATE {table} SET {set} WHERE {};",
+                    key_condition(key, kind)
+                )
+            }
+            Self::Insert { values } if values.is_empty() => match kind {
+                DriverKind::Mysql => format!("INSERT INTO {table} () VALUES ();"),
+                DriverKind::Postgres | DriverKind::Sqlite => {
+                    format!("INSERT INTO {table} DEFAULT VALUES;")
+                }
+            },
+            Self::Insert { values } => {
+                let (columns, values): (Vec<_>, Vec<_>) = values
+                    .iter()
+                    .map(|(column, value)| (kind.quote_ident(column), literal(value)))
+                    .unzip();
+
+                format!(
+                    "INSERT INTO {table} ({}) VALUES ({});",
+                    columns.join(", "),
+                    values.join(", ")
+                )
+            }
+            Self::Delete { key } => {
+                format!("DELETE FROM {table} WHERE {};", key_condition(key, kind))
+            }
+        }
+    }
+
+    const fn key(&self) -> Option<&RowKey> {
+        match self {
+            Self::Update { key, .. } | Self::Delete { key } => Some(key),
+            Self::Insert { .. } => None,
+        }
+    }
+}
+
+fn key_condition(key: &RowKey, kind: DriverKind) -> String {
+    key.iter()
+        .map(|(column, value)| {
+            format!(
+                "{} = {}",
+                kind.quote_ident(column),
+                kind.quote_literal(&value.to_string())
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" AND ")
+}
+
+#[derive(Debug, Clone)]
+pub struct EditBatch {
+    table: TableRef,
+    kind: DriverKind,
+    edits: Vec<RowEdit>,
+}
+
+impl EditBatch {
+    pub const fn new(table: TableRef, kind: DriverKind) -> Self {
+        Self {
+            table,
+            kind,
+            edits: vec![],
+        }
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.edits.clear();
+    }
+
+    pub fn update(&mut self, key: RowKey, column: &str, value: Option<String>) {
+        let position = self
+            .edits
+            .iter()
+            .position(|edit| matches!(edit, RowEdit::Update { key: k, .. } if *k == key));
+
+        let Some(RowEdit::Update { changes, .. }) = position.map(|i| &mut self.edits[i]) else {
+            let changes = vec![(column.to_owned(), value)];
+            self.edits.push(RowEdit::Update { key, changes });
+            return;
+        };
+
+        match changes.iter_mut().find(|(c, _)| c == column) {
+            Some(change) => change.1 = value,
+            None => changes.push((column.to_owned(), value)),
+        }
+    }
+
+    pub fn toggle_delete(&mut self, key: RowKey) {
+        let position = self
+            .edits
+            .iter()
+            .position(|edit| matches!(edit, RowEdit::Delete { key: k } if *k == key));
+
+        match position {
+            Some(i) => {
+                self.edits.remove(i);
+            }
+            None => self.edits.push(RowEdit::Delete { key }),
+        }
+    }
+
+    pub fn insert(&mut self) {
+        self.edits.push(RowEdit::Insert { values: vec![] });
+    }
+
+    pub fn update_insert(&mut self, index: usize, column: &str, value: Option<String>) {
+        let Some(RowEdit::Insert { values }) = self
+            .edits
+            .iter_mut()
+            .filter(|edit| matches!(edit, RowEdit::Insert { .. }))
+            .nth(index)
+        else {
+            return;
+        };
+
+        match values.iter_mut().find(|(c, _)| c == column) {
+            Some(change) => change.1 = value,
+            None => values.push((column.to_owned(), value)),
+        }
+    }
+
+    pub fn remove_insert(&mut self, index: usize) {
+        let position = self
+            .edits
+            .iter()
+            .enumerate()
+            .filter(|(_, edit)| matches!(edit, RowEdit::Insert { .. }))
+            .nth(index)
+            .map(|(i, _)| i);
+
+        if let Some(i) = position {
+            self.edits.remove(i);
+        }
+    }
+
+    pub fn inserts(&self) -> impl Iterator<Item = &[CellChange]> {
+        self.edits.iter().filter_map(|edit| match edit {
+            RowEdit::Insert { values } => Some(values.as_slice()),
+            _ => None,
+        })
+    }
+
+    pub fn changed_value(&self, key: &RowKey, column: &str) -> Option<&Option<String>> {
+        self.edits.iter().find_map(|edit| match edit {
+            RowEdit::Update { key: k, changes } if k == key => changes
+                .iter()
+                .find(|(c, _)| c == column)
+                .map(|(_, value)| value),
+            _ => None,
+        })
+    }
+
+    pub fn is_deleted(&self, key: &RowKey) -> bool {
+        self.edits
+            .iter()
+            .any(|edit| matches!(edit, RowEdit::Delete { .. }) && edit.key() == Some(key))
+    }
+
+    pub fn statements(&self) -> Vec<String> {
+        self.edits
+            .iter()
+            .map(|edit| edit.to_sql(&self.table, self.kind))
+            .collect()
+    }
+
+    pub fn preview(&self) -> String {
+        let mut sql = vec!["BEGIN;".to_owned()];
+        sql.extend(self.statements());
+        sql.push("COMMIT;".to_owned());
+        sql.join("\n")
+    }
+
+    // @note: in a transaction of its own, rolled back on the first failing statement
+    pub async fn commit(&self, conn: &dyn Connection) -> anyhow::Result<u64> {
+        conn.fetch("BEGIN").await?;
+
+        let affected = self.execute(conn).await;
+        let end = match affected.is_ok() {
+            true => "COMMIT",
+            false => "ROLLBACK",
+        };
+
+        driver::close(conn, &[end], affected).await
+    }
+
+    pub(super) async fn execute(&self, conn: &dyn Connection) -> anyhow::Result<u64> {
+        let mut affected = 0;
+
+        for edit in &self.edits {
+            let sql = edit.to_sql(&self.table, self.kind);
+            let rows = conn.fetch(&sql).await?.affected.unwrap_or_default();
+
+            // @note: the row was changed or removed since it was fetched
+            if edit.key().is_some() && rows != 1 {
+                anyhow::bail!("{rows} rows matched, expected 1: {sql}");
+            }
+
+            affected += rows;
+        }
+
+        Ok(affected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(id: i64) -> RowKey {
+        vec![("id".to_owned(), Value::Int(id))]
+    }
+
+    #[test]
+    fn test_statements() {
+        let table = TableRef::new("foo".to_owned(), "bar".to_owned());
+        let mut batch = EditBatch::new(table.clone(), DriverKind::Postgres);
+
+        batch.update(key(1), "baz", Some("it's".to_owned()));
+        batch.update(key(1), "qux", None);
+        batch.update(key(1), "baz", Some("x".to_owned()));
+        batch.toggle_delete(key(2));
+        batch.insert();
+        batch.insert();
+        batch.update_insert(1, "baz", Some("y".to_owned()));
+
+        assert_eq!(
+            batch.statements(),
+            vec![
+                r#"UPDATE "foo"."bar" SET "baz" = 'x', "qux" = NULL WHERE "id" = '1';"#,
+                r#"DELETE FROM "foo"."bar" WHERE "id" = '2';"#,
+                r#"INSERT INTO "foo"."bar" DEFAULT VALUES;"#,
+                r#"INSERT INTO "foo"."bar" ("baz") VALUES ('y');"#,
+            ]
+        );
+
+        assert!(batch.is_deleted(&key(2)));
+        assert_eq!(batch.changed_value(&key(1), "qux"), Some(&None));
+
+        batch.toggle_delete(key(2));
+        batch.remove_insert(0);
+
+        assert!(!batch.is_deleted(&key(2)));
+        assert_eq!(batch.statements().len(), 2);
+
+        let mut batch = EditBatch::new(table, DriverKind::Mysql);
+        batch.update(key(1), "baz", Some("a\\b".to_owned()));
+        batch.insert();
+
+        assert_eq!(
+            batch.statements(),
+            vec![
+                r#"UPDATE `foo`.`bar` SET `baz` = 'a\\b' WHERE `id` = '1';"#,
+                "INSERT INTO `foo`.`bar` () VALUES ();",
+            ]
+        );
+    }
+}
@@ -0,0 +1,290 @@
+use anyhow::Context;
+use serde_json::Value as Json;
+
+use super::driver::{self, Connection};
+
+// @note: a node of the plan, the nodes are kept flat in depth-first order like the database tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanNode {
+    pub depth: u8,
+    pub node_type: String,
+    // @note: relation, index, join type, ...
+    pub detail: Option<String>,
+    pub startup_cost: f64,
+    pub total_cost: f64,
+    // @note: estimated and actual rows are per loop
+    pub plan_rows: f64,
+    pub actual_rows: Option<f64>,
+    // @note: total time of all loops in ms, only with ANALYZE
+    pub actual_time: Option<f64>,
+    pub loops: u64,
+    pub shared_hit: Option<u64>,
+    pub shared_read: Option<u64>,
+}
+
+impl PlanNode {
+    // @note: how many times the estimate is off, `None` without ANALYZE
+    pub fn misestimate(&self) -> Option<f64> {
+        let actual = self.actual_rows?.max(1.0);
+        let planned = self.plan_rows.max(1.0);
+
+        Some(actual.max(planned) / actual.min(planned))
+    }
+
+    fn from_json(json: &Json, depth: u8, nodes: &mut Vec<Self>) -> anyhow::Result<()> {
+        let float = |key: &str| json.get(key).and_then(Json::as_f64);
+        let uint = |key: &str| json.get(key).and_then(Json::as_u64);
+        let text = |key: &str| json.get(key).and_then(Json::as_str);
+
+        let node_type = text("Node Type").context("plan node without a type")?;
+        let loops = uint("Actual Loops").unwrap_or(1);
+
+        let detail = match (text("Index Name"), text("Relation Name")) {
+            (Some(index), Some(relation)) => Some(format!("using {index} on {relation}")),
+            (Some(index), None) => Some(format!("using {index}")),
+            (None, Some(relation)) => Some(format!("on {relation}")),
+            (None, None) => text("Join Type")
+                .or_else(|| text("Strategy"))
+                .or_else(|| text("CTE Name"))
+                .or_else(|| text("Function Name"))
+                .map(|detail| format!("({detail})")),
+        };
+
+        nodes.push(Self {
+            depth,
+            node_type: node_type.to_owned(),
+            detail,
+            startup_cost: float("Startup Cost").unwrap_or_default(),
+            total_cost: float("Total Cost").unwrap_or_default(),
+            plan_rows: float("Plan Rows").unwrap_or_default(),
+            actual_rows: float("Actual Rows"),
+            actual_time: float("Actual Total Time").map(|time| time * loops as f64),
+            loops,
+            shared_hit: uint("Shared Hit Blocks"),
+            shared_read: uint("Shared Read Blocks"),
+        });
+
+        if let Some(children) = json.get("Plans").and_then(Json::as_array) {
+            for child in children {
+                Self::from_json(child, depth + 1, nodes)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plan {
+    nodes: Vec<PlanNode>,
+    pub planning_time: Option<f64>,
+    pub execution_time: Option<f64>,
+}
+
+impl Plan {
+    pub fn sql(sql: &str, analyze: bool) -> String {
+        let sql = sql.trim().trim_end_matches(';');
+
+        match analyze {
+            true => format!("EXPLAIN (FORMAT JSON, ANALYZE, BUFFERS) {sql}"),
+            false => format!("EXPLAIN (FORMAT JSON, BUFFERS) {sql}"),
+        }
+    }
+
+    // @note: ANALYZE runs the statement, so it is rolled back,
+    // to a savepoint inside an open transaction
+    pub async fn fetch(
+        conn: &dyn Connection,
+        sql: &str,
+        analyze: bool,
+        in_transaction: bool,
+    ) -> anyhow::Result<Self> {
+        let (begin, rollback): (_, &[_]) = match in_transaction {
+            true => (
+                "SAVEPOINT dbuzzy_explain",
+                &[
+                    "ROLLBACK TO SAVEPOINT dbuzzy_explain",
+                    "RELEASE SAVEPOINT dbuzzy_explain",
+                ],
+            ),
+            false => ("BEGIN", &["ROLLBACK"]),
+        };
+
+        conn.fetch(begin).await?;
+        let plan = conn.explain(sql, analyze).await;
+
+        driver::close(conn, rollback, plan).await
+    }
+
+    pub(super) const fn new(nodes: Vec<PlanNode>) -> Self {
+        Self {
+            nodes,
+            planning_time: None,
+            execution_time: None,
+        }
+    }
+
+    pub fn from_json(json: &Json) -> anyhow::Result<Self> {
+        let json = json
+            .as_array()
+            .and_then(|plans| plans.first())
+            .unwrap_or(json);
+
+        let mut nodes = vec![];
+        let root = json.get("Plan").context("no plan in the output")?;
+        PlanNode::from_json(root, 0, &mut nodes)?;
+
+        Ok(Self {
+            nodes,
+            planning_time: json.get("Planning Time").and_then(Json::as_f64),
+            execution_time: json.get("Execution Time").and_then(Json::as_f64),
+        })
+    }
+
+    pub fn nodes(&self) -> &[PlanNode] {
+        &self.nodes
+    }
+
+    pub fn is_analyzed(&self) -> bool {
+        self.nodes.iter().any(|node| node.actual_time.is_some())
+    }
+
+    pub fn children(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        let depth = self.nodes[index].depth;
+
+        (index + 1..self.nodes.len())
+            .take_while(move |&i| self.nodes[i].depth > depth)
+            .filter(move |&i| self.nodes[i].depth == depth + 1)
+    }
+
+    pub fn has_children(&self, index: usize) -> bool {
+        self.children(index).next().is_some()
+    }
+
+    pub fn parent_index(&self, index: usize) -> Option<usize> {
+        let depth = self.nodes.get(index)?.depth;
+        self.nodes[..index].iter().rposition(|x| x.depth < depth)
+    }
+
+    pub fn next_sibling(&self, index: usize) -> Option<usize> {
+        let depth = self.nodes.get(index)?.depth;
+        let next = self.nodes[index + 1..]
+            .iter()
+            .position(|x| x.depth <= depth)?
+            + index
+            + 1;
+
+        (self.nodes[next].depth == depth).then_some(next)
+    }
+
+    // @note: time or cost of the node without its children
+    pub fn exclusive(&self, index: usize) -> f64 {
+        let total = |node: &PlanNode| match self.is_analyzed() {
+            true => node.actual_time.unwrap_or_default(),
+            false => node.total_cost,
+        };
+
+        let children = self
+            .children(index)
+            .map(|i| total(&self.nodes[i]))
+            .sum::<f64>();
+
+        (total(&self.nodes[index]) - children).max(0.0)
+    }
+
+    // @note: by its own time with ANALYZE, by its own cost otherwise
+    pub fn most_expensive(&self) -> Option<usize> {
+        (0..self.nodes.len()).max_by(|a, b| self.exclusive(*a).total_cmp(&self.exclusive(*b)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan() -> anyhow::Result<()> {
+        let json = serde_json::json!([{
+            "Plan": {
+                "Node Type": "Hash Join",
+                "Join Type": "Inner",
+                "Startup Cost": 1.5,
+                "Total Cost": 40.0,
+                "Plan Rows": 10,
+                "Actual Total Time": 12.0,
+                "Actual Rows": 500,
+                "Actual Loops": 1,
+                "Plans": [
+                    {
+                        "Node Type": "Seq Scan",
+                        "Relation Name": "orders",
+                        "Startup Cost": 0.0,
+                        "Total Cost": 30.0,
+                        "Plan Rows": 1000,
+                        "Actual Total Time": 9.0,
+                        "Actual Rows": 1000,
+                        "Actual Loops": 1,
+                        "Shared Hit Blocks": 8,
+                        "Shared Read Blocks": 2
+                    },
+                    {
+                        "Node Type": "Hash",
+                        "Startup Cost": 1.0,
+                        "Total Cost": 1.0,
+                        "Plan Rows": 5,
+                        "Actual Total Time": 0.5,
+                        "Actual Rows": 5,
+                        "Actual Loops": 2,
+                        "Plans": [{
+                            "Node Type": "Index Scan",
+                            "Index Name": "users_pkey",
+                            "Relation Name": "users",
+                            "Startup Cost": 0.0,
+                            "Total Cost": 1.0,
+                            "Plan Rows": 5,
+                            "Actual Total Time": 0.25,
+                            "Actual Rows": 5,
+                            "Actual Loops": 2
+                        }]
+                    }
+                ]
+            },
+            "Planning Time": 0.2,
+            "Execution Time": 12.5
+        }]);
+
+        let plan = Plan::from_json(&json)?;
+        let nodes = plan.nodes();
+
+        assert_eq!(nodes.len(), 4);
+        assert!(plan.is_analyzed());
+        assert_eq!(plan.execution_time, Some(12.5));
+
+        assert_eq!(nodes[0].detail.as_deref(), Some("(Inner)"));
+        assert_eq!(nodes[1].detail.as_deref(), Some("on orders"));
+        assert_eq!(nodes[1].shared_read, Some(2));
+        assert_eq!(
+            nodes[3].detail.as_deref(),
+            Some("using users_pkey on users")
+        );
+        assert_eq!(nodes[3].depth, 2);
+        assert_eq!(nodes[2].actual_time, Some(1.0));
+
+        assert_eq!(plan.children(0).collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(plan.parent_index(3), Some(2));
+        assert_eq!(plan.next_sibling(1), Some(2));
+        assert_eq!(plan.next_sibling(2), None);
+
+        assert_eq!(nodes[0].misestimate(), Some(50.0));
+        assert_eq!(nodes[1].misestimate(), Some(1.0));
+        assert_eq!(plan.exclusive(0), 2.0);
+        assert_eq!(plan.most_expensive(), Some(1));
+
+        assert_eq!(
+            Plan::sql("select 1;\n", false),
+            "EXPLAIN (FORMAT JSON, BUFFERS) select 1"
+        );
+
+        Ok(())
+    }
+}
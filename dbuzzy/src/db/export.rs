@@ -0,0 +1,386 @@
+use std::{
+    io::{self, Write},
+    path::Path,
+};
+
+#[cfg(feature = "postgres")]
+use deadpool_postgres::Client;
+#[cfg(feature = "postgres")]
+use futures_util::TryStreamExt;
+use serde::Deserialize;
+
+#[cfg(feature = "postgres")]
+use super::{
+    query::{row_values, statement_columns, text_params},
+    Placeholders,
+};
+use super::{quote_ident, Column, QueryResult, Value};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CsvQuote {
+    #[default]
+    Minimal,
+    All,
+    NonNumeric,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ExportConfig {
+    pub delimiter: char,
+    pub quote: CsvQuote,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            quote: CsvQuote::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv { delimiter: char, quote: CsvQuote },
+    Json,
+    Ndjson,
+    Markdown,
+    Insert { table: String },
+}
+
+impl ExportFormat {
+    pub fn from_path(path: &Path, config: &ExportConfig) -> anyhow::Result<Self> {
+        let extension = path
+            .extension()
+            .and_then(|x| x.to_str())
+            .unwrap_or_default();
+
+        let format = match extension.to_lowercase().as_str() {
+            "csv" => Self::Csv {
+                delimiter: config.delimiter,
+                quote: config.quote,
+            },
+            "tsv" => Self::Csv {
+                delimiter: '\t',
+                quote: config.quote,
+            },
+            "json" => Self::Json,
+            "ndjson" | "jsonl" => Self::Ndjson,
+            "md" => Self::Markdown,
+            // @note: the file name doubles as the target table
+            "sql" => Self::Insert {
+                table: path
+                    .file_stem()
+                    .and_then(|x| x.to_str())
+                    .unwrap_or("export")
+                    .to_owned(),
+            },
+            other => anyhow::bail!("unsupported export format: {other:?}"),
+        };
+
+        Ok(format)
+    }
+}
+
+impl From<CsvQuote> for csv::QuoteStyle {
+    fn from(quote: CsvQuote) -> Self {
+        match quote {
+            CsvQuote::Minimal => Self::Necessary,
+            CsvQuote::All => Self::Always,
+            CsvQuote::NonNumeric => Self::NonNumeric,
+        }
+    }
+}
+
+// @note: the writer of each format, csv goes through the `csv` crate
+enum Output<W: Write> {
+    Csv(Box<csv::Writer<W>>),
+    Json(W),
+    Ndjson(W),
+    Markdown(W),
+    Insert(W, String),
+}
+
+pub struct RowWriter<W: Write> {
+    out: Output<W>,
+    columns: Vec<Column>,
+    rows: u64,
+}
+
+impl<W: Write> RowWriter<W> {
+    pub fn new(mut out: W, format: ExportFormat, columns: Vec<Column>) -> io::Result<Self> {
+        let out = match format {
+            ExportFormat::Csv { delimiter, quote } => {
+                if !delimiter.is_ascii() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("csv delimiter {delimiter:?} is not an ascii character"),
+                    ));
+                }
+
+                let mut out = csv::WriterBuilder::new()
+                    .delimiter(delimiter as u8)
+                    .quote_style(quote.into())
+                    .from_writer(out);
+
+                out.write_record(columns.iter().map(|c| &c.name))?;
+                Output::Csv(Box::new(out))
+            }
+            ExportFormat::Json => {
+                write!(out, "[")?;
+                Output::Json(out)
+            }
+            ExportFormat::Ndjson => Output::Ndjson(out),
+            ExportFormat::Markdown => {
+                let header = columns.iter().map(|c| markdown_cell(&c.name));
+                writeln!(out, "| {} |", join(header, " | "))?;
+                writeln!(out, "|{}", " --- |".repeat(columns.len()))?;
+                Output::Markdown(out)
+            }
+            ExportFormat::Insert { table } => Output::Insert(out, table),
+        };
+
+        Ok(Self {
+            out,
+            columns,
+            rows: 0,
+        })
+    }
+
+    pub fn write_row(&mut self, values: &[Value]) -> io::Result<()> {
+        match &mut self.out {
+            // @note: NULL is an empty field, quoted unless the quoting is minimal
+            Output::Csv(out) => {
+                let fields = values.iter().map(|value| match value {
+                    Value::Null => String::new(),
+                    value => value.to_string(),
+                });
+
+                out.write_record(fields)?;
+            }
+            Output::Json(out) => {
+                let separator = if self.rows == 0 { "" } else { "," };
+                write!(out, "{separator}\n  ")?;
+                serde_json::to_writer(&mut *out, &json_object(&self.columns, values))?;
+            }
+            Output::Ndjson(out) => {
+                serde_json::to_writer(&mut *out, &json_object(&self.columns, values))?;
+                writeln!(out)?;
+            }
+            Output::Markdown(out) => {
+                let cells = values.iter().map(|value| markdown_cell(&value.to_string()));
+                writeln!(out, "| {} |", join(cells, " | "))?;
+            }
+            Output::Insert(out, table) => {
+                let columns = self.columns.iter().map(|c| quote_ident(&c.name));
+                let values = values.iter().map(Value::to_sql_literal);
+
+                writeln!(
+                    out,
+                    "INSERT INTO {} ({}) VALUES ({});",
+                    quote_ident(table),
+                    join(columns, ", "),
+                    join(values, ", ")
+                )?;
+            }
+        }
+
+        self.rows += 1;
+        Ok(())
+    }
+
+    pub fn finish(self) -> io::Result<u64> {
+        match self.out {
+            Output::Csv(mut out) => out.flush()?,
+            Output::Json(mut out) => {
+                let newline = if self.rows == 0 { "" } else { "\n" };
+                writeln!(out, "{newline}]")?;
+                out.flush()?;
+            }
+            Output::Ndjson(mut out) | Output::Markdown(mut out) | Output::Insert(mut out, _) => {
+                out.flush()?;
+            }
+        }
+
+        Ok(self.rows)
+    }
+}
+
+fn json_object(columns: &[Column], values: &[Value]) -> serde_json::Map<String, serde_json::Value> {
+    columns
+        .iter()
+        .zip(values)
+        .map(|(c, value)| (c.name.to_owned(), value.to_json()))
+        .collect()
+}
+
+fn join(items: impl Iterator<Item = String>, separator: impl std::fmt::Display) -> String {
+    let separator = separator.to_string();
+    items.collect::<Vec<_>>().join(&separator)
+}
+
+fn markdown_cell(cell: &str) -> String {
+    cell.replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+// @note: rows are streamed from the server and written one by one,
+// the caller runs it in a read only transaction, so the statement can't change data
+#[cfg(feature = "postgres")]
+pub(super) async fn stream(
+    client: &Client,
+    sql: &str,
+    values: Option<&[Option<String>]>,
+    format: ExportFormat,
+    out: impl Write,
+) -> anyhow::Result<u64> {
+    let stmt = match values {
+        Some(_) => client.prepare(&Placeholders::parse(sql).sql()).await?,
+        None => client.prepare(sql).await?,
+    };
+
+    let columns = statement_columns(&stmt);
+    if columns.is_empty() {
+        anyhow::bail!("statement does not return rows");
+    }
+
+    let mut writer = RowWriter::new(out, format, columns)?;
+    let params = text_params(values.unwrap_or_default());
+    let stream = client.query_raw(&stmt, params.iter()).await?;
+    futures_util::pin_mut!(stream);
+
+    while let Some(row) = stream.try_next().await? {
+        writer.write_row(&row_values(&row)?)?;
+    }
+
+    Ok(writer.finish()?)
+}
+
+// @note: writes the result shown in the grid, for the drivers without row streams
+pub fn export(result: &QueryResult, format: ExportFormat, out: impl Write) -> io::Result<u64> {
+    let mut writer = RowWriter::new(out, format, result.columns.clone())?;
+
+    for row in &result.rows {
+        writer.write_row(row)?;
+    }
+
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns() -> Vec<Column> {
+        ["id", "name"]
+            .into_iter()
+            .map(|name| Column {
+                name: name.to_owned(),
+                type_name: String::new(),
+            })
+            .collect()
+    }
+
+    fn rows() -> Vec<Vec<Value>> {
+        vec![
+            vec![Value::Int(1), Value::Text("a,\"b\"".to_owned())],
+            vec![Value::Int(2), Value::Null],
+        ]
+    }
+
+    fn write(format: ExportFormat) -> io::Result<String> {
+        let result = QueryResult {
+            columns: columns(),
+            rows: rows(),
+            ..Default::default()
+        };
+
+        let mut out = vec![];
+        assert_eq!(export(&result, format, &mut out)?, 2);
+        Ok(String::from_utf8(out).expect("utf8"))
+    }
+
+    #[test]
+    fn test_export_csv() -> io::Result<()> {
+        let minimal = ExportFormat::Csv {
+            delimiter: ',',
+            quote: CsvQuote::Minimal,
+        };
+        assert_eq!(write(minimal)?, "id,name\n1,\"a,\"\"b\"\"\"\n2,\n");
+
+        let non_numeric = ExportFormat::Csv {
+            delimiter: ';',
+            quote: CsvQuote::NonNumeric,
+        };
+        assert_eq!(
+            write(non_numeric)?,
+            "\"id\";\"name\"\n1;\"a,\"\"b\"\"\"\n2;\"\"\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_json() -> io::Result<()> {
+        assert_eq!(
+            write(ExportFormat::Json)?,
+            "[\n  {\"id\":1,\"name\":\"a,\\\"b\\\"\"},\n  {\"id\":2,\"name\":null}\n]\n"
+        );
+        assert_eq!(
+            write(ExportFormat::Ndjson)?,
+            "{\"id\":1,\"name\":\"a,\\\"b\\\"\"}\n{\"id\":2,\"name\":null}\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_markdown() -> io::Result<()> {
+        assert_eq!(
+            write(ExportFormat::Markdown)?,
+            "| id | name |\n| --- | --- |\n| 1 | a,\"b\" |\n| 2 | NULL |\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_insert() -> io::Result<()> {
+        let format = ExportFormat::Insert {
+            table: "foo".to_owned(),
+        };
+
+        assert_eq!(
+            write(format)?,
+            "INSERT INTO \"foo\" (\"id\", \"name\") VALUES (1, 'a,\"b\"');\n\
+             INSERT INTO \"foo\" (\"id\", \"name\") VALUES (2, NULL);\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_from_path() -> anyhow::Result<()> {
+        let config = ExportConfig::default();
+
+        assert_eq!(
+            ExportFormat::from_path(Path::new("/tmp/users.sql"), &config)?,
+            ExportFormat::Insert {
+                table: "users".to_owned()
+            }
+        );
+        assert_eq!(
+            ExportFormat::from_path(Path::new("a.TSV"), &config)?,
+            ExportFormat::Csv {
+                delimiter: '\t',
+                quote: CsvQuote::Minimal
+            }
+        );
+        assert!(ExportFormat::from_path(Path::new("a.xlsx"), &config).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,418 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+};
+
+use tokio::sync::mpsc;
+
+use super::{
+    driver::{self, Connection},
+    Column, DriverKind, TableRef,
+};
+
+const BATCH_SIZE: usize = 1000;
+const DELIMITERS: [u8; 4] = [b',', b';', b'\t', b'|'];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvPreview {
+    pub delimiter: u8,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl CsvPreview {
+    pub fn read(path: &Path, limit: usize) -> anyhow::Result<Self> {
+        let mut first_line = String::new();
+        BufReader::new(File::open(path)?).read_line(&mut first_line)?;
+
+        let delimiter = sniff_delimiter(&first_line);
+        let mut reader = csv_reader(path, delimiter)?;
+
+        let headers = reader.headers()?.iter().map(ToOwned::to_owned).collect();
+        let rows = reader
+            .records()
+            .take(limit)
+            .map(|record| Ok(record?.iter().map(ToOwned::to_owned).collect()))
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(Self {
+            delimiter,
+            headers,
+            rows,
+        })
+    }
+}
+
+fn sniff_delimiter(line: &str) -> u8 {
+    DELIMITERS
+        .into_iter()
+        .max_by_key(|d| line.bytes().filter(|b| b == d).count())
+        .unwrap_or(b',')
+}
+
+fn csv_reader(path: &Path, delimiter: u8) -> csv::Result<csv::Reader<File>> {
+    csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_path(path)
+}
+
+// @note: csv column for each table column, matched by name
+pub fn auto_mapping(columns: &[Column], headers: &[String]) -> Vec<Option<usize>> {
+    let normalize = |name: &str| name.trim().to_lowercase().replace([' ', '-'], "_");
+
+    columns
+        .iter()
+        .map(|column| {
+            let name = normalize(&column.name);
+            headers.iter().position(|header| normalize(header) == name)
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportProgress {
+    pub rows: u64,
+    pub failed: u64,
+    pub bytes: u64,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowError {
+    pub line: u64,
+    pub message: String,
+}
+
+#[derive(Debug, Default)]
+pub struct ImportSummary {
+    pub rows: u64,
+    pub errors: Vec<RowError>,
+}
+
+// @note: the records read between two batches and the rows that couldn't be read
+#[derive(Debug, Default)]
+struct Chunk {
+    records: Vec<csv::StringRecord>,
+    errors: Vec<RowError>,
+    bytes: u64,
+}
+
+// @note: the file is read on a blocking thread, one batch ahead of the loading
+fn read_chunks(path: PathBuf, delimiter: u8) -> mpsc::Receiver<anyhow::Result<Chunk>> {
+    let (tx, rx) = mpsc::channel(1);
+
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = read_file(&path, delimiter, &tx) {
+            tx.blocking_send(Err(e)).ok();
+        }
+    });
+
+    rx
+}
+
+fn read_file(
+    path: &Path,
+    delimiter: u8,
+    tx: &mpsc::Sender<anyhow::Result<Chunk>>,
+) -> anyhow::Result<()> {
+    let mut reader = csv_reader(path, delimiter)?;
+    let headers = reader.headers()?.len();
+    let mut chunk = Chunk::default();
+
+    for record in reader.records() {
+        let record = match record {
+            Ok(record) if record.len() == headers => record,
+            Ok(record) => {
+                chunk.errors.push(RowError {
+                    line: record.position().map_or(0, |p| p.line()),
+                    message: format!("expected {headers} fields, found {}", record.len()),
+                });
+                continue;
+            }
+            Err(e) => {
+                chunk.errors.push(RowError {
+                    line: e.position().map_or(0, |p| p.line()),
+                    message: e.to_string(),
+                });
+                continue;
+            }
+        };
+
+        chunk.bytes = record.position().map_or(0, |p| p.byte());
+        chunk.records.push(record);
+
+        // @note: a closed channel is a failed load, nothing to read for
+        if chunk.records.len() == BATCH_SIZE
+            && tx.blocking_send(Ok(std::mem::take(&mut chunk))).is_err()
+        {
+            return Ok(());
+        }
+    }
+
+    tx.blocking_send(Ok(chunk)).ok();
+    Ok(())
+}
+
+pub struct CsvImport {
+    pub table: TableRef,
+    pub path: PathBuf,
+    pub delimiter: u8,
+    // @note: (table column, csv column) pairs
+    pub mapping: Vec<(String, usize)>,
+}
+
+impl CsvImport {
+    fn columns(&self, kind: DriverKind) -> String {
+        self.mapping
+            .iter()
+            .map(|(column, _)| kind.quote_ident(column))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    pub(super) fn copy_sql(&self) -> String {
+        format!(
+            "COPY {} ({}) FROM STDIN WITH (FORMAT csv)",
+            self.table.qualified_name(),
+            self.columns(DriverKind::Postgres)
+        )
+    }
+
+    // @note: as recorded in the history, the rows are loaded with COPY or with one INSERT
+    // per batch, so only the shape of the statement is kept, without the values
+    pub fn statement(&self, kind: DriverKind) -> String {
+        match kind {
+            DriverKind::Postgres => {
+                format!("-- import of {}\n{}", self.path.display(), self.copy_sql())
+            }
+            DriverKind::Mysql | DriverKind::Sqlite => format!(
+                "-- import of {}, {BATCH_SIZE} rows per statement\nINSERT INTO {} ({}) VALUES (…), …",
+                self.path.display(),
+                self.table.quoted_name(kind),
+                self.columns(kind)
+            ),
+        }
+    }
+
+    // @note: one INSERT for the batch, empty fields are NULL like with COPY
+    pub(super) fn insert_sql(&self, kind: DriverKind, records: &[csv::StringRecord]) -> String {
+        let columns = self.columns(kind);
+
+        let rows = records
+            .iter()
+            .map(|record| {
+                let values = self
+                    .mapping
+                    .iter()
+                    .map(|(_, i)| match record.get(*i).unwrap_or_default() {
+                        "" => "NULL".to_owned(),
+                        field => kind.quote_literal(field),
+                    })
+                    .collect::<Vec<_>>();
+
+                format!("({})", values.join(", "))
+            })
+            .collect::<Vec<_>>();
+
+        format!(
+            "INSERT INTO {} ({columns}) VALUES {}",
+            self.table.quoted_name(kind),
+            rows.join(", ")
+        )
+    }
+
+    // @note: re-encode mapped fields, empty ones are loaded as NULL
+    pub(super) fn encode(&self, record: &csv::StringRecord) -> Vec<u8> {
+        let fields = self
+            .mapping
+            .iter()
+            .map(|(_, i)| match record.get(*i).unwrap_or_default() {
+                "" => String::new(),
+                field => format!("\"{}\"", field.replace('"', "\"\"")),
+            })
+            .collect::<Vec<_>>();
+
+        let mut line = fields.join(",").into_bytes();
+        line.push(b'\n');
+        line
+    }
+
+    // @note: good rows are loaded in one transaction, every failing row is
+    // reported and skipped, so one typo doesn't throw away the whole file
+    pub async fn run(
+        &self,
+        conn: &dyn Connection,
+        mut progress: impl FnMut(ImportProgress),
+    ) -> anyhow::Result<ImportSummary> {
+        if self.mapping.is_empty() {
+            anyhow::bail!("no columns are mapped");
+        }
+
+        conn.fetch("BEGIN").await?;
+
+        let summary = self.load(conn, &mut progress).await;
+        let end = match summary.is_ok() {
+            true => "COMMIT",
+            false => "ROLLBACK",
+        };
+
+        driver::close(conn, &[end], summary).await
+    }
+
+    async fn load(
+        &self,
+        conn: &dyn Connection,
+        progress: &mut impl FnMut(ImportProgress),
+    ) -> anyhow::Result<ImportSummary> {
+        let mut chunks = read_chunks(self.path.clone(), self.delimiter);
+
+        let mut state = ImportProgress {
+            total_bytes: tokio::fs::metadata(&self.path).await?.len(),
+            ..Default::default()
+        };
+
+        let mut summary = ImportSummary::default();
+
+        while let Some(chunk) = chunks.recv().await {
+            let mut chunk = chunk?;
+
+            summary.errors.append(&mut chunk.errors);
+            self.load_batch(conn, &mut chunk.records, &mut summary)
+                .await?;
+
+            state.rows = summary.rows;
+            state.failed = summary.errors.len() as u64;
+            state.bytes = chunk.bytes;
+            progress(state);
+        }
+
+        // @note: unreadable rows are reported before the rejected ones of their batch
+        summary.errors.sort_by_key(|e| e.line);
+
+        state.bytes = state.total_bytes;
+        progress(state);
+
+        Ok(summary)
+    }
+
+    async fn load_batch(
+        &self,
+        conn: &dyn Connection,
+        batch: &mut Vec<csv::StringRecord>,
+        summary: &mut ImportSummary,
+    ) -> anyhow::Result<()> {
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        if let Ok(rows) = self.load_savepoint(conn, batch).await? {
+            summary.rows += rows;
+            batch.clear();
+            return Ok(());
+        }
+
+        // @note: the batch has a bad row somewhere, retry one by one to find it
+        for record in batch.drain(..) {
+            let line = record.position().map_or(0, |p| p.line());
+
+            match self.load_savepoint(conn, &[record]).await? {
+                Ok(rows) => summary.rows += rows,
+                Err(message) => summary.errors.push(RowError { line, message }),
+            }
+        }
+
+        Ok(())
+    }
+
+    // @note: the inner error is a rejected row, the outer one a broken connection
+    async fn load_savepoint(
+        &self,
+        conn: &dyn Connection,
+        records: &[csv::StringRecord],
+    ) -> anyhow::Result<Result<u64, String>> {
+        conn.fetch("SAVEPOINT dbuzzy_import").await?;
+
+        let result = conn.load_rows(self, records).await?;
+
+        if result.is_err() {
+            conn.fetch("ROLLBACK TO SAVEPOINT dbuzzy_import").await?;
+        }
+        conn.fetch("RELEASE SAVEPOINT dbuzzy_import").await?;
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    fn column(name: &str) -> Column {
+        Column {
+            name: name.to_owned(),
+            type_name: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_auto_mapping() {
+        let columns = [column("id"), column("first_name"), column("age")];
+        let headers = ["First Name", "ID", "email"].map(ToOwned::to_owned);
+
+        assert_eq!(
+            auto_mapping(&columns, &headers),
+            vec![Some(1), Some(0), None]
+        );
+    }
+
+    #[test]
+    fn test_preview() -> anyhow::Result<()> {
+        let mut path = std::env::temp_dir();
+        path.push("dbuzzy_test_preview.csv");
+
+        {
+            let mut file = File::create(&path)?;
+            write!(file, "id;name\n1;\"a;b\"\n2;c\n3;d\n")?;
+        }
+
+        let preview = CsvPreview::read(&path, 2)?;
+        std::fs::remove_file(&path)?;
+
+        assert_eq!(preview.delimiter, b';');
+        assert_eq!(preview.headers, vec!["id", "name"]);
+        assert_eq!(preview.rows, vec![vec!["1", "a;b"], vec!["2", "c"]]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode() {
+        let import = CsvImport {
+            table: TableRef::new("foo".to_owned(), "bar".to_owned()),
+            path: PathBuf::from("bar.csv"),
+            delimiter: b',',
+            mapping: vec![("b".to_owned(), 2), ("a".to_owned(), 0)],
+        };
+
+        let record = csv::StringRecord::from(vec!["x\"y", "skip", ""]);
+
+        assert_eq!(import.encode(&record), b",\"x\"\"y\"\n");
+        assert_eq!(
+            import.copy_sql(),
+            "COPY \"foo\".\"bar\" (\"b\", \"a\") FROM STDIN WITH (FORMAT csv)"
+        );
+        assert_eq!(
+            import.insert_sql(DriverKind::Mysql, &[record]),
+            "INSERT INTO `foo`.`bar` (`b`, `a`) VALUES (NULL, 'x\"y')"
+        );
+        assert_eq!(
+            import.statement(DriverKind::Sqlite),
+            "-- import of bar.csv, 1000 rows per statement\n\
+             INSERT INTO \"foo\".\"bar\" (\"b\", \"a\") VALUES (…), …"
+        );
+    }
+}
@@ -2,18 +2,66 @@
 #![allow(dead_code)]
 
 mod conn;
+#[cfg(feature = "postgres")]
+mod ddl;
+mod driver;
+mod edit;
+mod explain;
+mod export;
+mod import;
+#[cfg(feature = "mysql")]
+mod mysql;
+mod params;
+mod password;
+#[cfg(feature = "postgres")]
+mod postgres;
+mod query;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod ssh;
+mod table;
+#[cfg(feature = "postgres")]
+mod tls;
+mod transaction;
 pub mod tree;
+mod url;
+mod value;
 
-pub use conn::{ConnectionConfig, PgPool};
+pub use conn::{ConnectionConfig, SslMode};
+pub use driver::{connect, ping, CancelHandle, Connection, DbPool, Driver, DriverKind, ServerInfo};
+pub use edit::{CellChange, EditBatch, RowEdit, RowKey};
+pub use explain::{Plan, PlanNode};
+pub use export::{export, CsvQuote, ExportConfig, ExportFormat, RowWriter};
+pub use import::{auto_mapping, CsvImport, CsvPreview, ImportProgress, ImportSummary, RowError};
+pub use params::Placeholders;
+pub use password::{is_auth_error, resolve_password};
+#[cfg(feature = "postgres")]
+pub use postgres::PgPool;
+pub use query::{Column, QueryResult};
+pub use ssh::SshConfig;
+pub use table::{quote_ident, quote_literal, RowCount, TableInfo, TablePage, TableRef};
+pub use transaction::{Transaction, TransactionState};
+pub use url::set_option;
+pub use value::Value;
 
 pub type DbResult<T> = anyhow::Result<T, Error>;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
+    #[cfg(feature = "postgres")]
     #[error("Pool error: {0}")]
     PgPool(#[from] deadpool_postgres::PoolError),
+    #[cfg(feature = "postgres")]
     #[error("Create Pool error: {0}")]
     CreatePgPool(#[from] deadpool_postgres::CreatePoolError),
+    #[cfg(feature = "postgres")]
     #[error("PostgreSQL error: {0}")]
     Postgres(#[from] tokio_postgres::Error),
+    #[cfg(feature = "postgres")]
+    #[error("TLS error: {0}")]
+    Tls(#[from] native_tls::Error),
+    #[error("Failed to read {}: {1}", .0.display())]
+    ReadCert(std::path::PathBuf, #[source] std::io::Error),
+    #[error("`sslcert` and `sslkey` should be set together")]
+    ClientCert,
 }
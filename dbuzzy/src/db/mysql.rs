@@ -0,0 +1,892 @@
+use std::{sync::Arc, time::Instant};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use mysql_async::{
+    consts::ColumnType,
+    prelude::{Protocol, Queryable},
+    Conn, OptsBuilder, PoolConstraints, PoolOpts, Row,
+};
+use tokio::sync::Mutex;
+
+use super::{
+    driver::{CancelHandle, Connection, Driver, DriverKind, POOL_WAIT_TIMEOUT},
+    table::EXACT_COUNT_THRESHOLD,
+    tree::{ColumnInfo, DatabaseTree, TreeGroup, TreeItem, TreeItemKind},
+    Column, ConnectionConfig, Placeholders, Plan, PlanNode, QueryResult, RowCount, TableInfo,
+    TableRef, Value,
+};
+
+const SCHEMA_GROUPS: [TreeGroup; 3] = [TreeGroup::Tables, TreeGroup::Views, TreeGroup::Functions];
+
+const TABLE_GROUPS: [TreeGroup; 4] = [
+    TreeGroup::Columns,
+    TreeGroup::Indexes,
+    TreeGroup::ForeignKeys,
+    TreeGroup::Triggers,
+];
+
+// @note: collation id of binary strings, everything else is text
+const BINARY_CHARSET: u16 = 63;
+
+#[derive(Clone)]
+pub struct MysqlPool {
+    inner: mysql_async::Pool,
+    statement_timeout: Option<u64>,
+}
+
+impl MysqlPool {
+    pub fn create(config: &ConnectionConfig) -> anyhow::Result<Self> {
+        let host = config
+            .hostaddr
+            .map_or_else(|| config.host.to_owned(), |addr| addr.to_string());
+
+        let opts = OptsBuilder::default()
+            .ip_or_hostname(host)
+            .tcp_port(
+                config
+                    .port
+                    .or(DriverKind::Mysql.default_port())
+                    .unwrap_or_default(),
+            )
+            .user(Some(config.user.to_owned()))
+            .pass(config.password.to_owned())
+            .db_name(config.dbname.to_owned())
+            .prefer_socket(false)
+            // @note: UPDATE counts the matched rows, like the other drivers
+            .client_found_rows(true)
+            .pool_opts(
+                config
+                    .pool_size
+                    .and_then(|size| PoolConstraints::new(1, size))
+                    .map(|constraints| PoolOpts::default().with_constraints(constraints)),
+            );
+
+        Ok(Self {
+            inner: mysql_async::Pool::new(opts),
+            statement_timeout: config.statement_timeout,
+        })
+    }
+}
+
+#[async_trait]
+impl Driver for MysqlPool {
+    fn kind(&self) -> DriverKind {
+        DriverKind::Mysql
+    }
+
+    // @note: the pool resets the session of a returned connection, so the timeout is set again,
+    // mysql limits the time of SELECT statements only
+    async fn acquire(&self) -> anyhow::Result<Box<dyn Connection>> {
+        let mut conn = tokio::time::timeout(POOL_WAIT_TIMEOUT, self.inner.get_conn())
+            .await
+            .context("no free connection in the pool")??;
+
+        if let Some(timeout) = self.statement_timeout {
+            conn.query_drop(format!("SET SESSION max_execution_time = {timeout}"))
+                .await?;
+        }
+
+        Ok(Box::new(MysqlConnection {
+            id: conn.id(),
+            conn: Mutex::new(conn),
+            pool: self.inner.clone(),
+        }))
+    }
+}
+
+// @note: queries need `&mut Conn`, while a connection is shared by reference
+pub struct MysqlConnection {
+    conn: Mutex<Conn>,
+    id: u32,
+    // @note: a running query is killed from another connection
+    pool: mysql_async::Pool,
+}
+
+#[async_trait]
+impl Connection for MysqlConnection {
+    fn kind(&self) -> DriverKind {
+        DriverKind::Mysql
+    }
+
+    async fn fetch(&self, sql: &str) -> anyhow::Result<QueryResult> {
+        let started = Instant::now();
+        let mut conn = self.conn.lock().await;
+        let result = conn.query_iter(sql).await?;
+
+        collect(result, started).await
+    }
+
+    // @note: `?` are bound in the order they appear, a repeated placeholder is sent again
+    async fn fetch_params(
+        &self,
+        sql: &str,
+        values: &[Option<String>],
+    ) -> anyhow::Result<QueryResult> {
+        let started = Instant::now();
+        let placeholders = Placeholders::parse(sql);
+
+        let params = placeholders
+            .order()
+            .into_iter()
+            .map(|n| match values.get(n - 1).cloned().flatten() {
+                Some(value) => mysql_async::Value::Bytes(value.into_bytes()),
+                None => mysql_async::Value::NULL,
+            })
+            .collect::<Vec<_>>();
+
+        let mut conn = self.conn.lock().await;
+        let result = conn
+            .exec_iter(placeholders.render(|_| "?".to_owned()), params)
+            .await?;
+
+        collect(result, started).await
+    }
+
+    // @note: mysql databases play the role of schemas
+    async fn load_tree(&self) -> anyhow::Result<DatabaseTree> {
+        let mut conn = self.conn.lock().await;
+
+        let name: Option<String> = conn
+            .query_first("SELECT coalesce(database(), @@hostname)")
+            .await?;
+
+        let db_item = Arc::new(TreeItem::database(name.unwrap_or_default()));
+        let mut tree = vec![Arc::clone(&db_item)];
+
+        let schemas: Vec<String> = conn
+            .query(
+                r#"
+                    SELECT schema_name FROM information_schema.schemata
+                    WHERE schema_name NOT IN ('information_schema', 'mysql', 'performance_schema', 'sys')
+                    ORDER BY schema_name
+                "#,
+            )
+            .await?;
+
+        for schema in schemas {
+            tree.push(Arc::new(TreeItem::schema(schema, Arc::clone(&db_item))));
+        }
+
+        Ok(DatabaseTree::new(tree))
+    }
+
+    async fn children(&self, item: &Arc<TreeItem>) -> anyhow::Result<Vec<Arc<TreeItem>>> {
+        let children = match item.kind {
+            TreeItemKind::Schema { .. } => TreeItem::groups(&SCHEMA_GROUPS, item),
+            TreeItemKind::Table { .. } => TreeItem::groups(&TABLE_GROUPS, item),
+            TreeItemKind::Group { group, .. } => {
+                let mut conn = self.conn.lock().await;
+                load_group(&mut conn, group, item).await?
+            }
+            _ => vec![],
+        };
+
+        Ok(children)
+    }
+
+    async fn ddl(&self, item: &Arc<TreeItem>) -> anyhow::Result<String> {
+        let mut conn = self.conn.lock().await;
+        ddl(&mut conn, item).await
+    }
+
+    async fn table_info(&self, table: &TableRef) -> anyhow::Result<TableInfo> {
+        let mut conn = self.conn.lock().await;
+
+        let key: Vec<String> = conn
+            .exec(
+                r#"
+                    SELECT column_name FROM information_schema.statistics
+                    WHERE table_schema = ? AND table_name = ? AND index_name = (
+                        SELECT index_name FROM information_schema.statistics
+                        WHERE table_schema = ? AND table_name = ? AND non_unique = 0
+                        GROUP BY index_name
+                        HAVING sum(nullable = 'YES' OR column_name IS NULL) = 0
+                        ORDER BY index_name = 'PRIMARY' DESC, index_name
+                        LIMIT 1
+                    )
+                    ORDER BY seq_in_index
+                "#,
+                (&table.schema, &table.name, &table.schema, &table.name),
+            )
+            .await?;
+
+        let estimate: Option<Option<i64>> = conn
+            .exec_first(
+                r#"
+                    SELECT table_rows FROM information_schema.tables
+                    WHERE table_schema = ? AND table_name = ?
+                "#,
+                (&table.schema, &table.name),
+            )
+            .await?;
+
+        let rows = match estimate.flatten() {
+            Some(estimate) if estimate >= EXACT_COUNT_THRESHOLD => RowCount::Estimated(estimate),
+            _ => {
+                let sql = format!("SELECT count(*) FROM {}", table.quoted_name(self.kind()));
+                let count: Option<i64> = conn.query_first(sql).await?;
+                RowCount::Exact(count.unwrap_or_default())
+            }
+        };
+
+        Ok(TableInfo { key, rows })
+    }
+
+    async fn table_columns(&self, table: &TableRef) -> anyhow::Result<Vec<Column>> {
+        let mut conn = self.conn.lock().await;
+
+        let columns: Vec<(String, String)> = conn
+            .exec(
+                r#"
+                    SELECT column_name, column_type FROM information_schema.columns
+                    WHERE table_schema = ? AND table_name = ?
+                    ORDER BY ordinal_position
+                "#,
+                (&table.schema, &table.name),
+            )
+            .await?;
+
+        Ok(columns
+            .into_iter()
+            .map(|(name, type_name)| Column { name, type_name })
+            .collect())
+    }
+
+    async fn explain(&self, sql: &str, analyze: bool) -> anyhow::Result<Plan> {
+        let sql = sql.trim().trim_end_matches(';');
+        let sql = match analyze {
+            true => format!("EXPLAIN ANALYZE {sql}"),
+            false => format!("EXPLAIN FORMAT=TREE {sql}"),
+        };
+
+        let mut conn = self.conn.lock().await;
+        let tree: Option<String> = conn.query_first(sql).await?;
+
+        parse_plan(&tree.context("no plan in the output")?)
+    }
+
+    fn cancel_handle(&self) -> Option<Box<dyn CancelHandle>> {
+        Some(Box::new(MysqlCancel {
+            pool: self.pool.clone(),
+            id: self.id,
+        }))
+    }
+}
+
+struct MysqlCancel {
+    pool: mysql_async::Pool,
+    id: u32,
+}
+
+#[async_trait]
+impl CancelHandle for MysqlCancel {
+    async fn cancel(&self) -> anyhow::Result<()> {
+        let mut conn = self.pool.get_conn().await?;
+        conn.query_drop(format!("KILL QUERY {}", self.id)).await?;
+        Ok(())
+    }
+}
+
+async fn collect<P: Protocol>(
+    mut result: mysql_async::QueryResult<'_, '_, P>,
+    started: Instant,
+) -> anyhow::Result<QueryResult> {
+    let columns = result.columns().unwrap_or_else(|| Arc::new([]));
+
+    if columns.is_empty() {
+        let affected = result.affected_rows();
+        result.drop_result().await?;
+
+        return Ok(QueryResult {
+            affected: Some(affected),
+            elapsed: started.elapsed(),
+            ..Default::default()
+        });
+    }
+
+    let rows = result
+        .collect::<Row>()
+        .await?
+        .into_iter()
+        .map(|row| {
+            row.unwrap()
+                .into_iter()
+                .zip(columns.iter())
+                .map(|(v, column)| value(column, v))
+                .collect()
+        })
+        .collect();
+
+    // @note: only the first result set is shown
+    result.drop_result().await?;
+
+    Ok(QueryResult {
+        columns: columns
+            .iter()
+            .map(|c| Column {
+                name: c.name_str().into_owned(),
+                type_name: type_name(c).to_owned(),
+            })
+            .collect(),
+        rows,
+        affected: None,
+        elapsed: started.elapsed(),
+    })
+}
+
+// @note: the server rebuilds the CREATE statements, the table ones hold indexes and keys
+async fn ddl(conn: &mut Conn, item: &TreeItem) -> anyhow::Result<String> {
+    let schema = item
+        .schema_name()
+        .with_context(|| format!("no DDL for {}", item.name))?;
+    let kind = DriverKind::Mysql;
+    let object = TableRef::new(schema.to_owned(), item.name.to_owned());
+
+    match &item.kind {
+        TreeItemKind::Schema { .. } => {
+            show_create(conn, "DATABASE", kind.quote_ident(schema), 1).await
+        }
+        TreeItemKind::Table { .. } => show_create(conn, "TABLE", object.quoted_name(kind), 1).await,
+        TreeItemKind::Column {
+            data_type,
+            nullable,
+            default,
+            group,
+        } => {
+            let table = group.parent().context("column should have a table")?;
+            let table = TableRef::new(schema.to_owned(), table.name.to_owned());
+
+            let mut sql = format!(
+                "ALTER TABLE {} ADD COLUMN {} {data_type}",
+                table.quoted_name(kind),
+                kind.quote_ident(&item.name)
+            );
+            if !nullable {
+                sql.push_str(" NOT NULL");
+            }
+            if let Some(default) = default {
+                sql = format!("{sql} DEFAULT {}", kind.quote_literal(default));
+            }
+
+            Ok(format!("{sql};"))
+        }
+        TreeItemKind::Object { definition, group } => {
+            let group_kind = group.group_kind().context("object should be in a group")?;
+            let table = || {
+                let table = group.parent().context("object should have a table")?;
+                anyhow::Ok(TableRef::new(schema.to_owned(), table.name.to_owned()))
+            };
+
+            match group_kind {
+                TreeGroup::Views => show_create(conn, "VIEW", object.quoted_name(kind), 1).await,
+                // @note: the tree keeps `procedure` or the return type as the definition
+                TreeGroup::Functions => {
+                    let what = match definition.as_str() {
+                        "procedure" => "PROCEDURE",
+                        _ => "FUNCTION",
+                    };
+                    show_create(conn, what, object.quoted_name(kind), 2).await
+                }
+                TreeGroup::Triggers => {
+                    show_create(conn, "TRIGGER", object.quoted_name(kind), 2).await
+                }
+                TreeGroup::Indexes => {
+                    let table = table()?;
+                    let index: Option<(bool, String, String)> = conn
+                        .exec_first(
+                            r#"
+                                SELECT
+                                    non_unique = 0, index_type,
+                                    group_concat(column_name ORDER BY seq_in_index SEPARATOR '\0')
+                                FROM information_schema.statistics
+                                WHERE table_schema = ? AND table_name = ? AND index_name = ?
+                                GROUP BY non_unique, index_type
+                            "#,
+                            (schema, &table.name, &item.name),
+                        )
+                        .await?;
+
+                    let (unique, index_type, columns) =
+                        index.with_context(|| format!("{table} has no index {}", item.name))?;
+                    let columns = columns
+                        .split('\0')
+                        .map(|column| kind.quote_ident(column))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+
+                    let table = table.quoted_name(kind);
+                    let prefix = match index_type.as_str() {
+                        "FULLTEXT" | "SPATIAL" => format!("{index_type} "),
+                        _ if unique => "UNIQUE ".to_owned(),
+                        _ => String::new(),
+                    };
+
+                    Ok(match item.name.as_str() {
+                        "PRIMARY" => format!("ALTER TABLE {table} ADD PRIMARY KEY ({columns});"),
+                        name => format!(
+                            "CREATE {prefix}INDEX {} ON {table} ({columns});",
+                            kind.quote_ident(name)
+                        ),
+                    })
+                }
+                TreeGroup::ForeignKeys => Ok(format!(
+                    "ALTER TABLE {} ADD CONSTRAINT {} {definition};",
+                    table()?.quoted_name(kind),
+                    kind.quote_ident(&item.name)
+                )),
+                _ => anyhow::bail!("unexpected object in {}", group_kind.as_ref()),
+            }
+        }
+        TreeItemKind::Database | TreeItemKind::Group { .. } => {
+            anyhow::bail!("no DDL for {}", item.name)
+        }
+    }
+}
+
+// @note: the statement is in the column `index` of the SHOW CREATE output
+async fn show_create(
+    conn: &mut Conn,
+    what: &str,
+    name: String,
+    index: usize,
+) -> anyhow::Result<String> {
+    let row: Option<Row> = conn
+        .query_first(format!("SHOW CREATE {what} {name}"))
+        .await?;
+    let ddl = row
+        .and_then(|row| row.get::<String, _>(index))
+        .with_context(|| format!("no DDL for {name}"))?;
+
+    Ok(format!("{ddl};"))
+}
+
+// @note: the TREE format nests the nodes by 4 spaces, a line reads
+// `-> Table scan on t  (cost=0.35 rows=1) (actual time=0.02..0.03 rows=1 loops=1)`
+fn parse_plan(tree: &str) -> anyhow::Result<Plan> {
+    let mut nodes = vec![];
+
+    for line in tree.lines() {
+        let text = line.trim_start();
+        let Some(text) = text.strip_prefix("-> ") else {
+            continue;
+        };
+
+        let depth = u8::try_from((line.len() - text.len() - 3) / 4)?;
+        let (description, stats) = text.split_once("  (").unwrap_or((text, ""));
+
+        let (node_type, detail) = match description.split_once(": ") {
+            Some((node_type, detail)) => (node_type, Some(detail.to_owned())),
+            None => match description.split_once(" on ") {
+                Some((node_type, relation)) => (node_type, Some(format!("on {relation}"))),
+                None => (description, None),
+            },
+        };
+
+        let (estimate, actual) = stats.split_once(") (actual ").unwrap_or((stats, ""));
+        let stat = |stats: &str, key: &str| {
+            stats
+                .split(|c: char| c.is_whitespace() || c == '(' || c == ')')
+                .find_map(|pair| pair.strip_prefix(key)?.strip_prefix('='))
+                .map(ToOwned::to_owned)
+        };
+        let float = |stats: &str, key: &str| stat(stats, key).and_then(|v| v.parse::<f64>().ok());
+
+        let (startup_cost, total_cost) = match stat(estimate, "cost") {
+            Some(cost) => match cost.split_once("..") {
+                Some((startup, total)) => (startup.parse()?, total.parse()?),
+                None => (0.0, cost.parse()?),
+            },
+            None => (0.0, 0.0),
+        };
+
+        let loops = float(actual, "loops").map_or(1, |loops| loops as u64);
+        // @note: the time of the last row of a loop, pg style it is summed over the loops
+        let actual_time = stat(actual, "time")
+            .and_then(|time| time.split_once("..")?.1.parse::<f64>().ok())
+            .map(|time| time * loops as f64);
+
+        nodes.push(PlanNode {
+            depth,
+            node_type: node_type.to_owned(),
+            detail,
+            startup_cost,
+            total_cost,
+            plan_rows: float(estimate, "rows").unwrap_or_default(),
+            actual_rows: float(actual, "rows"),
+            actual_time,
+            loops,
+            shared_hit: None,
+            shared_read: None,
+        });
+    }
+
+    anyhow::ensure!(!nodes.is_empty(), "no plan in the output");
+
+    Ok(Plan::new(nodes))
+}
+
+async fn load_group(
+    conn: &mut Conn,
+    group: TreeGroup,
+    item: &Arc<TreeItem>,
+) -> anyhow::Result<Vec<Arc<TreeItem>>> {
+    let schema = item.schema_name().context("group should have a schema")?;
+    let table = item.parent().context("group should have a parent")?;
+
+    let children: Vec<TreeItem> = match group {
+        TreeGroup::Tables | TreeGroup::Views => {
+            let table_type = match group {
+                TreeGroup::Tables => "BASE TABLE",
+                _ => "VIEW",
+            };
+
+            let names: Vec<String> = conn
+                .exec(
+                    r#"
+                        SELECT table_name FROM information_schema.tables
+                        WHERE table_schema = ? AND table_type = ?
+                        ORDER BY table_name
+                    "#,
+                    (schema, table_type),
+                )
+                .await?;
+
+            names
+                .into_iter()
+                .map(|name| match group {
+                    TreeGroup::Tables => TreeItem::table(name, Arc::clone(item)),
+                    _ => TreeItem::object(name, String::new(), Arc::clone(item)),
+                })
+                .collect()
+        }
+        TreeGroup::Columns => {
+            let columns: Vec<(String, String, bool, Option<String>)> = conn
+                .exec(
+                    r#"
+                        SELECT column_name, column_type, is_nullable = 'YES', column_default
+                        FROM information_schema.columns
+                        WHERE table_schema = ? AND table_name = ?
+                        ORDER BY ordinal_position
+                    "#,
+                    (schema, &table.name),
+                )
+                .await?;
+
+            columns
+                .into_iter()
+                .map(|(name, data_type, nullable, default)| {
+                    let column = ColumnInfo {
+                        name,
+                        data_type,
+                        nullable,
+                        default,
+                    };
+
+                    TreeItem::column(column, Arc::clone(item))
+                })
+                .collect()
+        }
+        _ => {
+            let (sql, params) = match group {
+                TreeGroup::Functions => (
+                    r#"
+                        SELECT
+                            routine_name,
+                            if(routine_type = 'PROCEDURE', 'procedure', dtd_identifier)
+                        FROM information_schema.routines
+                        WHERE routine_schema = ?
+                        ORDER BY routine_name
+                    "#,
+                    vec![schema],
+                ),
+                TreeGroup::Indexes => (
+                    r#"
+                        SELECT
+                            index_name,
+                            concat(
+                                if(non_unique = 0, 'UNIQUE ', ''), index_type, ' (',
+                                group_concat(column_name ORDER BY seq_in_index SEPARATOR ', '), ')'
+                            )
+                        FROM information_schema.statistics
+                        WHERE table_schema = ? AND table_name = ?
+                        GROUP BY index_name, non_unique, index_type
+                        ORDER BY index_name
+                    "#,
+                    vec![schema, &table.name],
+                ),
+                TreeGroup::ForeignKeys => (
+                    r#"
+                        SELECT
+                            constraint_name,
+                            concat(
+                                'FOREIGN KEY (',
+                                group_concat(column_name ORDER BY ordinal_position SEPARATOR ', '),
+                                ') REFERENCES ', referenced_table_schema, '.', referenced_table_name, '(',
+                                group_concat(referenced_column_name ORDER BY ordinal_position SEPARATOR ', '),
+                                ')'
+                            )
+                        FROM information_schema.key_column_usage
+                        WHERE table_schema = ? AND table_name = ? AND referenced_table_name IS NOT NULL
+                        GROUP BY constraint_name, referenced_table_schema, referenced_table_name
+                        ORDER BY constraint_name
+                    "#,
+                    vec![schema, &table.name],
+                ),
+                TreeGroup::Triggers => (
+                    r#"
+                        SELECT trigger_name, concat(action_timing, ' ', event_manipulation)
+                        FROM information_schema.triggers
+                        WHERE event_object_schema = ? AND event_object_table = ?
+                        ORDER BY trigger_name
+                    "#,
+                    vec![schema, &table.name],
+                ),
+                _ => return Ok(vec![]),
+            };
+
+            let objects: Vec<(String, String)> = conn.exec(sql, params).await?;
+
+            objects
+                .into_iter()
+                .map(|(name, definition)| TreeItem::object(name, definition, Arc::clone(item)))
+                .collect()
+        }
+    };
+
+    Ok(children.into_iter().map(Arc::new).collect())
+}
+
+fn type_name(column: &mysql_async::Column) -> &'static str {
+    let binary = column.character_set() == BINARY_CHARSET;
+
+    match column.column_type() {
+        ColumnType::MYSQL_TYPE_TINY => "tinyint",
+        ColumnType::MYSQL_TYPE_SHORT => "smallint",
+        ColumnType::MYSQL_TYPE_INT24 => "mediumint",
+        ColumnType::MYSQL_TYPE_LONG => "int",
+        ColumnType::MYSQL_TYPE_LONGLONG => "bigint",
+        ColumnType::MYSQL_TYPE_FLOAT => "float",
+        ColumnType::MYSQL_TYPE_DOUBLE => "double",
+        ColumnType::MYSQL_TYPE_DECIMAL | ColumnType::MYSQL_TYPE_NEWDECIMAL => "decimal",
+        ColumnType::MYSQL_TYPE_YEAR => "year",
+        ColumnType::MYSQL_TYPE_DATE | ColumnType::MYSQL_TYPE_NEWDATE => "date",
+        ColumnType::MYSQL_TYPE_TIME | ColumnType::MYSQL_TYPE_TIME2 => "time",
+        ColumnType::MYSQL_TYPE_DATETIME | ColumnType::MYSQL_TYPE_DATETIME2 => "datetime",
+        ColumnType::MYSQL_TYPE_TIMESTAMP | ColumnType::MYSQL_TYPE_TIMESTAMP2 => "timestamp",
+        ColumnType::MYSQL_TYPE_JSON => "json",
+        ColumnType::MYSQL_TYPE_BIT => "bit",
+        ColumnType::MYSQL_TYPE_ENUM => "enum",
+        ColumnType::MYSQL_TYPE_SET => "set",
+        ColumnType::MYSQL_TYPE_GEOMETRY => "geometry",
+        ColumnType::MYSQL_TYPE_NULL => "null",
+        ColumnType::MYSQL_TYPE_STRING if binary => "binary",
+        ColumnType::MYSQL_TYPE_STRING => "char",
+        ColumnType::MYSQL_TYPE_VARCHAR | ColumnType::MYSQL_TYPE_VAR_STRING if binary => "varbinary",
+        ColumnType::MYSQL_TYPE_VARCHAR | ColumnType::MYSQL_TYPE_VAR_STRING => "varchar",
+        _ if binary => "blob",
+        _ => "text",
+    }
+}
+
+// @note: the text protocol sends every value as a string, the column type tells what it is,
+// dates and times of prepared statements are read from their text too
+fn value(column: &mysql_async::Column, value: mysql_async::Value) -> Value {
+    use mysql_async::Value as My;
+
+    let bytes = match value {
+        My::NULL => return Value::Null,
+        My::Bytes(bytes) => bytes,
+        My::Int(v) => return Value::Int(v),
+        My::UInt(v) => return i64::try_from(v).map_or(Value::Numeric(v.to_string()), Value::Int),
+        My::Float(v) => return Value::Float(v.into()),
+        My::Double(v) => return Value::Float(v),
+        v => v.as_sql(true).trim_matches('\'').as_bytes().to_vec(),
+    };
+
+    let text = String::from_utf8_lossy(&bytes).into_owned();
+
+    match column.column_type() {
+        ColumnType::MYSQL_TYPE_TINY
+        | ColumnType::MYSQL_TYPE_SHORT
+        | ColumnType::MYSQL_TYPE_INT24
+        | ColumnType::MYSQL_TYPE_LONG
+        | ColumnType::MYSQL_TYPE_LONGLONG
+        | ColumnType::MYSQL_TYPE_YEAR => {
+            // @note: unsigned bigint may not fit
+            text.parse().map_or(Value::Numeric(text), Value::Int)
+        }
+        ColumnType::MYSQL_TYPE_FLOAT | ColumnType::MYSQL_TYPE_DOUBLE => {
+            text.parse().map_or(Value::Text(text), Value::Float)
+        }
+        ColumnType::MYSQL_TYPE_DECIMAL | ColumnType::MYSQL_TYPE_NEWDECIMAL => Value::Numeric(text),
+        // @note: zero dates and times out of a day range stay text
+        ColumnType::MYSQL_TYPE_DATE | ColumnType::MYSQL_TYPE_NEWDATE => {
+            NaiveDate::parse_from_str(&text, "%Y-%m-%d").map_or(Value::Text(text), Value::Date)
+        }
+        ColumnType::MYSQL_TYPE_TIME | ColumnType::MYSQL_TYPE_TIME2 => {
+            NaiveTime::parse_from_str(&text, "%H:%M:%S%.f").map_or(Value::Text(text), Value::Time)
+        }
+        ColumnType::MYSQL_TYPE_DATETIME
+        | ColumnType::MYSQL_TYPE_DATETIME2
+        | ColumnType::MYSQL_TYPE_TIMESTAMP
+        | ColumnType::MYSQL_TYPE_TIMESTAMP2 => {
+            NaiveDateTime::parse_from_str(&text, "%Y-%m-%d %H:%M:%S%.f")
+                .map_or(Value::Text(text), Value::Timestamp)
+        }
+        ColumnType::MYSQL_TYPE_JSON => {
+            serde_json::from_slice(&bytes).map_or(Value::Text(text), Value::Json)
+        }
+        ColumnType::MYSQL_TYPE_BIT | ColumnType::MYSQL_TYPE_GEOMETRY => Value::Bytes(bytes),
+        _ if column.character_set() == BINARY_CHARSET => Value::Bytes(bytes),
+        _ => Value::Text(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(column_type: ColumnType, charset: u16) -> mysql_async::Column {
+        mysql_async::Column::new(column_type).with_character_set(charset)
+    }
+
+    #[test]
+    fn test_value() {
+        const UTF8: u16 = 255;
+
+        let cases = [
+            (ColumnType::MYSQL_TYPE_LONG, UTF8, "42", Value::Int(42)),
+            (
+                ColumnType::MYSQL_TYPE_LONGLONG,
+                UTF8,
+                "18446744073709551615",
+                Value::Numeric("18446744073709551615".to_owned()),
+            ),
+            (
+                ColumnType::MYSQL_TYPE_DOUBLE,
+                UTF8,
+                "1.5",
+                Value::Float(1.5),
+            ),
+            (
+                ColumnType::MYSQL_TYPE_NEWDECIMAL,
+                UTF8,
+                "10.50",
+                Value::Numeric("10.50".to_owned()),
+            ),
+            (
+                ColumnType::MYSQL_TYPE_DATE,
+                UTF8,
+                "2024-02-29",
+                Value::Date(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()),
+            ),
+            (
+                ColumnType::MYSQL_TYPE_DATE,
+                UTF8,
+                "0000-00-00",
+                Value::Text("0000-00-00".to_owned()),
+            ),
+            (
+                ColumnType::MYSQL_TYPE_TIME,
+                UTF8,
+                "838:59:59",
+                Value::Text("838:59:59".to_owned()),
+            ),
+            (
+                ColumnType::MYSQL_TYPE_DATETIME,
+                UTF8,
+                "2024-02-29 10:20:30.5",
+                Value::Timestamp(
+                    NaiveDate::from_ymd_opt(2024, 2, 29)
+                        .unwrap()
+                        .and_hms_milli_opt(10, 20, 30, 500)
+                        .unwrap(),
+                ),
+            ),
+            (
+                ColumnType::MYSQL_TYPE_JSON,
+                BINARY_CHARSET,
+                r#"{"a": 1}"#,
+                Value::Json(serde_json::json!({"a": 1})),
+            ),
+            (
+                ColumnType::MYSQL_TYPE_VAR_STRING,
+                UTF8,
+                "foo",
+                Value::Text("foo".to_owned()),
+            ),
+            (
+                ColumnType::MYSQL_TYPE_BLOB,
+                BINARY_CHARSET,
+                "\x01\x02",
+                Value::Bytes(vec![1, 2]),
+            ),
+        ];
+
+        for (column_type, charset, text, expected) in cases {
+            let column = column(column_type, charset);
+            let actual = value(&column, mysql_async::Value::Bytes(text.as_bytes().to_vec()));
+            assert_eq!(actual, expected, "{text}");
+        }
+
+        let column = column(ColumnType::MYSQL_TYPE_LONG, UTF8);
+        assert_eq!(value(&column, mysql_async::Value::NULL), Value::Null);
+
+        let column = mysql_async::Column::new(ColumnType::MYSQL_TYPE_DATE);
+        let date = mysql_async::Value::Date(2024, 2, 29, 0, 0, 0, 0);
+        assert_eq!(
+            value(&column, date),
+            Value::Date(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_plan() -> anyhow::Result<()> {
+        let plan = parse_plan(
+            "-> Nested loop inner join  (cost=0.70 rows=2) (actual time=0.05..0.08 rows=2 loops=1)
+    -> Filter: (t1.a > 1)  (cost=0.35 rows=1) (actual time=0.02..0.03 rows=1 loops=1)
+        -> Table scan on t1  (cost=0.35 rows=1) (actual time=0.01..0.02 rows=1 loops=1)
+    -> Index lookup on t2 using a (a=t1.a)  (cost=0.35 rows=1) (actual time=0.01..0.02 rows=2 loops=1)
+",
+        )?;
+
+        let nodes = plan.nodes();
+        assert_eq!(
+            nodes
+                .iter()
+                .map(|node| (node.depth, node.node_type.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (0, "Nested loop inner join"),
+                (1, "Filter"),
+                (2, "Table scan"),
+                (1, "Index lookup"),
+            ]
+        );
+        assert_eq!(nodes[1].detail.as_deref(), Some("(t1.a > 1)"));
+        assert_eq!(nodes[3].detail.as_deref(), Some("on t2 using a (a=t1.a)"));
+        assert_eq!(nodes[0].total_cost, 0.70);
+        assert_eq!(nodes[0].plan_rows, 2.0);
+        assert_eq!(nodes[0].actual_time, Some(0.08));
+        assert_eq!(nodes[3].actual_rows, Some(2.0));
+
+        let plan = parse_plan("-> Rows fetched before execution  (cost=0..0 rows=1)")?;
+        assert_eq!(plan.nodes()[0].actual_rows, None);
+        assert!(!plan.is_analyzed());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_type_name() {
+        assert_eq!(type_name(&column(ColumnType::MYSQL_TYPE_LONG, 63)), "int");
+        assert_eq!(type_name(&column(ColumnType::MYSQL_TYPE_BLOB, 255)), "text");
+        assert_eq!(type_name(&column(ColumnType::MYSQL_TYPE_BLOB, 63)), "blob");
+        assert_eq!(
+            type_name(&column(ColumnType::MYSQL_TYPE_VAR_STRING, 63)),
+            "varbinary"
+        );
+    }
+}
@@ -0,0 +1,282 @@
+// @note: `:name` and `$1` placeholders of a statement, strings, quoted identifiers,
+// comments and `::` casts are skipped
+#[derive(Debug, PartialEq, Eq)]
+pub struct Placeholders {
+    pieces: Vec<Piece>,
+    // @note: labels of `$1`, `$2`, ..., named ones are numbered after the positional
+    params: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Piece {
+    Text(String),
+    Param(usize),
+}
+
+impl Placeholders {
+    pub fn parse(sql: &str) -> Self {
+        if !takes_params(sql) {
+            return Self {
+                pieces: vec![Piece::Text(sql.to_owned())],
+                params: vec![],
+            };
+        }
+
+        let chars = sql.chars().collect::<Vec<_>>();
+
+        let mut raw = vec![];
+        let mut text = String::new();
+        let mut positional = 0;
+        let mut i = 0;
+
+        while i < chars.len() {
+            let start = i;
+
+            match (chars[i], chars.get(i + 1).copied()) {
+                ('\'' | '"', _) => i = skip_quoted(&chars, i),
+                ('-', Some('-')) => {
+                    i = position(&chars, i, "\n").map_or(chars.len(), |end| end + 1);
+                }
+                ('/', Some('*')) => {
+                    i = position(&chars, i + 2, "*/").map_or(chars.len(), |end| end + 2);
+                }
+                (':', Some(':')) => i += 2,
+                (':', Some(next)) if is_ident_start(next) && !is_ident(&chars, i) => {
+                    let end = ident_end(&chars, i + 1);
+                    let name = chars[i + 1..end].iter().collect::<String>();
+
+                    raw.push(RawPiece::Text(std::mem::take(&mut text)));
+                    raw.push(RawPiece::Named(name));
+                    i = end;
+                    continue;
+                }
+                // @note: the numbers start at 1, `$0` and the ones too big are kept as text
+                ('$', Some('1'..='9')) if !is_ident(&chars, i) => {
+                    let end = (i + 1..chars.len())
+                        .find(|&j| !chars[j].is_ascii_digit())
+                        .unwrap_or(chars.len());
+                    let n = chars[i + 1..end].iter().collect::<String>().parse();
+
+                    if let Ok(n) = n {
+                        positional = positional.max(n);
+                        raw.push(RawPiece::Text(std::mem::take(&mut text)));
+                        raw.push(RawPiece::Positional(n));
+                        i = end;
+                        continue;
+                    }
+
+                    i = end;
+                }
+                ('$', _) if !is_ident(&chars, i) => i = skip_dollar_quoted(&chars, i),
+                _ => i += 1,
+            }
+
+            text.extend(&chars[start..i]);
+        }
+
+        raw.push(RawPiece::Text(text));
+
+        let mut params = (1..=positional)
+            .map(|n| format!("${n}"))
+            .collect::<Vec<_>>();
+
+        let pieces = raw
+            .into_iter()
+            .filter(|piece| !matches!(piece, RawPiece::Text(text) if text.is_empty()))
+            .map(|piece| match piece {
+                RawPiece::Text(text) => Piece::Text(text),
+                RawPiece::Positional(n) => Piece::Param(n),
+                RawPiece::Named(name) => {
+                    let label = format!(":{name}");
+                    let n = match params.iter().position(|p| *p == label) {
+                        Some(i) => i + 1,
+                        None => {
+                            params.push(label);
+                            params.len()
+                        }
+                    };
+                    Piece::Param(n)
+                }
+            })
+            .collect();
+
+        Self { pieces, params }
+    }
+
+    pub fn params(&self) -> &[String] {
+        &self.params
+    }
+
+    // @note: named placeholders replaced with `$n`
+    pub fn sql(&self) -> String {
+        self.render(|n| format!("${n}"))
+    }
+
+    // @note: numbers of the placeholders in the order they appear, for drivers with bare `?`
+    pub(super) fn order(&self) -> Vec<usize> {
+        self.pieces
+            .iter()
+            .filter_map(|piece| match piece {
+                Piece::Param(n) => Some(*n),
+                Piece::Text(_) => None,
+            })
+            .collect()
+    }
+
+    pub(super) fn render(&self, param: impl Fn(usize) -> String) -> String {
+        self.pieces
+            .iter()
+            .map(|piece| match piece {
+                Piece::Text(text) => text.clone(),
+                Piece::Param(n) => param(*n),
+            })
+            .collect()
+    }
+}
+
+enum RawPiece {
+    Text(String),
+    Positional(usize),
+    Named(String),
+}
+
+// @note: `$1` of PREPARE and of function bodies are their arguments, not placeholders
+fn takes_params(sql: &str) -> bool {
+    let mut rest = sql.trim_start();
+
+    loop {
+        if let Some(comment) = rest.strip_prefix("--") {
+            rest = comment
+                .split_once('\n')
+                .map_or("", |(_, rest)| rest)
+                .trim_start();
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            rest = comment
+                .split_once("*/")
+                .map_or("", |(_, rest)| rest)
+                .trim_start();
+        } else {
+            break;
+        }
+    }
+
+    let words = rest
+        .split(|ch: char| !ch.is_ascii_alphabetic())
+        .filter(|word| !word.is_empty())
+        .take(4)
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>();
+
+    let words = words.iter().map(String::as_str).collect::<Vec<_>>();
+
+    !matches!(
+        words.as_slice(),
+        ["prepare", ..]
+            | ["create", "function" | "procedure", ..]
+            | ["create", "or", "replace", "function" | "procedure"]
+    )
+}
+
+const fn is_ident_start(ch: char) -> bool {
+    ch.is_ascii_alphabetic() || ch == '_'
+}
+
+// @note: the char before `i` continues an identifier, like in `a:b` or `price$1`
+fn is_ident(chars: &[char], i: usize) -> bool {
+    i > 0 && (chars[i - 1].is_alphanumeric() || chars[i - 1] == '_' || chars[i - 1] == '$')
+}
+
+fn ident_end(chars: &[char], start: usize) -> usize {
+    (start..chars.len())
+        .find(|&j| !(chars[j].is_alphanumeric() || chars[j] == '_'))
+        .unwrap_or(chars.len())
+}
+
+fn position(chars: &[char], start: usize, pattern: &str) -> Option<usize> {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    (start..chars.len()).find(|&j| chars[j..].starts_with(&pattern))
+}
+
+// @note: `''` and `""` escape the quote
+fn skip_quoted(chars: &[char], start: usize) -> usize {
+    let quote = chars[start];
+    let mut i = start + 1;
+
+    while i < chars.len() {
+        if chars[i] == quote {
+            if chars.get(i + 1) == Some(&quote) {
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        i += 1;
+    }
+
+    chars.len()
+}
+
+// @note: `$$...$$` or `$tag$...$tag$`, a lone `$` is kept as is
+fn skip_dollar_quoted(chars: &[char], start: usize) -> usize {
+    let end = ident_end(chars, start + 1);
+
+    if chars.get(end) != Some(&'$') {
+        return start + 1;
+    }
+
+    let tag = chars[start..=end].iter().collect::<String>();
+    position(chars, end + 1, &tag).map_or(chars.len(), |close| close + tag.chars().count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placeholders() {
+        let p = Placeholders::parse("select * from t where a = :id and b = :name or c = :id");
+        assert_eq!(p.params(), [":id", ":name"]);
+        assert_eq!(p.sql(), "select * from t where a = $1 and b = $2 or c = $1");
+
+        let p = Placeholders::parse("update t set a = $2 where b = $1 and c = :c");
+        assert_eq!(p.params(), ["$1", "$2", ":c"]);
+        assert_eq!(p.sql(), "update t set a = $2 where b = $1 and c = $3");
+
+        let p = Placeholders::parse("select :b, $1, :b");
+        assert_eq!(p.order(), [2, 1, 2]);
+        assert_eq!(p.render(|_| "?".to_owned()), "select ?, ?, ?");
+
+        let sql = "select ':a', \":b\", now()::date, 'it''s :c' -- :d\n\
+                   /* $1 */ from f($$ :e $$, $x$ $2 $x$) where x = 1";
+        let p = Placeholders::parse(sql);
+        assert!(p.params().is_empty());
+        assert_eq!(p.sql(), sql);
+
+        let p = Placeholders::parse("select a:b, price$1 from t");
+        assert!(p.params().is_empty());
+
+        let p = Placeholders::parse("select $0, $99999999999999999999999, $1");
+        assert_eq!(p.params(), ["$1"]);
+        assert_eq!(p.sql(), "select $0, $99999999999999999999999, $1");
+    }
+
+    #[test]
+    fn test_no_placeholders() {
+        let statements = [
+            "PREPARE q (int) AS SELECT * FROM t WHERE id = $1",
+            "-- find\n/* by id */ prepare q as select $1",
+            "create function f(int) returns int language sql return $1 + 1",
+            "CREATE OR REPLACE PROCEDURE p(a int) LANGUAGE sql BEGIN ATOMIC SELECT $1; END",
+            "create function f(a int) returns int as $body$ select :a + $1 $body$ language sql",
+        ];
+
+        for sql in statements {
+            let p = Placeholders::parse(sql);
+            assert!(p.params().is_empty(), "{sql}");
+            assert_eq!(p.sql(), sql);
+        }
+
+        let p = Placeholders::parse("do $$ begin perform $1; end $$; select :a");
+        assert_eq!(p.params(), [":a"]);
+    }
+}
@@ -0,0 +1,220 @@
+use std::{ffi::OsString, path::PathBuf};
+
+use anyhow::Context;
+
+use super::{ConnectionConfig, DriverKind};
+
+// @note: first of `password`, `password_command`, `password_env`, the driver env var and ~/.pgpass
+pub async fn resolve_password(config: &ConnectionConfig) -> anyhow::Result<Option<String>> {
+    resolve(config, |name| std::env::var_os(name)).await
+}
+
+async fn resolve(
+    config: &ConnectionConfig,
+    env: impl Fn(&str) -> Option<OsString>,
+) -> anyhow::Result<Option<String>> {
+    if let Some(password) = config.password.as_ref() {
+        return Ok(Some(password.to_owned()));
+    }
+
+    if let Some(command) = config.password_command.clone() {
+        let password = tokio::task::spawn_blocking(move || run_command(&command)).await??;
+        return Ok(Some(password));
+    }
+
+    let name = config.password_env.as_deref().or(match config.driver {
+        DriverKind::Postgres => Some("PGPASSWORD"),
+        DriverKind::Mysql => Some("MYSQL_PWD"),
+        DriverKind::Sqlite => None,
+    });
+
+    if let Some(password) = name.and_then(|name| env(name)?.into_string().ok()) {
+        return Ok(Some(password));
+    }
+
+    match config.driver {
+        DriverKind::Postgres => pgpass_password(config, env),
+        _ => Ok(None),
+    }
+}
+
+// @note: the server asked for a password, that is missing or wrong
+#[cfg_attr(
+    not(any(feature = "postgres", feature = "mysql")),
+    allow(unused_variables)
+)]
+pub fn is_auth_error(e: &anyhow::Error) -> bool {
+    e.chain().any(|e| {
+        #[cfg(feature = "postgres")]
+        if let Some(e) = e.downcast_ref::<tokio_postgres::Error>() {
+            return e.code() == Some(&tokio_postgres::error::SqlState::INVALID_PASSWORD);
+        }
+
+        #[cfg(feature = "mysql")]
+        if let Some(mysql_async::Error::Server(e)) = e.downcast_ref::<mysql_async::Error>() {
+            // @note: ER_ACCESS_DENIED_ERROR
+            return e.code == 1045;
+        }
+
+        false
+    })
+}
+
+fn run_command(command: &str) -> anyhow::Result<String> {
+    let output = std::process::Command::new("sh")
+        .args(["-c", command])
+        .stdin(std::process::Stdio::null())
+        .output()
+        .with_context(|| format!("failed to run `{command}`"))?;
+
+    anyhow::ensure!(
+        output.status.success(),
+        "`{command}` failed: {}",
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+
+    let stdout = String::from_utf8(output.stdout)?;
+    Ok(stdout.trim_end_matches(['\r', '\n']).to_owned())
+}
+
+fn pgpass_password(
+    config: &ConnectionConfig,
+    env: impl Fn(&str) -> Option<OsString>,
+) -> anyhow::Result<Option<String>> {
+    let path = match env("PGPASSFILE") {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let Some(home) = env("HOME") else {
+                return Ok(None);
+            };
+            PathBuf::from(home).join(".pgpass")
+        }
+    };
+
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return Ok(None);
+    };
+
+    // @note: like libpq, ignore a file that others can read
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        if std::fs::metadata(&path)?.permissions().mode() & 0o077 != 0 {
+            return Ok(None);
+        }
+    }
+
+    // @note: libpq matches sockets and an empty host as `localhost`
+    let host = match config.host.as_str() {
+        "" => "localhost",
+        host if host.starts_with('/') => "localhost",
+        host => host,
+    };
+
+    let port = config
+        .port
+        .or(config.driver.default_port())
+        .unwrap_or_default()
+        .to_string();
+    let dbname = config.dbname.as_deref().unwrap_or(&config.user);
+
+    Ok(pgpass_lookup(&content, [host, &port, dbname, &config.user]))
+}
+
+fn pgpass_lookup(content: &str, target: [&str; 4]) -> Option<String> {
+    content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .filter_map(pgpass_fields)
+        .find(|fields| {
+            fields
+                .iter()
+                .zip(target)
+                .all(|(field, value)| field == "*" || field == value)
+        })
+        .map(|[.., password]| password)
+}
+
+// @note: `host:port:dbname:user:password`, `\:` and `\\` are escapes
+fn pgpass_fields(line: &str) -> Option<[String; 5]> {
+    let mut fields = vec![String::new()];
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => fields.last_mut()?.extend(chars.next()),
+            ':' if fields.len() < 5 => fields.push(String::new()),
+            c => fields.last_mut()?.push(c),
+        }
+    }
+
+    fields.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pgpass_lookup() {
+        let content = r#"
+# comment
+db.local:5432:foo:bar:first
+*:*:*:bar:second
+localhost:*:baz:*:p\:a\\ss
+broken:line
+"#;
+
+        assert_eq!(
+            pgpass_lookup(content, ["db.local", "5432", "foo", "bar"]).as_deref(),
+            Some("first")
+        );
+        assert_eq!(
+            pgpass_lookup(content, ["db.local", "5433", "foo", "bar"]).as_deref(),
+            Some("second")
+        );
+        assert_eq!(
+            pgpass_lookup(content, ["localhost", "5432", "baz", "qux"]).as_deref(),
+            Some(r"p:a\ss")
+        );
+        assert_eq!(pgpass_lookup(content, ["broken", "line", "", ""]), None);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_password() -> anyhow::Result<()> {
+        let mut config = ConnectionConfig {
+            password_command: Some("echo secret".to_owned()),
+            password_env: Some("DBUZZY_TEST_PASSWORD".to_owned()),
+            ..Default::default()
+        };
+
+        let env = |name: &str| match name {
+            "DBUZZY_TEST_PASSWORD" => Some("from env".into()),
+            "PGPASSWORD" => Some("from pgpassword".into()),
+            _ => None,
+        };
+
+        assert_eq!(resolve(&config, env).await?.as_deref(), Some("secret"));
+
+        config.password_command = Some("exit 1".to_owned());
+        assert!(resolve(&config, env).await.is_err());
+
+        config.password_command = None;
+        assert_eq!(resolve(&config, env).await?.as_deref(), Some("from env"));
+
+        config.password_env = None;
+        assert_eq!(
+            resolve(&config, env).await?.as_deref(),
+            Some("from pgpassword")
+        );
+
+        // @note: no pgpass without HOME and PGPASSFILE
+        assert_eq!(resolve(&config, |_| None).await?, None);
+
+        config.password = Some("plain".to_owned());
+        assert_eq!(resolve(&config, env).await?.as_deref(), Some("plain"));
+
+        Ok(())
+    }
+}
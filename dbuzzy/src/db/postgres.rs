@@ -0,0 +1,241 @@
+use std::{io::Write, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use deadpool_postgres::{Client, Runtime};
+use futures_util::SinkExt;
+use postgres_native_tls::MakeTlsConnector;
+use tokio_postgres::CancelToken;
+
+use super::{
+    driver::{CancelHandle, Connection, Driver, DriverKind, POOL_WAIT_TIMEOUT},
+    tree::{self, DatabaseTree, TreeItem},
+    Column, ConnectionConfig, CsvImport, ExportFormat, Plan, QueryResult, SslMode, TableInfo,
+    TableRef,
+};
+
+const DEFAULT_CONNECT_TIMEOUT: u64 = 5;
+
+impl From<SslMode> for deadpool_postgres::SslMode {
+    fn from(mode: SslMode) -> Self {
+        match mode {
+            SslMode::Disable => Self::Disable,
+            SslMode::Prefer => Self::Prefer,
+            SslMode::Require | SslMode::VerifyCa | SslMode::VerifyFull => Self::Require,
+        }
+    }
+}
+
+impl From<&ConnectionConfig> for deadpool_postgres::Config {
+    fn from(conf: &ConnectionConfig) -> Self {
+        let connect_timeout =
+            Duration::from_secs(conf.connect_timeout.unwrap_or(DEFAULT_CONNECT_TIMEOUT));
+
+        Self {
+            user: Some(conf.user.to_owned()),
+            // @note: an empty password is sent when there is none, so a server asking for one
+            // fails with INVALID_PASSWORD instead of the untyped `password missing`
+            password: Some(conf.password.clone().unwrap_or_default()),
+            dbname: conf.dbname.to_owned(),
+            application_name: conf.application_name.clone().or_else(|| conf.name.clone()),
+            host: Some(conf.host.to_owned()),
+            hostaddr: conf.hostaddr,
+            port: conf.port,
+            connect_timeout: Some(connect_timeout),
+            keepalives: Some(true),
+            pool: Some(deadpool_postgres::PoolConfig {
+                max_size: conf
+                    .pool_size
+                    .unwrap_or_else(|| deadpool_postgres::PoolConfig::default().max_size),
+                timeouts: deadpool_postgres::Timeouts {
+                    wait: Some(POOL_WAIT_TIMEOUT),
+                    create: Some(connect_timeout),
+                    recycle: Some(connect_timeout),
+                },
+                ..Default::default()
+            }),
+            ssl_mode: Some(conf.sslmode.into()),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct PgPool {
+    inner: deadpool_postgres::Pool,
+    // @note: `None` without TLS, cancel requests connect with it
+    tls: Option<MakeTlsConnector>,
+    statement_timeout: Option<u64>,
+}
+
+impl PgPool {
+    pub fn create(config: &ConnectionConfig) -> super::DbResult<Self> {
+        let pg_conf: deadpool_postgres::Config = config.into();
+
+        let tls = match config.sslmode {
+            SslMode::Disable => None,
+            _ => Some(super::tls::connector(config)?),
+        };
+
+        let pool = match tls.clone() {
+            None => pg_conf.create_pool(Some(Runtime::Tokio1), tokio_postgres::NoTls)?,
+            Some(tls) => pg_conf.create_pool(Some(Runtime::Tokio1), tls)?,
+        };
+
+        Ok(Self {
+            inner: pool,
+            tls,
+            statement_timeout: config.statement_timeout,
+        })
+    }
+
+    pub async fn acquire(&self) -> super::DbResult<Client> {
+        let client = self.inner.get().await?;
+
+        if let Some(timeout) = self.statement_timeout {
+            client
+                .batch_execute(&format!("SET statement_timeout = {timeout}"))
+                .await?;
+        }
+
+        Ok(client)
+    }
+}
+
+#[async_trait]
+impl Driver for PgPool {
+    fn kind(&self) -> DriverKind {
+        DriverKind::Postgres
+    }
+
+    async fn acquire(&self) -> anyhow::Result<Box<dyn Connection>> {
+        Ok(Box::new(PgConnection {
+            client: Self::acquire(self).await?,
+            tls: self.tls.clone(),
+        }))
+    }
+}
+
+pub struct PgConnection {
+    client: Client,
+    tls: Option<MakeTlsConnector>,
+}
+
+#[async_trait]
+impl Connection for PgConnection {
+    fn kind(&self) -> DriverKind {
+        DriverKind::Postgres
+    }
+
+    async fn fetch(&self, sql: &str) -> anyhow::Result<QueryResult> {
+        Ok(QueryResult::fetch(&self.client, sql).await?)
+    }
+
+    async fn fetch_params(
+        &self,
+        sql: &str,
+        values: &[Option<String>],
+    ) -> anyhow::Result<QueryResult> {
+        Ok(QueryResult::fetch_params(&self.client, sql, values).await?)
+    }
+
+    async fn load_tree(&self) -> anyhow::Result<DatabaseTree> {
+        DatabaseTree::load(&self.client).await
+    }
+
+    async fn children(&self, item: &Arc<TreeItem>) -> anyhow::Result<Vec<Arc<TreeItem>>> {
+        tree::load_children(&self.client, item).await
+    }
+
+    async fn ddl(&self, item: &Arc<TreeItem>) -> anyhow::Result<String> {
+        super::ddl::object_ddl(&self.client, item).await
+    }
+
+    async fn table_info(&self, table: &TableRef) -> anyhow::Result<TableInfo> {
+        Ok(table.info(&self.client).await?)
+    }
+
+    async fn table_columns(&self, table: &TableRef) -> anyhow::Result<Vec<Column>> {
+        Ok(table.columns(&self.client).await?)
+    }
+
+    async fn explain(&self, sql: &str, analyze: bool) -> anyhow::Result<Plan> {
+        let row = self.client.query_one(&Plan::sql(sql, analyze), &[]).await?;
+        Plan::from_json(&row.try_get(0)?)
+    }
+
+    // @note: COPY instead of INSERT, the fields are re-encoded as csv
+    async fn load_rows(
+        &self,
+        import: &CsvImport,
+        records: &[csv::StringRecord],
+    ) -> anyhow::Result<Result<u64, String>> {
+        let data = records
+            .iter()
+            .flat_map(|record| import.encode(record))
+            .collect::<Vec<_>>();
+
+        let result = async {
+            let sink = self.client.copy_in(&import.copy_sql()).await?;
+            futures_util::pin_mut!(sink);
+
+            sink.send(Bytes::from(data)).await?;
+            sink.finish().await
+        }
+        .await;
+
+        match result {
+            Ok(rows) => Ok(Ok(rows)),
+            Err(e) => match e.as_db_error() {
+                Some(db_error) => Ok(Err(db_error.message().to_owned())),
+                None => Err(e.into()),
+            },
+        }
+    }
+
+    async fn export(
+        &self,
+        sql: &str,
+        values: Option<&[Option<String>]>,
+        format: ExportFormat,
+        out: &mut (dyn Write + Send),
+    ) -> Option<anyhow::Result<u64>> {
+        let rows = async {
+            self.client.batch_execute("BEGIN READ ONLY").await?;
+            let rows = super::export::stream(&self.client, sql, values, format, out).await;
+            super::driver::close(self, &["ROLLBACK"], rows).await
+        };
+
+        Some(rows.await)
+    }
+
+    fn cancel_handle(&self) -> Option<Box<dyn CancelHandle>> {
+        Some(Box::new(PgCancel {
+            token: self.client.cancel_token(),
+            tls: self.tls.clone(),
+        }))
+    }
+
+    // @note: the pool does not reset its clients
+    fn discard(self: Box<Self>) {
+        drop(Client::take(self.client));
+    }
+}
+
+struct PgCancel {
+    token: CancelToken,
+    tls: Option<MakeTlsConnector>,
+}
+
+#[async_trait]
+impl CancelHandle for PgCancel {
+    // @note: the query keeps running until the server gets the request
+    async fn cancel(&self) -> anyhow::Result<()> {
+        match self.tls.clone() {
+            None => self.token.cancel_query(tokio_postgres::NoTls).await?,
+            Some(tls) => self.token.cancel_query(tls).await?,
+        }
+
+        Ok(())
+    }
+}
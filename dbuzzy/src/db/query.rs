@@ -0,0 +1,158 @@
+use std::time::Duration;
+#[cfg(feature = "postgres")]
+use std::time::Instant;
+
+#[cfg(feature = "postgres")]
+use deadpool_postgres::{Client, GenericClient};
+#[cfg(feature = "postgres")]
+use tokio_postgres::{
+    types::{to_sql_checked, Format, IsNull, ToSql, Type},
+    Row, Statement,
+};
+
+use super::Value;
+#[cfg(feature = "postgres")]
+use super::{DbResult, Placeholders};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Column {
+    pub name: String,
+    pub type_name: String,
+}
+
+#[derive(Debug, Default)]
+pub struct QueryResult {
+    pub columns: Vec<Column>,
+    pub rows: Vec<Vec<Value>>,
+    pub affected: Option<u64>,
+    pub elapsed: Duration,
+}
+
+impl QueryResult {
+    pub fn summary(&self) -> String {
+        let elapsed = self.elapsed.as_millis();
+
+        match self.affected {
+            Some(affected) => format!("{affected} rows affected ({elapsed} ms)"),
+            None => format!("{} rows ({elapsed} ms)", self.rows.len()),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl QueryResult {
+    pub async fn fetch(client: &Client, sql: &str) -> DbResult<Self> {
+        let started = Instant::now();
+        let stmt = client.prepare(sql).await?;
+
+        Self::execute(client, &stmt, &[], started).await
+    }
+
+    // @note: `values` of the placeholders in order, `None` is null
+    pub async fn fetch_params(
+        client: &Client,
+        sql: &str,
+        values: &[Option<String>],
+    ) -> DbResult<Self> {
+        let started = Instant::now();
+        let stmt = client.prepare(&Placeholders::parse(sql).sql()).await?;
+
+        let values = text_params(values);
+        let params = values
+            .iter()
+            .map(|value| value as &(dyn ToSql + Sync))
+            .collect::<Vec<_>>();
+
+        Self::execute(client, &stmt, &params, started).await
+    }
+
+    async fn execute(
+        client: &Client,
+        stmt: &Statement,
+        params: &[&(dyn ToSql + Sync)],
+        started: Instant,
+    ) -> DbResult<Self> {
+        let columns = statement_columns(stmt);
+
+        if columns.is_empty() {
+            let affected = client.execute(stmt, params).await?;
+
+            return Ok(Self {
+                affected: Some(affected),
+                elapsed: started.elapsed(),
+                ..Default::default()
+            });
+        }
+
+        let rows = client
+            .query(stmt, params)
+            .await?
+            .iter()
+            .map(row_values)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            columns,
+            rows,
+            affected: None,
+            elapsed: started.elapsed(),
+        })
+    }
+}
+
+// @note: a typed value is sent in the text format, so the server parses it as the type
+// of its parameter, like a literal
+#[cfg(feature = "postgres")]
+#[derive(Debug)]
+pub(super) struct TextParam<'a>(Option<&'a str>);
+
+#[cfg(feature = "postgres")]
+pub(super) fn text_params(values: &[Option<String>]) -> Vec<TextParam<'_>> {
+    values
+        .iter()
+        .map(|value| TextParam(value.as_deref()))
+        .collect()
+}
+
+#[cfg(feature = "postgres")]
+impl ToSql for TextParam<'_> {
+    fn to_sql(
+        &self,
+        _: &Type,
+        out: &mut bytes::BytesMut,
+    ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        match self.0 {
+            Some(text) => {
+                out.extend_from_slice(text.as_bytes());
+                Ok(IsNull::No)
+            }
+            None => Ok(IsNull::Yes),
+        }
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+
+    fn encode_format(&self, _: &Type) -> Format {
+        Format::Text
+    }
+
+    to_sql_checked!();
+}
+
+#[cfg(feature = "postgres")]
+pub(super) fn statement_columns(stmt: &Statement) -> Vec<Column> {
+    stmt.columns()
+        .iter()
+        .map(|c| Column {
+            name: c.name().to_owned(),
+            type_name: c.type_().name().to_owned(),
+        })
+        .collect()
+}
+
+#[cfg(feature = "postgres")]
+pub(super) fn row_values(row: &Row) -> Result<Vec<Value>, tokio_postgres::Error> {
+    (0..row.len()).map(|i| row.try_get::<_, Value>(i)).collect()
+}
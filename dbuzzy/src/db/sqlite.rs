@@ -0,0 +1,782 @@
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use rusqlite::{types::ValueRef, InterruptHandle, OpenFlags};
+
+use super::{
+    driver::{CancelHandle, Connection, Driver, DriverKind},
+    quote_ident,
+    tree::{ColumnInfo, DatabaseTree, TreeGroup, TreeItem, TreeItemKind},
+    Column, ConnectionConfig, Placeholders, Plan, PlanNode, QueryResult, RowCount, TableInfo,
+    TableRef, Value,
+};
+
+const SCHEMA_GROUPS: [TreeGroup; 2] = [TreeGroup::Tables, TreeGroup::Views];
+
+const TABLE_GROUPS: [TreeGroup; 4] = [
+    TreeGroup::Columns,
+    TreeGroup::Indexes,
+    TreeGroup::ForeignKeys,
+    TreeGroup::Triggers,
+];
+
+#[derive(Clone)]
+pub struct SqlitePool {
+    path: PathBuf,
+    statement_timeout: Option<Duration>,
+}
+
+impl SqlitePool {
+    pub fn create(config: &ConnectionConfig) -> anyhow::Result<Self> {
+        let path = config
+            .path
+            .clone()
+            .context("sqlite connection needs a `path`")?;
+
+        Ok(Self {
+            path,
+            statement_timeout: config.statement_timeout.map(Duration::from_millis),
+        })
+    }
+}
+
+#[async_trait]
+impl Driver for SqlitePool {
+    fn kind(&self) -> DriverKind {
+        DriverKind::Sqlite
+    }
+
+    // @note: opening a file is cheap, so every acquire gets a fresh connection
+    async fn acquire(&self) -> anyhow::Result<Box<dyn Connection>> {
+        let path = self.path.clone();
+
+        let conn = tokio::task::spawn_blocking(move || {
+            // @note: don't create a new database on a typo in the path
+            let flags = OpenFlags::SQLITE_OPEN_READ_WRITE
+                | OpenFlags::SQLITE_OPEN_URI
+                | OpenFlags::SQLITE_OPEN_NO_MUTEX;
+
+            rusqlite::Connection::open_with_flags(&path, flags)
+                .with_context(|| format!("failed to open {}", path.display()))
+        })
+        .await??;
+
+        Ok(Box::new(SqliteConnection {
+            interrupt: Arc::new(conn.get_interrupt_handle()),
+            conn: Arc::new(Mutex::new(conn)),
+            statement_timeout: self.statement_timeout,
+        }))
+    }
+}
+
+pub struct SqliteConnection {
+    conn: Arc<Mutex<rusqlite::Connection>>,
+    interrupt: Arc<InterruptHandle>,
+    statement_timeout: Option<Duration>,
+}
+
+impl SqliteConnection {
+    // @note: rusqlite is blocking, run it off the async runtime,
+    // sqlite has no timeout of its own, the statement is interrupted instead
+    async fn with<T, F>(&self, f: F) -> anyhow::Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&rusqlite::Connection) -> anyhow::Result<T> + Send + 'static,
+    {
+        let conn = Arc::clone(&self.conn);
+
+        let timer = self.statement_timeout.map(|timeout| {
+            let interrupt = Arc::clone(&self.interrupt);
+
+            tokio::spawn(async move {
+                tokio::time::sleep(timeout).await;
+                interrupt.interrupt();
+            })
+        });
+
+        let result = tokio::task::spawn_blocking(move || {
+            let conn = conn
+                .lock()
+                .map_err(|_| anyhow::anyhow!("connection is poisoned"))?;
+            f(&conn)
+        })
+        .await?;
+
+        if let Some(timer) = timer {
+            timer.abort();
+        }
+
+        result
+    }
+}
+
+#[async_trait]
+impl Connection for SqliteConnection {
+    fn kind(&self) -> DriverKind {
+        DriverKind::Sqlite
+    }
+
+    async fn fetch(&self, sql: &str) -> anyhow::Result<QueryResult> {
+        let sql = sql.to_owned();
+        self.with(move |conn| fetch(conn, &sql, [])).await
+    }
+
+    async fn fetch_params(
+        &self,
+        sql: &str,
+        values: &[Option<String>],
+    ) -> anyhow::Result<QueryResult> {
+        let sql = Placeholders::parse(sql).render(|n| format!("?{n}"));
+        let values = values.to_vec();
+
+        self.with(move |conn| fetch(conn, &sql, rusqlite::params_from_iter(values)))
+            .await
+    }
+
+    async fn load_tree(&self) -> anyhow::Result<DatabaseTree> {
+        self.with(load_tree).await
+    }
+
+    async fn children(&self, item: &Arc<TreeItem>) -> anyhow::Result<Vec<Arc<TreeItem>>> {
+        let item = Arc::clone(item);
+        self.with(move |conn| children(conn, &item)).await
+    }
+
+    async fn ddl(&self, item: &Arc<TreeItem>) -> anyhow::Result<String> {
+        let item = Arc::clone(item);
+        self.with(move |conn| ddl(conn, &item)).await
+    }
+
+    async fn table_info(&self, table: &TableRef) -> anyhow::Result<TableInfo> {
+        let table = table.clone();
+        self.with(move |conn| table_info(conn, &table)).await
+    }
+
+    async fn table_columns(&self, table: &TableRef) -> anyhow::Result<Vec<Column>> {
+        let table = table.clone();
+
+        self.with(move |conn| {
+            let mut stmt =
+                conn.prepare("SELECT name, type FROM pragma_table_info(?1, ?2) ORDER BY cid")?;
+
+            let columns = stmt
+                .query_map((&table.name, &table.schema), |row| {
+                    Ok(Column {
+                        name: row.get(0)?,
+                        type_name: row.get::<_, String>(1)?.to_lowercase(),
+                    })
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+
+            Ok(columns)
+        })
+        .await
+    }
+
+    async fn explain(&self, sql: &str, analyze: bool) -> anyhow::Result<Plan> {
+        if analyze {
+            anyhow::bail!("sqlite has no EXPLAIN ANALYZE");
+        }
+
+        let sql = format!("EXPLAIN QUERY PLAN {}", sql.trim().trim_end_matches(';'));
+        self.with(move |conn| explain(conn, &sql)).await
+    }
+
+    fn cancel_handle(&self) -> Option<Box<dyn CancelHandle>> {
+        Some(Box::new(SqliteCancel(Arc::clone(&self.interrupt))))
+    }
+}
+
+struct SqliteCancel(Arc<InterruptHandle>);
+
+#[async_trait]
+impl CancelHandle for SqliteCancel {
+    async fn cancel(&self) -> anyhow::Result<()> {
+        self.0.interrupt();
+        Ok(())
+    }
+}
+
+fn fetch(
+    conn: &rusqlite::Connection,
+    sql: &str,
+    params: impl rusqlite::Params,
+) -> anyhow::Result<QueryResult> {
+    let started = Instant::now();
+    let mut stmt = conn.prepare(sql)?;
+
+    if stmt.column_count() == 0 {
+        let affected = stmt.execute(params)?;
+
+        return Ok(QueryResult {
+            affected: Some(affected as u64),
+            elapsed: started.elapsed(),
+            ..Default::default()
+        });
+    }
+
+    let columns = stmt
+        .columns()
+        .iter()
+        .map(|c| Column {
+            name: c.name().to_owned(),
+            type_name: c.decl_type().unwrap_or_default().to_lowercase(),
+        })
+        .collect::<Vec<_>>();
+
+    let mut rows = vec![];
+    let mut query = stmt.query(params)?;
+
+    while let Some(row) = query.next()? {
+        let values = (0..columns.len())
+            .map(|i| Ok(value(row.get_ref(i)?)))
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        rows.push(values);
+    }
+
+    Ok(QueryResult {
+        columns,
+        rows,
+        affected: None,
+        elapsed: started.elapsed(),
+    })
+}
+
+fn value(value: ValueRef<'_>) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) => Value::Int(i),
+        ValueRef::Real(f) => Value::Float(f),
+        ValueRef::Text(text) => Value::Text(String::from_utf8_lossy(text).into_owned()),
+        ValueRef::Blob(bytes) => Value::Bytes(bytes.to_vec()),
+    }
+}
+
+// @note: sqlite keeps the CREATE statements, a table comes with its indexes and triggers
+fn ddl(conn: &rusqlite::Connection, item: &TreeItem) -> anyhow::Result<String> {
+    let schema = item
+        .schema_name()
+        .with_context(|| format!("no DDL for {}", item.name))?;
+
+    let statements = |kind: Option<&str>, table: Option<&str>| -> anyhow::Result<String> {
+        let sql = format!(
+            r#"
+                SELECT sql FROM {}.sqlite_master
+                WHERE (?1 IS NULL OR type = ?1) AND (?2 IS NULL OR tbl_name = ?2)
+                    AND (?2 IS NOT NULL OR name = ?3) AND sql IS NOT NULL
+                ORDER BY type = 'table' DESC, type, name
+            "#,
+            quote_ident(schema)
+        );
+
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt
+            .query_map((kind, table, &item.name), |row| row.get::<_, String>(0))?
+            .map(|sql| Ok(format!("{};", sql?)))
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        match rows.is_empty() {
+            true => anyhow::bail!("{schema}.{} not found", item.name),
+            false => Ok(rows.join("\n\n")),
+        }
+    };
+
+    match &item.kind {
+        TreeItemKind::Table { .. } => statements(None, Some(&item.name)),
+        TreeItemKind::Column {
+            data_type,
+            nullable,
+            default,
+            group,
+        } => {
+            let table = group.parent().context("column should have a table")?;
+            let table = TableRef::new(schema.to_owned(), table.name.to_owned());
+
+            let mut sql = format!(
+                "ALTER TABLE {} ADD COLUMN {} {data_type}",
+                table.qualified_name(),
+                quote_ident(&item.name)
+            );
+            if !nullable {
+                sql.push_str(" NOT NULL");
+            }
+            if let Some(default) = default {
+                sql = format!("{sql} DEFAULT {default}");
+            }
+
+            Ok(format!("{sql};"))
+        }
+        TreeItemKind::Object { group, .. } => match group.group_kind() {
+            Some(TreeGroup::Views) => statements(Some("view"), None),
+            Some(TreeGroup::Indexes) => statements(Some("index"), None),
+            Some(TreeGroup::Triggers) => statements(Some("trigger"), None),
+            // @note: sqlite can't add a constraint to an existing table
+            _ => anyhow::bail!("no DDL for {}, see its table", item.name),
+        },
+        TreeItemKind::Database | TreeItemKind::Schema { .. } | TreeItemKind::Group { .. } => {
+            anyhow::bail!("no DDL for {}", item.name)
+        }
+    }
+}
+
+// @note: the primary key, or the first unique index over not null columns
+fn table_info(conn: &rusqlite::Connection, table: &TableRef) -> anyhow::Result<TableInfo> {
+    let columns = |sql: &str| -> rusqlite::Result<Vec<String>> {
+        conn.prepare(sql)?
+            .query_map((&table.name, &table.schema), |row| row.get(0))?
+            .collect()
+    };
+
+    let mut key = columns(
+        r#"
+            SELECT name FROM pragma_table_info(?1, ?2)
+            WHERE pk > 0
+            ORDER BY pk
+        "#,
+    )?;
+
+    if key.is_empty() {
+        key = columns(
+            r#"
+                SELECT ii.name
+                FROM (
+                    SELECT il.name FROM pragma_index_list(?1, ?2) il
+                    WHERE il."unique" AND NOT il.partial AND NOT EXISTS (
+                        SELECT 1 FROM pragma_index_info(il.name, ?2) ii
+                        LEFT JOIN pragma_table_info(?1, ?2) c ON c.name = ii.name
+                        WHERE c.name IS NULL OR NOT c."notnull"
+                    )
+                    ORDER BY il.seq
+                    LIMIT 1
+                ) il
+                CROSS JOIN pragma_index_info(il.name, ?2) ii
+                ORDER BY ii.seqno
+            "#,
+        )?;
+    }
+
+    // @note: sqlite keeps no estimate, the count is always exact
+    let sql = format!("SELECT count(*) FROM {}", table.qualified_name());
+    let count = conn.query_row(&sql, [], |row| row.get(0))?;
+
+    Ok(TableInfo {
+        key,
+        rows: RowCount::Exact(count),
+    })
+}
+
+// @note: the query plan has no costs, only the steps and their nesting
+fn explain(conn: &rusqlite::Connection, sql: &str) -> anyhow::Result<Plan> {
+    let mut stmt = conn.prepare(sql)?;
+    let steps = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get(3)?))
+        })?
+        .collect::<rusqlite::Result<Vec<(i64, i64, String)>>>()?;
+
+    let mut depths: Vec<(i64, u8)> = vec![];
+    let mut nodes = vec![];
+
+    for (id, parent, detail) in steps {
+        let depth = depths
+            .iter()
+            .find(|(step, _)| *step == parent)
+            .map_or(0, |(_, depth)| depth + 1);
+        depths.push((id, depth));
+
+        let (node_type, detail) = match detail.split_once(' ') {
+            Some((node_type, detail)) => (node_type.to_owned(), Some(detail.to_owned())),
+            None => (detail, None),
+        };
+
+        nodes.push(PlanNode {
+            depth,
+            node_type,
+            detail,
+            startup_cost: 0.0,
+            total_cost: 0.0,
+            plan_rows: 0.0,
+            actual_rows: None,
+            actual_time: None,
+            loops: 1,
+            shared_hit: None,
+            shared_read: None,
+        });
+    }
+
+    Ok(Plan::new(nodes))
+}
+
+// @note: attached databases play the role of schemas
+fn load_tree(conn: &rusqlite::Connection) -> anyhow::Result<DatabaseTree> {
+    let name = conn
+        .path()
+        .and_then(|path| std::path::Path::new(path).file_name())
+        .map_or_else(
+            || "sqlite".to_owned(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+
+    let db_item = Arc::new(TreeItem::database(name));
+    let mut tree = vec![Arc::clone(&db_item)];
+
+    let mut stmt = conn.prepare("SELECT name FROM pragma_database_list WHERE name <> 'temp'")?;
+    let schemas = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    for schema in schemas {
+        tree.push(Arc::new(TreeItem::schema(schema, Arc::clone(&db_item))));
+    }
+
+    Ok(DatabaseTree::new(tree))
+}
+
+fn children(
+    conn: &rusqlite::Connection,
+    item: &Arc<TreeItem>,
+) -> anyhow::Result<Vec<Arc<TreeItem>>> {
+    let children = match item.kind {
+        TreeItemKind::Schema { .. } => TreeItem::groups(&SCHEMA_GROUPS, item),
+        TreeItemKind::Table { .. } => TreeItem::groups(&TABLE_GROUPS, item),
+        TreeItemKind::Group { group, .. } => load_group(conn, group, item)?,
+        _ => vec![],
+    };
+
+    Ok(children)
+}
+
+fn load_group(
+    conn: &rusqlite::Connection,
+    group: TreeGroup,
+    item: &Arc<TreeItem>,
+) -> anyhow::Result<Vec<Arc<TreeItem>>> {
+    let schema = item.schema_name().context("group should have a schema")?;
+    let table = item.parent().context("group should have a parent")?;
+
+    // @note: (name, sql) of schema objects, optionally of a single table
+    let objects = |kind: &str, table: Option<&str>| -> anyhow::Result<Vec<(String, String)>> {
+        let sql = format!(
+            r#"
+                SELECT name, coalesce(sql, '') FROM {}.sqlite_master
+                WHERE type = ?1 AND (?2 IS NULL OR tbl_name = ?2) AND name NOT LIKE 'sqlite\_%' ESCAPE '\'
+                ORDER BY name
+            "#,
+            quote_ident(schema)
+        );
+
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt
+            .query_map((kind, table), |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(rows)
+    };
+
+    let children = match group {
+        TreeGroup::Tables => objects("table", None)?
+            .into_iter()
+            .map(|(name, _)| TreeItem::table(name, Arc::clone(item)))
+            .collect(),
+        TreeGroup::Views => objects("view", None)?
+            .into_iter()
+            .map(|(name, _)| TreeItem::object(name, String::new(), Arc::clone(item)))
+            .collect(),
+        TreeGroup::Indexes => objects("index", Some(&table.name))?
+            .into_iter()
+            .map(|(name, sql)| TreeItem::object(name, sql, Arc::clone(item)))
+            .collect(),
+        TreeGroup::Triggers => objects("trigger", Some(&table.name))?
+            .into_iter()
+            .map(|(name, sql)| TreeItem::object(name, sql, Arc::clone(item)))
+            .collect(),
+        TreeGroup::Columns => {
+            let mut stmt = conn.prepare(
+                r#"
+                    SELECT name, type, "notnull", dflt_value
+                    FROM pragma_table_info(?1, ?2)
+                    ORDER BY cid
+                "#,
+            )?;
+
+            let columns = stmt
+                .query_map((&table.name, schema), |row| {
+                    Ok(ColumnInfo {
+                        name: row.get(0)?,
+                        data_type: row.get::<_, String>(1)?.to_lowercase(),
+                        nullable: !row.get::<_, bool>(2)?,
+                        default: row.get(3)?,
+                    })
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+
+            columns
+                .into_iter()
+                .map(|column| TreeItem::column(column, Arc::clone(item)))
+                .collect()
+        }
+        TreeGroup::ForeignKeys => {
+            let mut stmt = conn.prepare(
+                r#"
+                    SELECT
+                        'fk_' || id,
+                        'FOREIGN KEY (' || group_concat("from", ', ') || ') REFERENCES '
+                            || "table" || '(' || coalesce(group_concat("to", ', '), '') || ')'
+                    FROM pragma_foreign_key_list(?1, ?2)
+                    GROUP BY id
+                    ORDER BY id
+                "#,
+            )?;
+
+            let keys = stmt
+                .query_map((&table.name, schema), |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+
+            keys.into_iter()
+                .map(|(name, definition)| TreeItem::object(name, definition, Arc::clone(item)))
+                .collect()
+        }
+        _ => vec![],
+    };
+
+    Ok(children.into_iter().map(Arc::new).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::CsvImport;
+
+    fn names(items: &[Arc<TreeItem>]) -> Vec<String> {
+        items.iter().map(|item| item.label()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_sqlite() -> anyhow::Result<()> {
+        let mut path = std::env::temp_dir();
+        path.push("dbuzzy_test_sqlite.db");
+        std::fs::remove_file(&path).ok();
+
+        rusqlite::Connection::open(&path)?.execute_batch(
+            r#"
+                CREATE TABLE foo (id INTEGER PRIMARY KEY, name TEXT NOT NULL DEFAULT 'x');
+                CREATE TABLE bar (id INTEGER, foo_id INTEGER REFERENCES foo(id));
+                CREATE INDEX bar_foo ON bar(foo_id);
+                CREATE VIEW baz AS SELECT 1;
+            "#,
+        )?;
+
+        let config = ConnectionConfig {
+            name: None,
+            driver: DriverKind::Sqlite,
+            host: String::new(),
+            port: None,
+            dbname: None,
+            user: String::new(),
+            password: None,
+            path: Some(path.clone()),
+            ..Default::default()
+        };
+
+        let pool = SqlitePool::create(&config)?;
+        let conn = pool.acquire().await?;
+
+        let mut tree = conn.load_tree().await?;
+        assert_eq!(names(tree.as_ref()), vec!["dbuzzy_test_sqlite.db", "main"]);
+
+        tree.expand(conn.as_ref(), 1).await?;
+        tree.expand(conn.as_ref(), 2).await?;
+        assert_eq!(
+            names(&tree.as_ref()[2..]),
+            vec!["Tables", "bar", "foo", "Views"]
+        );
+
+        tree.expand(conn.as_ref(), 3).await?;
+        tree.expand(conn.as_ref(), 4).await?;
+        tree.expand(conn.as_ref(), 7).await?;
+        tree.expand(conn.as_ref(), 9).await?;
+        assert_eq!(
+            names(&tree.as_ref()[4..12]),
+            vec![
+                "Columns",
+                "id integer",
+                "foo_id integer",
+                "Indexes",
+                "bar_foo: CREATE INDEX bar_foo ON bar(foo_id)",
+                "Foreign Keys",
+                "fk_0: FOREIGN KEY (foo_id) REFERENCES foo(id)",
+                "Triggers",
+            ]
+        );
+
+        let result = conn.fetch("INSERT INTO foo (id) VALUES (1), (2)").await?;
+        assert_eq!(result.affected, Some(2));
+
+        let result = conn.fetch("SELECT id, name, NULL AS n FROM foo").await?;
+        assert_eq!(result.columns[1].type_name, "text");
+        assert_eq!(
+            result.rows[1],
+            vec![Value::Int(2), Value::Text("x".to_owned()), Value::Null]
+        );
+
+        let values = [None, Some("1".to_owned())];
+        let result = conn
+            .fetch_params("SELECT name, :n IS NULL FROM foo WHERE id > :id", &values)
+            .await?;
+        assert_eq!(
+            result.rows,
+            vec![vec![Value::Text("x".to_owned()), Value::Int(1)]]
+        );
+
+        let table = TableRef::new("main".to_owned(), "foo".to_owned());
+        let info = conn.table_info(&table).await?;
+        assert_eq!(info.key, vec!["id"]);
+        assert_eq!(info.rows, RowCount::Exact(2));
+
+        let columns = conn.table_columns(&table).await?;
+        assert_eq!(columns[1].name, "name");
+        assert_eq!(columns[1].type_name, "text");
+
+        assert_eq!(
+            conn.ddl(&tree.as_ref()[3]).await?,
+            "CREATE TABLE bar (id INTEGER, foo_id INTEGER REFERENCES foo(id));\n\n\
+             CREATE INDEX bar_foo ON bar(foo_id);"
+        );
+
+        let plan = conn
+            .explain("SELECT * FROM bar WHERE foo_id = 1", false)
+            .await?;
+        assert_eq!(plan.nodes()[0].node_type, "SEARCH");
+
+        std::fs::remove_file(&path)?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_close() -> anyhow::Result<()> {
+        let mut path = std::env::temp_dir();
+        path.push("dbuzzy_test_close.db");
+        rusqlite::Connection::open(&path)?;
+
+        let config = ConnectionConfig {
+            driver: DriverKind::Sqlite,
+            path: Some(path.clone()),
+            ..Default::default()
+        };
+        let conn = SqlitePool::create(&config)?.acquire().await?;
+
+        // @note: no transaction is open, so the rollback fails
+        let failed = Err::<(), _>(anyhow::anyhow!("UNIQUE constraint failed"));
+        let e = super::super::driver::close(conn.as_ref(), &["ROLLBACK"], failed)
+            .await
+            .unwrap_err();
+
+        assert!(e.to_string().starts_with("ROLLBACK failed too"));
+        assert_eq!(e.root_cause().to_string(), "UNIQUE constraint failed");
+
+        std::fs::remove_file(&path)?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_interrupt() -> anyhow::Result<()> {
+        let mut path = std::env::temp_dir();
+        path.push("dbuzzy_test_interrupt.db");
+        rusqlite::Connection::open(&path)?;
+
+        let config = ConnectionConfig {
+            driver: DriverKind::Sqlite,
+            path: Some(path.clone()),
+            statement_timeout: Some(100),
+            ..Default::default()
+        };
+
+        let endless = "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n) \
+                       SELECT count(*) FROM n";
+
+        let pool = SqlitePool::create(&config)?;
+        let conn = pool.acquire().await?;
+        assert!(conn.fetch(endless).await.is_err());
+
+        // @note: without a timeout, the statement runs until it is cancelled
+        let pool = SqlitePool::create(&ConnectionConfig {
+            statement_timeout: None,
+            ..config
+        })?;
+        let conn = pool.acquire().await?;
+        let cancel = conn
+            .cancel_handle()
+            .context("sqlite interrupts statements")?;
+
+        let task = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            cancel.cancel().await
+        });
+
+        assert!(conn.fetch(endless).await.is_err());
+        task.await??;
+
+        let result = conn.fetch("SELECT 1").await?;
+        assert_eq!(result.rows, vec![vec![Value::Int(1)]]);
+
+        std::fs::remove_file(&path)?;
+
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_import() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir();
+        let path = dir.join("dbuzzy_test_import.db");
+        let csv = dir.join("dbuzzy_test_import.csv");
+        rusqlite::Connection::open(&path)?;
+
+        // @note: more rows than a batch, one unreadable and one rejected
+        let mut lines = vec!["id,name".to_owned()];
+        lines.extend((1..=2500).map(|id| format!("{id},row {id}")));
+        lines[1200] = "1200".to_owned();
+        lines[2100] = "1,duplicate".to_owned();
+        std::fs::write(&csv, lines.join("\n"))?;
+
+        let config = ConnectionConfig {
+            driver: DriverKind::Sqlite,
+            path: Some(path.clone()),
+            ..Default::default()
+        };
+        let conn = SqlitePool::create(&config)?.acquire().await?;
+        conn.fetch("CREATE TABLE foo (id INTEGER PRIMARY KEY, name TEXT)")
+            .await?;
+
+        let import = CsvImport {
+            table: TableRef::new("main".to_owned(), "foo".to_owned()),
+            path: csv.clone(),
+            delimiter: b',',
+            mapping: vec![("id".to_owned(), 0), ("name".to_owned(), 1)],
+        };
+
+        let mut progress = Vec::new();
+        let summary = import.run(conn.as_ref(), |p| progress.push(p)).await?;
+
+        assert_eq!(summary.rows, 2498);
+        assert_eq!(
+            summary.errors.iter().map(|e| e.line).collect::<Vec<_>>(),
+            vec![1201, 2101]
+        );
+        assert_eq!(progress.last().map(|p| p.rows), Some(2498));
+
+        let result = conn.fetch("SELECT count(*) FROM foo").await?;
+        assert_eq!(result.rows, vec![vec![Value::Int(2498)]]);
+
+        std::fs::remove_file(&path)?;
+        std::fs::remove_file(&csv)?;
+
+        Ok(())
+    }
+}
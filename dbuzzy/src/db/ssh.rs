@@ -0,0 +1,235 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use serde::Deserialize;
+use tokio::{
+    io::AsyncReadExt,
+    net::TcpListener,
+    process::{Child, Command},
+};
+
+const READY_TIMEOUT: Duration = Duration::from_secs(15);
+// @note: the free port can be taken before ssh binds it
+const ATTEMPTS: usize = 3;
+const FORWARD_FAILURE: &str = "Could not request local forwarding";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SshConfig {
+    pub host: String,
+    pub port: Option<u16>,
+    pub user: Option<String>,
+    // @note: private key, otherwise the agent and ~/.ssh/config decide
+    pub key: Option<PathBuf>,
+    // @note: bastions to hop through in order, `[user@]host[:port]`
+    #[serde(default)]
+    pub jump: Vec<String>,
+    // @note: extra `-o` options, e.g. `StrictHostKeyChecking=accept-new`
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+impl SshConfig {
+    fn args(
+        &self,
+        control: &Path,
+        local_port: u16,
+        remote_host: &str,
+        remote_port: u16,
+    ) -> Vec<String> {
+        let remote_host = if remote_host.contains(':') {
+            format!("[{remote_host}]")
+        } else {
+            remote_host.to_owned()
+        };
+
+        // @note: no prompts, they would mess up the terminal, the control socket tells when
+        // the forward is up
+        let mut args = vec![
+            "-N".to_owned(),
+            "-T".to_owned(),
+            "-M".to_owned(),
+            "-S".to_owned(),
+            control.to_string_lossy().into_owned(),
+            "-o".to_owned(),
+            "ControlPersist=no".to_owned(),
+            "-o".to_owned(),
+            "BatchMode=yes".to_owned(),
+            "-o".to_owned(),
+            "ExitOnForwardFailure=yes".to_owned(),
+            "-L".to_owned(),
+            format!("127.0.0.1:{local_port}:{remote_host}:{remote_port}"),
+        ];
+
+        if let Some(port) = self.port {
+            args.extend(["-p".to_owned(), port.to_string()]);
+        }
+        if let Some(user) = self.user.as_ref() {
+            args.extend(["-l".to_owned(), user.to_owned()]);
+        }
+        if let Some(key) = self.key.as_ref() {
+            args.extend(["-i".to_owned(), key.to_string_lossy().into_owned()]);
+        }
+        if !self.jump.is_empty() {
+            args.extend(["-J".to_owned(), self.jump.join(",")]);
+        }
+        for option in &self.options {
+            args.extend(["-o".to_owned(), option.to_owned()]);
+        }
+
+        args.push(self.host.to_owned());
+        args
+    }
+}
+
+// @note: a local port forward by the system `ssh`, that is killed on drop
+pub struct SshTunnel {
+    child: Child,
+    control: PathBuf,
+    local_port: u16,
+}
+
+impl SshTunnel {
+    pub async fn open(
+        config: &SshConfig,
+        remote_host: &str,
+        remote_port: u16,
+    ) -> anyhow::Result<Self> {
+        for _ in 0..ATTEMPTS {
+            let local_port = TcpListener::bind("127.0.0.1:0").await?.local_addr()?.port();
+
+            if let Some(tunnel) = Self::start(config, local_port, remote_host, remote_port).await? {
+                return Ok(tunnel);
+            }
+        }
+
+        anyhow::bail!("ssh to {} could not bind a local port", config.host)
+    }
+
+    // @note: `None` when the local port was taken meanwhile
+    async fn start(
+        config: &SshConfig,
+        local_port: u16,
+        remote_host: &str,
+        remote_port: u16,
+    ) -> anyhow::Result<Option<Self>> {
+        let control =
+            std::env::temp_dir().join(format!("dbuzzy-ssh-{}-{local_port}", std::process::id()));
+
+        let mut child = Command::new("ssh")
+            .args(config.args(&control, local_port, remote_host, remote_port))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .context("failed to run `ssh`")?;
+
+        // @note: drained all along, so ssh never blocks on a full pipe
+        let mut pipe = child.stderr.take().context("no stderr of `ssh`")?;
+        let stderr = tokio::spawn(async move {
+            let mut stderr = String::new();
+            pipe.read_to_string(&mut stderr).await.ok();
+            stderr
+        });
+
+        let mut tunnel = Self {
+            child,
+            control,
+            local_port,
+        };
+
+        let started = Instant::now();
+
+        loop {
+            if let Some(status) = tunnel.child.try_wait()? {
+                let stderr = stderr.await.unwrap_or_default();
+                if stderr.contains(FORWARD_FAILURE) {
+                    return Ok(None);
+                }
+
+                anyhow::bail!(
+                    "ssh to {} exited with {status}: {}",
+                    config.host,
+                    stderr.trim()
+                );
+            }
+
+            if tunnel.is_ready(config).await {
+                return Ok(Some(tunnel));
+            }
+
+            anyhow::ensure!(
+                started.elapsed() < READY_TIMEOUT,
+                "ssh tunnel to {} timed out",
+                config.host
+            );
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    // @note: the master listens on the control socket, once it is logged in and forwards
+    async fn is_ready(&self, config: &SshConfig) -> bool {
+        Command::new("ssh")
+            .arg("-S")
+            .arg(&self.control)
+            .args(["-O", "check", &config.host])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await
+            .is_ok_and(|status| status.success())
+    }
+
+    pub const fn local_port(&self) -> u16 {
+        self.local_port
+    }
+}
+
+impl Drop for SshTunnel {
+    fn drop(&mut self) {
+        // @note: a killed master leaves its socket behind
+        let _ = std::fs::remove_file(&self.control);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ssh_args() {
+        let config = SshConfig {
+            host: "db.internal".to_owned(),
+            port: Some(2222),
+            user: Some("deploy".to_owned()),
+            key: Some("/home/foo/.ssh/id_ed25519".into()),
+            jump: vec!["bastion".to_owned(), "admin@edge:2200".to_owned()],
+            options: vec!["StrictHostKeyChecking=accept-new".to_owned()],
+        };
+
+        assert_eq!(
+            config
+                .args(Path::new("/tmp/ctl"), 40000, "10.0.0.5", 5432)
+                .join(" "),
+            "-N -T -M -S /tmp/ctl -o ControlPersist=no -o BatchMode=yes \
+             -o ExitOnForwardFailure=yes \
+             -L 127.0.0.1:40000:10.0.0.5:5432 -p 2222 -l deploy -i /home/foo/.ssh/id_ed25519 \
+             -J bastion,admin@edge:2200 -o StrictHostKeyChecking=accept-new db.internal"
+        );
+
+        let config = SshConfig {
+            host: "db.internal".to_owned(),
+            ..Default::default()
+        };
+
+        assert!(config
+            .args(Path::new("/tmp/ctl"), 40000, "::1", 5432)
+            .contains(&"127.0.0.1:40000:[::1]:5432".to_owned()));
+    }
+}
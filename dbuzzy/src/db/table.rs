@@ -0,0 +1,265 @@
+#[cfg(feature = "postgres")]
+use deadpool_postgres::{Client, GenericClient};
+
+use super::{driver::Connection, DriverKind, QueryResult, Value};
+#[cfg(feature = "postgres")]
+use super::{Column, DbResult};
+
+// @note: below this estimate an exact `count(*)` is cheap enough
+pub(super) const EXACT_COUNT_THRESHOLD: i64 = 100_000;
+// @note: a table without statistics is counted only while it is this small
+#[cfg(feature = "postgres")]
+const EXACT_COUNT_MAX_BYTES: i64 = 8 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableRef {
+    pub schema: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowCount {
+    Exact(i64),
+    Estimated(i64),
+    // @note: no statistics and too big to count right away, counted on demand
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableInfo {
+    // @note: primary key, or the first unique key over not null columns
+    pub key: Vec<String>,
+    pub rows: RowCount,
+}
+
+#[derive(Debug)]
+pub struct TablePage {
+    pub page: usize,
+    pub result: QueryResult,
+}
+
+impl TableRef {
+    pub const fn new(schema: String, name: String) -> Self {
+        Self { schema, name }
+    }
+
+    pub fn qualified_name(&self) -> String {
+        self.quoted_name(DriverKind::Postgres)
+    }
+
+    // @note: `qualified_name` in the quotes of the driver
+    pub fn quoted_name(&self, kind: DriverKind) -> String {
+        format!(
+            "{}.{}",
+            kind.quote_ident(&self.schema),
+            kind.quote_ident(&self.name)
+        )
+    }
+
+    #[cfg(feature = "postgres")]
+    pub async fn info(&self, client: &Client) -> DbResult<TableInfo> {
+        let stmt = client
+            .prepare_cached(
+                r#"
+                    SELECT a.attname AS name
+                    FROM (
+                        SELECT i.indrelid, i.indkey
+                        FROM pg_index i
+                        WHERE i.indrelid = $1::text::regclass AND (
+                            i.indisprimary OR (
+                                i.indisunique AND i.indpred IS NULL AND i.indexprs IS NULL
+                                AND NOT EXISTS (
+                                    SELECT 1 FROM pg_attribute a
+                                    WHERE a.attrelid = i.indrelid
+                                        AND a.attnum = ANY(i.indkey) AND NOT a.attnotnull
+                                )
+                            )
+                        )
+                        ORDER BY i.indisprimary DESC, i.indexrelid
+                        LIMIT 1
+                    ) i
+                    CROSS JOIN LATERAL unnest(i.indkey) WITH ORDINALITY AS k(attnum, ord)
+                    JOIN pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = k.attnum
+                    ORDER BY k.ord
+                "#,
+            )
+            .await?;
+
+        let key = client
+            .query(&stmt, &[&self.qualified_name()])
+            .await?
+            .iter()
+            .map(|row| row.try_get("name"))
+            .collect::<Result<Vec<String>, _>>()?;
+
+        let rows = self.row_count(client).await?;
+
+        Ok(TableInfo { key, rows })
+    }
+
+    #[cfg(feature = "postgres")]
+    pub async fn columns(&self, client: &Client) -> DbResult<Vec<Column>> {
+        let stmt = client
+            .prepare_cached(
+                r#"
+                    SELECT a.attname AS name, format_type(a.atttypid, a.atttypmod) AS type_name
+                    FROM pg_attribute a
+                    WHERE a.attrelid = $1::text::regclass AND a.attnum > 0 AND NOT a.attisdropped
+                    ORDER BY a.attnum
+                "#,
+            )
+            .await?;
+
+        let columns = client
+            .query(&stmt, &[&self.qualified_name()])
+            .await?
+            .iter()
+            .map(|row| {
+                Ok(Column {
+                    name: row.try_get("name")?,
+                    type_name: row.try_get("type_name")?,
+                })
+            })
+            .collect::<DbResult<Vec<_>>>()?;
+
+        Ok(columns)
+    }
+
+    #[cfg(feature = "postgres")]
+    async fn row_count(&self, client: &Client) -> DbResult<RowCount> {
+        let stmt = client
+            .prepare_cached(
+                "SELECT reltuples::bigint, pg_relation_size(oid) FROM pg_class \
+                 WHERE oid = $1::text::regclass",
+            )
+            .await?;
+
+        let row = client.query_one(&stmt, &[&self.qualified_name()]).await?;
+        let estimate: i64 = row.try_get(0)?;
+        let size: i64 = row.try_get(1)?;
+
+        // @note: `reltuples` is -1 for tables that were never analyzed
+        match estimate {
+            ..0 if size > EXACT_COUNT_MAX_BYTES => return Ok(RowCount::Unknown),
+            EXACT_COUNT_THRESHOLD.. => return Ok(RowCount::Estimated(estimate)),
+            _ => {}
+        }
+
+        let sql = format!("SELECT count(*) FROM {}", self.qualified_name());
+        let count: i64 = client.query_one(&sql, &[]).await?.try_get(0)?;
+
+        Ok(RowCount::Exact(count))
+    }
+
+    pub async fn count(&self, conn: &dyn Connection) -> anyhow::Result<RowCount> {
+        let sql = self.count_query(conn.kind());
+        let result = conn.fetch(&sql).await?;
+
+        match result.rows.first().and_then(|row| row.first()) {
+            Some(Value::Int(count)) => Ok(RowCount::Exact(*count)),
+            value => anyhow::bail!("unexpected count: {value:?}"),
+        }
+    }
+
+    pub fn count_query(&self, kind: DriverKind) -> String {
+        format!("SELECT count(*) FROM {}", self.quoted_name(kind))
+    }
+
+    pub fn page_query(
+        &self,
+        kind: DriverKind,
+        info: &TableInfo,
+        page: usize,
+        page_size: usize,
+    ) -> String {
+        let mut sql = format!("SELECT * FROM {}", self.quoted_name(kind));
+
+        // @note: without a stable order pages may overlap
+        if !info.key.is_empty() {
+            let order = info
+                .key
+                .iter()
+                .map(|column| kind.quote_ident(column))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            sql = format!("{sql} ORDER BY {order}");
+        }
+
+        format!("{sql} LIMIT {page_size} OFFSET {}", page * page_size)
+    }
+
+    pub async fn fetch_page(
+        &self,
+        conn: &dyn Connection,
+        info: &TableInfo,
+        page: usize,
+        page_size: usize,
+    ) -> anyhow::Result<TablePage> {
+        let sql = self.page_query(conn.kind(), info, page, page_size);
+        let result = conn.fetch(&sql).await?;
+
+        Ok(TablePage { page, result })
+    }
+}
+
+impl std::fmt::Display for TableRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.schema, self.name)
+    }
+}
+
+impl std::fmt::Display for RowCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exact(count) => write!(f, "{count}"),
+            Self::Estimated(count) => write!(f, "~{count}"),
+            Self::Unknown => write!(f, "?"),
+        }
+    }
+}
+
+// @note: always quoted, so keywords and mixed case names are safe
+pub fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+pub fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_ident() {
+        assert_eq!(quote_ident("foo"), "\"foo\"");
+        assert_eq!(quote_ident("a\"b"), "\"a\"\"b\"");
+        assert_eq!(quote_literal("it's"), "'it''s'");
+
+        assert_eq!(DriverKind::Mysql.quote_ident("a`b"), "`a``b`");
+        assert_eq!(DriverKind::Mysql.quote_literal("it's \\"), "'it''s \\\\'");
+        assert_eq!(DriverKind::Sqlite.quote_ident("foo"), "\"foo\"");
+    }
+
+    #[test]
+    fn test_page_query() {
+        let table = TableRef::new("foo".to_owned(), "Bar".to_owned());
+        let mut info = TableInfo {
+            key: vec!["id".to_owned(), "Key".to_owned()],
+            rows: RowCount::Exact(0),
+        };
+
+        assert_eq!(
+            table.page_query(DriverKind::Postgres, &info, 2, 50),
+            "SELECT * FROM \"foo\".\"Bar\" ORDER BY \"id\", \"Key\" LIMIT 50 OFFSET 100"
+        );
+
+        info.key.clear();
+        assert_eq!(
+            table.page_query(DriverKind::Mysql, &info, 0, 50),
+            "SELECT * FROM `foo`.`Bar` LIMIT 50 OFFSET 0"
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use std::path::Path;
+
+use native_tls::{Certificate, Identity, TlsConnector};
+use postgres_native_tls::MakeTlsConnector;
+
+use super::{ConnectionConfig, DbResult, Error, SslMode};
+
+pub fn connector(config: &ConnectionConfig) -> DbResult<MakeTlsConnector> {
+    let mut builder = TlsConnector::builder();
+
+    if let Some(path) = config.sslrootcert.as_ref() {
+        builder.add_root_certificate(Certificate::from_pem(&read(path)?)?);
+    }
+
+    match config.sslmode {
+        // @note: like libpq, a root certificate turns `require` into `verify-ca`
+        SslMode::Prefer | SslMode::Require if config.sslrootcert.is_none() => {
+            builder.danger_accept_invalid_certs(true);
+        }
+        SslMode::Prefer | SslMode::Require | SslMode::VerifyCa => {
+            builder.danger_accept_invalid_hostnames(true);
+        }
+        SslMode::Disable | SslMode::VerifyFull => (),
+    }
+
+    // @note: native-tls only reads PKCS#8 keys (`BEGIN PRIVATE KEY`)
+    match (config.sslcert.as_ref(), config.sslkey.as_ref()) {
+        (Some(cert), Some(key)) => {
+            builder.identity(Identity::from_pkcs8(&read(cert)?, &read(key)?)?);
+        }
+        (None, None) => (),
+        _ => return Err(Error::ClientCert),
+    }
+
+    Ok(MakeTlsConnector::new(builder.build()?))
+}
+
+fn read(path: &Path) -> DbResult<Vec<u8>> {
+    std::fs::read(path).map_err(|e| Error::ReadCert(path.to_owned(), e))
+}
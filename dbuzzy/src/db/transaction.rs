@@ -0,0 +1,215 @@
+use anyhow::Context;
+
+use super::{
+    driver::{self, CancelHandle, Connection},
+    DbPool, DriverKind, EditBatch, Plan, QueryResult, RowCount, TableInfo, TablePage, TableRef,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionState {
+    Active,
+    // @note: a statement failed, only a rollback makes it usable again
+    Failed,
+}
+
+// @note: a connection pinned to the session from BEGIN to COMMIT or ROLLBACK
+pub struct Transaction {
+    // @note: `None` when finished
+    conn: Option<Box<dyn Connection>>,
+    state: TransactionState,
+    savepoints: usize,
+}
+
+impl Transaction {
+    pub async fn begin(pool: &DbPool) -> anyhow::Result<Self> {
+        let conn = pool.acquire().await?;
+        conn.fetch("BEGIN").await?;
+
+        Ok(Self {
+            conn: Some(conn),
+            state: TransactionState::Active,
+            savepoints: 0,
+        })
+    }
+
+    pub const fn state(&self) -> TransactionState {
+        self.state
+    }
+
+    pub const fn is_finished(&self) -> bool {
+        self.conn.is_none()
+    }
+
+    pub const fn savepoints(&self) -> usize {
+        self.savepoints
+    }
+
+    pub fn cancel_handle(&self) -> Option<Box<dyn CancelHandle>> {
+        self.conn.as_ref().and_then(|conn| conn.cancel_handle())
+    }
+
+    fn conn(&self) -> anyhow::Result<&dyn Connection> {
+        self.conn.as_deref().context("the transaction is finished")
+    }
+
+    pub async fn fetch(&mut self, sql: &str) -> anyhow::Result<QueryResult> {
+        let result = self.conn()?.fetch(sql).await;
+        self.track(sql, result.is_ok());
+        result
+    }
+
+    pub async fn fetch_params(
+        &mut self,
+        sql: &str,
+        values: &[Option<String>],
+    ) -> anyhow::Result<QueryResult> {
+        let result = self.conn()?.fetch_params(sql, values).await;
+        self.track(sql, result.is_ok());
+        result
+    }
+
+    pub async fn explain(&self, sql: &str, analyze: bool) -> anyhow::Result<Plan> {
+        Plan::fetch(self.conn()?, sql, analyze, true).await
+    }
+
+    pub async fn table_info(&self, table: &TableRef) -> anyhow::Result<TableInfo> {
+        self.begin_nested().await?;
+        let info = self.conn()?.table_info(table).await;
+        self.end_nested(info).await
+    }
+
+    pub async fn count_rows(&self, table: &TableRef) -> anyhow::Result<RowCount> {
+        self.begin_nested().await?;
+        let count = table.count(self.conn()?).await;
+        self.end_nested(count).await
+    }
+
+    pub async fn fetch_page(
+        &self,
+        table: &TableRef,
+        info: &TableInfo,
+        page: usize,
+        page_size: usize,
+    ) -> anyhow::Result<TablePage> {
+        self.begin_nested().await?;
+        let page = table.fetch_page(self.conn()?, info, page, page_size).await;
+        self.end_nested(page).await
+    }
+
+    // @note: the edits are kept until the transaction commits
+    pub async fn commit_edits(&self, edits: &EditBatch) -> anyhow::Result<u64> {
+        self.begin_nested().await?;
+        let affected = edits.execute(self.conn()?).await;
+        self.end_nested(affected).await
+    }
+
+    // @note: the table views work inside a savepoint of their own,
+    // so a failing statement doesn't fail the whole transaction
+    async fn begin_nested(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.state == TransactionState::Active,
+            "the transaction failed, roll it back first"
+        );
+
+        self.conn()?.fetch("SAVEPOINT dbuzzy_nested").await?;
+        Ok(())
+    }
+
+    async fn end_nested<T>(&self, result: anyhow::Result<T>) -> anyhow::Result<T> {
+        let statements: &[_] = match result.is_ok() {
+            true => &["RELEASE SAVEPOINT dbuzzy_nested"],
+            false => &[
+                "ROLLBACK TO SAVEPOINT dbuzzy_nested",
+                "RELEASE SAVEPOINT dbuzzy_nested",
+            ],
+        };
+
+        driver::close(self.conn()?, statements, result).await
+    }
+
+    pub async fn savepoint(&mut self, name: &str) -> anyhow::Result<()> {
+        let kind = self.conn()?.kind();
+        self.fetch(&format!("SAVEPOINT {}", kind.quote_ident(name)))
+            .await?;
+        self.savepoints += 1;
+        Ok(())
+    }
+
+    pub async fn commit(&mut self) -> anyhow::Result<()> {
+        self.finish("COMMIT").await
+    }
+
+    pub async fn rollback(&mut self) -> anyhow::Result<()> {
+        self.finish("ROLLBACK").await
+    }
+
+    async fn finish(&mut self, sql: &str) -> anyhow::Result<()> {
+        let result = self.conn()?.fetch(sql).await;
+
+        // @note: the transaction is over either way
+        if let Some(conn) = self.conn.take() {
+            if result.is_err() {
+                conn.discard();
+            }
+        }
+
+        result.map(|_| ())
+    }
+
+    // @note: COMMIT or ROLLBACK typed in the editor end the transaction too,
+    // only Postgres refuses every statement after a failed one
+    fn track(&mut self, sql: &str, ok: bool) {
+        let kind = self.conn.as_ref().map(|conn| conn.kind());
+
+        if !ok && kind == Some(DriverKind::Postgres) {
+            self.state = TransactionState::Failed;
+        } else if ok && ends_transaction(sql) {
+            self.conn = None;
+        } else if ok {
+            self.state = TransactionState::Active;
+        }
+    }
+}
+
+impl Drop for Transaction {
+    // @note: the pool does not reset its connections, so an unfinished transaction
+    // is not given back, closing its connection rolls it back
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            conn.discard();
+        }
+    }
+}
+
+fn ends_transaction(sql: &str) -> bool {
+    let words = sql
+        .trim()
+        .trim_end_matches(';')
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>();
+
+    match words.first().map(String::as_str) {
+        Some("commit" | "end" | "abort") => true,
+        Some("rollback") => !words.iter().any(|word| word == "to"),
+        Some("prepare") => words.get(1).is_some_and(|word| word == "transaction"),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ends_transaction() {
+        assert!(ends_transaction("commit;"));
+        assert!(ends_transaction("  ROLLBACK WORK"));
+        assert!(ends_transaction("end"));
+        assert!(ends_transaction("prepare transaction 'tx1'"));
+        assert!(!ends_transaction("rollback to savepoint sp1"));
+        assert!(!ends_transaction("ROLLBACK TO sp1"));
+        assert!(!ends_transaction("select 'commit'"));
+        assert!(!ends_transaction("prepare stmt as select 1"));
+    }
+}
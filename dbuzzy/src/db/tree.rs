@@ -1,8 +1,15 @@
-use std::sync::{Arc, Weak};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 
+#[cfg(feature = "postgres")]
 use anyhow::Context;
+#[cfg(feature = "postgres")]
 use deadpool_postgres::{Client, GenericClient};
 
+use super::driver::Connection;
+
 const DEFAULT_INDENT: u8 = 4;
 
 #[derive(Debug, Default)]
@@ -14,11 +21,62 @@ impl AsRef<[Arc<TreeItem>]> for DatabaseTree {
     }
 }
 
+// @note: items are shared with their children behind `Arc`,
+// so the flag has to be mutable through a shared reference
+#[derive(Debug, Default)]
+pub struct Collapsed(AtomicBool);
+
+impl Collapsed {
+    pub const fn new(value: bool) -> Self {
+        Self(AtomicBool::new(value))
+    }
+
+    pub fn get(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn set(&self, value: bool) {
+        self.0.store(value, Ordering::Relaxed);
+    }
+}
+
+impl PartialEq for Collapsed {
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+// @note: the children were fetched, a group may have none
+#[derive(Debug, Default)]
+pub struct Loaded(AtomicBool);
+
+impl Loaded {
+    pub const fn new() -> Self {
+        Self(AtomicBool::new(false))
+    }
+
+    pub fn get(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn set(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+impl PartialEq for Loaded {
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct TreeItem {
     pub indent: u8,
     pub name: String,
     pub kind: TreeItemKind,
+    pub collapsed: Collapsed,
+    pub loaded: Loaded,
 }
 
 impl TreeItem {
@@ -26,7 +84,9 @@ impl TreeItem {
         Self {
             name,
             indent: 0,
-            kind: TreeItemKind::Database { collapsed: false },
+            kind: TreeItemKind::Database,
+            collapsed: Collapsed::new(false),
+            loaded: Loaded::new(),
         }
     }
 
@@ -34,23 +94,66 @@ impl TreeItem {
         Self {
             name,
             indent: DEFAULT_INDENT,
-            kind: TreeItemKind::Schema {
-                database,
-                collapsed: false,
+            kind: TreeItemKind::Schema { database },
+            collapsed: Collapsed::new(true),
+            loaded: Loaded::new(),
+        }
+    }
+
+    pub fn table(name: String, group: Arc<Self>) -> Self {
+        Self {
+            name,
+            indent: group.indent + DEFAULT_INDENT,
+            kind: TreeItemKind::Table { group },
+            collapsed: Collapsed::new(true),
+            loaded: Loaded::new(),
+        }
+    }
+
+    pub fn group(group: TreeGroup, parent: Arc<Self>) -> Self {
+        Self {
+            name: group.as_ref().to_owned(),
+            indent: parent.indent + DEFAULT_INDENT,
+            kind: TreeItemKind::Group { group, parent },
+            collapsed: Collapsed::new(true),
+            loaded: Loaded::new(),
+        }
+    }
+
+    pub fn groups(groups: &[TreeGroup], parent: &Arc<Self>) -> Vec<Arc<Self>> {
+        groups
+            .iter()
+            .map(|group| Arc::new(Self::group(*group, Arc::clone(parent))))
+            .collect()
+    }
+
+    pub fn column(column: ColumnInfo, group: Arc<Self>) -> Self {
+        Self {
+            name: column.name,
+            indent: group.indent + DEFAULT_INDENT,
+            kind: TreeItemKind::Column {
+                data_type: column.data_type,
+                nullable: column.nullable,
+                default: column.default,
+                group,
             },
+            collapsed: Collapsed::new(true),
+            loaded: Loaded::new(),
         }
     }
 
-    pub const fn table(name: String, schema: Arc<Self>, database: Arc<Self>) -> Self {
+    pub fn object(name: String, definition: String, group: Arc<Self>) -> Self {
         Self {
             name,
-            indent: DEFAULT_INDENT * 2,
-            kind: TreeItemKind::Table { schema, database },
+            indent: group.indent + DEFAULT_INDENT,
+            kind: TreeItemKind::Object { definition, group },
+            collapsed: Collapsed::new(true),
+            loaded: Loaded::new(),
         }
     }
 
     pub const fn is_database(&self) -> bool {
-        matches!(self.kind, TreeItemKind::Database { .. })
+        matches!(self.kind, TreeItemKind::Database)
     }
 
     pub const fn is_schema(&self) -> bool {
@@ -61,105 +164,600 @@ impl TreeItem {
         matches!(self.kind, TreeItemKind::Table { .. })
     }
 
-    pub const fn is_collapsed(&self) -> bool {
+    pub const fn is_group(&self) -> bool {
+        matches!(self.kind, TreeItemKind::Group { .. })
+    }
+
+    pub const fn is_leaf(&self) -> bool {
+        matches!(
+            self.kind,
+            TreeItemKind::Column { .. } | TreeItemKind::Object { .. }
+        )
+    }
+
+    pub fn is_collapsed(&self) -> bool {
+        self.collapsed.get()
+    }
+
+    pub const fn parent(&self) -> Option<&Arc<Self>> {
         match self.kind {
-            TreeItemKind::Database { collapsed } => collapsed,
-            TreeItemKind::Schema { collapsed, .. } => collapsed,
-            TreeItemKind::Table { .. } => true,
+            TreeItemKind::Database => None,
+            TreeItemKind::Schema { ref database, .. } => Some(database),
+            TreeItemKind::Group { ref parent, .. } => Some(parent),
+            TreeItemKind::Table { ref group }
+            | TreeItemKind::Column { ref group, .. }
+            | TreeItemKind::Object { ref group, .. } => Some(group),
         }
     }
 
-    pub fn is_visible(&self) -> bool {
+    pub fn schema_name(&self) -> Option<&str> {
+        if self.is_schema() {
+            return Some(&self.name);
+        }
+
+        self.parent()?.schema_name()
+    }
+
+    pub const fn group_kind(&self) -> Option<TreeGroup> {
         match self.kind {
-            TreeItemKind::Database { .. } => true,
-            TreeItemKind::Schema { ref database, .. } => !database.is_collapsed(),
-            TreeItemKind::Table {
-                ref schema,
-                ref database,
-            } => !schema.is_collapsed() || !database.is_collapsed(),
+            TreeItemKind::Group { group, .. } => Some(group),
+            _ => None,
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        !self
+            .parent()
+            .is_some_and(|p| p.is_collapsed() || !p.is_visible())
+    }
+
+    pub fn label(&self) -> String {
+        match &self.kind {
+            TreeItemKind::Column {
+                data_type,
+                nullable,
+                default,
+                ..
+            } => {
+                let mut label = format!("{} {data_type}", self.name);
+                if !nullable {
+                    label.push_str(" not null");
+                }
+                if let Some(default) = default {
+                    label = format!("{label} default {default}");
+                }
+                label
+            }
+            TreeItemKind::Object { definition, .. } if !definition.is_empty() => {
+                format!("{}: {definition}", self.name)
+            }
+            _ => self.name.to_owned(),
         }
     }
 }
 
 #[derive(Debug, PartialEq)]
 pub enum TreeItemKind {
-    Database {
-        collapsed: bool,
-    },
+    Database,
     Schema {
-        collapsed: bool,
         database: Arc<TreeItem>,
     },
+    Group {
+        group: TreeGroup,
+        parent: Arc<TreeItem>,
+    },
     Table {
-        schema: Arc<TreeItem>,
-        database: Arc<TreeItem>,
+        group: Arc<TreeItem>,
+    },
+    Column {
+        data_type: String,
+        nullable: bool,
+        default: Option<String>,
+        group: Arc<TreeItem>,
+    },
+    Object {
+        definition: String,
+        group: Arc<TreeItem>,
     },
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreeGroup {
+    Tables,
+    Views,
+    MaterializedViews,
+    Functions,
+    Sequences,
+    Types,
+    Extensions,
+    Columns,
+    Indexes,
+    Constraints,
+    ForeignKeys,
+    Triggers,
+}
+
+impl TreeGroup {
+    const SCHEMA: [Self; 7] = [
+        Self::Tables,
+        Self::Views,
+        Self::MaterializedViews,
+        Self::Functions,
+        Self::Sequences,
+        Self::Types,
+        Self::Extensions,
+    ];
+
+    const TABLE: [Self; 5] = [
+        Self::Columns,
+        Self::Indexes,
+        Self::Constraints,
+        Self::ForeignKeys,
+        Self::Triggers,
+    ];
+
+    const fn is_schema_level(&self) -> bool {
+        matches!(
+            self,
+            Self::Tables
+                | Self::Views
+                | Self::MaterializedViews
+                | Self::Functions
+                | Self::Sequences
+                | Self::Types
+                | Self::Extensions
+        )
+    }
+
+    const fn query(&self) -> &'static str {
+        match self {
+            Self::Tables => {
+                r#"
+                    SELECT c.relname AS name, '' AS definition
+                    FROM pg_class c
+                    JOIN pg_namespace n ON n.oid = c.relnamespace
+                    WHERE n.nspname = $1 AND c.relkind IN ('r', 'p', 'f')
+                    ORDER BY c.relname
+                "#
+            }
+            Self::Views => {
+                r#"
+                    SELECT c.relname AS name, '' AS definition
+                    FROM pg_class c
+                    JOIN pg_namespace n ON n.oid = c.relnamespace
+                    WHERE n.nspname = $1 AND c.relkind = 'v'
+                    ORDER BY c.relname
+                "#
+            }
+            Self::MaterializedViews => {
+                r#"
+                    SELECT c.relname AS name, '' AS definition
+                    FROM pg_class c
+                    JOIN pg_namespace n ON n.oid = c.relnamespace
+                    WHERE n.nspname = $1 AND c.relkind = 'm'
+                    ORDER BY c.relname
+                "#
+            }
+            Self::Functions => {
+                r#"
+                    SELECT
+                        p.proname || '(' || pg_get_function_identity_arguments(p.oid) || ')' AS name,
+                        CASE p.prokind
+                            WHEN 'p' THEN 'procedure'
+                            WHEN 'a' THEN 'aggregate'
+                            WHEN 'w' THEN 'window'
+                            ELSE pg_get_function_result(p.oid)
+                        END AS definition
+                    FROM pg_proc p
+                    JOIN pg_namespace n ON n.oid = p.pronamespace
+                    WHERE n.nspname = $1 AND NOT EXISTS (
+                        SELECT 1 FROM pg_depend d WHERE d.objid = p.oid AND d.deptype = 'e'
+                    )
+                    ORDER BY 1
+                "#
+            }
+            Self::Sequences => {
+                r#"
+                    SELECT c.relname AS name, format_type(s.seqtypid, NULL) AS definition
+                    FROM pg_sequence s
+                    JOIN pg_class c ON c.oid = s.seqrelid
+                    JOIN pg_namespace n ON n.oid = c.relnamespace
+                    WHERE n.nspname = $1
+                    ORDER BY c.relname
+                "#
+            }
+            Self::Types => {
+                r#"
+                    SELECT
+                        t.typname AS name,
+                        CASE t.typtype
+                            WHEN 'e' THEN 'enum (' || (
+                                SELECT string_agg(quote_literal(e.enumlabel), ', ' ORDER BY e.enumsortorder)
+                                FROM pg_enum e WHERE e.enumtypid = t.oid
+                            ) || ')'
+                            WHEN 'd' THEN 'domain ' || format_type(t.typbasetype, t.typtypmod)
+                            ELSE 'composite'
+                        END AS definition
+                    FROM pg_type t
+                    JOIN pg_namespace n ON n.oid = t.typnamespace
+                    WHERE n.nspname = $1 AND (
+                        t.typtype IN ('e', 'd') OR (t.typtype = 'c' AND EXISTS (
+                            SELECT 1 FROM pg_class c WHERE c.oid = t.typrelid AND c.relkind = 'c'
+                        ))
+                    )
+                    ORDER BY t.typname
+                "#
+            }
+            Self::Extensions => {
+                r#"
+                    SELECT e.extname AS name, e.extversion AS definition
+                    FROM pg_extension e
+                    JOIN pg_namespace n ON n.oid = e.extnamespace
+                    WHERE n.nspname = $1
+                    ORDER BY e.extname
+                "#
+            }
+            Self::Columns => {
+                r#"
+                    SELECT
+                        a.attname AS name,
+                        format_type(a.atttypid, a.atttypmod) AS data_type,
+                        NOT a.attnotnull AS nullable,
+                        pg_get_expr(d.adbin, d.adrelid) AS "default"
+                    FROM pg_attribute a
+                    JOIN pg_class c ON c.oid = a.attrelid
+                    JOIN pg_namespace n ON n.oid = c.relnamespace
+                    LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+                    WHERE n.nspname = $1 AND c.relname = $2
+                        AND a.attnum > 0 AND NOT a.attisdropped
+                    ORDER BY a.attnum
+                "#
+            }
+            Self::Indexes => {
+                r#"
+                    SELECT i.relname AS name, pg_get_indexdef(x.indexrelid) AS definition
+                    FROM pg_index x
+                    JOIN pg_class i ON i.oid = x.indexrelid
+                    JOIN pg_class c ON c.oid = x.indrelid
+                    JOIN pg_namespace n ON n.oid = c.relnamespace
+                    WHERE n.nspname = $1 AND c.relname = $2
+                    ORDER BY i.relname
+                "#
+            }
+            Self::Constraints => {
+                r#"
+                    SELECT con.conname AS name, pg_get_constraintdef(con.oid) AS definition
+                    FROM pg_constraint con
+                    JOIN pg_class c ON c.oid = con.conrelid
+                    JOIN pg_namespace n ON n.oid = c.relnamespace
+                    WHERE n.nspname = $1 AND c.relname = $2 AND con.contype <> 'f'
+                    ORDER BY con.conname
+                "#
+            }
+            Self::ForeignKeys => {
+                r#"
+                    SELECT con.conname AS name, pg_get_constraintdef(con.oid) AS definition
+                    FROM pg_constraint con
+                    JOIN pg_class c ON c.oid = con.conrelid
+                    JOIN pg_namespace n ON n.oid = c.relnamespace
+                    WHERE n.nspname = $1 AND c.relname = $2 AND con.contype = 'f'
+                    ORDER BY con.conname
+                "#
+            }
+            Self::Triggers => {
+                r#"
+                    SELECT t.tgname AS name, pg_get_triggerdef(t.oid) AS definition
+                    FROM pg_trigger t
+                    JOIN pg_class c ON c.oid = t.tgrelid
+                    JOIN pg_namespace n ON n.oid = c.relnamespace
+                    WHERE n.nspname = $1 AND c.relname = $2 AND NOT t.tgisinternal
+                    ORDER BY t.tgname
+                "#
+            }
+        }
+    }
+}
+
+impl AsRef<str> for TreeGroup {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Tables => "Tables",
+            Self::Views => "Views",
+            Self::MaterializedViews => "Materialized Views",
+            Self::Functions => "Functions",
+            Self::Sequences => "Sequences",
+            Self::Types => "Types",
+            Self::Extensions => "Extensions",
+            Self::Columns => "Columns",
+            Self::Indexes => "Indexes",
+            Self::Constraints => "Constraints",
+            Self::ForeignKeys => "Foreign Keys",
+            Self::Triggers => "Triggers",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnInfo {
+    pub name: String,
+    pub data_type: String,
+    pub nullable: bool,
+    pub default: Option<String>,
+}
+
 impl DatabaseTree {
+    pub const fn new(items: Vec<Arc<TreeItem>>) -> Self {
+        Self(items)
+    }
+
+    #[cfg(feature = "postgres")]
     pub async fn load(client: &Client) -> anyhow::Result<Self> {
         let stmt = client
             .prepare(
                 r#"
                     SELECT
-                        table_catalog AS database,
-                        table_schema AS schema,
-                        jsonb_agg(table_name) AS tables
-                    FROM information_schema.tables
-                    WHERE table_schema NOT IN ('pg_catalog', 'information_schema')
-                    GROUP BY table_catalog, table_schema
-                    ORDER BY table_catalog
+                        current_database() AS database,
+                        jsonb_agg(nspname ORDER BY nspname) AS schemas
+                    FROM pg_namespace
+                    WHERE nspname NOT IN ('pg_catalog', 'information_schema')
+                        AND nspname NOT LIKE 'pg\_toast%'
+                        AND nspname NOT LIKE 'pg\_temp\_%'
                 "#,
             )
             .await?;
 
-        let mut tree = vec![];
-        let mut db = Weak::<TreeItem>::default();
+        let row = client.query_one(&stmt, &[]).await?;
+
+        let db_item = Arc::new(TreeItem::database(row.try_get("database")?));
+        let mut tree = vec![Arc::clone(&db_item)];
 
-        let rows = client.query(&stmt, &[]).await?;
+        let json_value: serde_json::Value = row.try_get("schemas")?;
+        let schemas: Vec<String> = serde_json::from_value(json_value)?;
 
-        for row in rows {
-            let db_item = Arc::new(TreeItem::database(row.try_get("database")?));
-            new_db_tree(&db_item, &mut db, &mut tree);
+        for schema in schemas {
+            let schema_item = Arc::new(TreeItem::schema(schema, Arc::clone(&db_item)));
+            tree.push(schema_item);
+        }
 
-            let db_item = db.upgrade().with_context(|| "shouldn't be dropped")?;
+        Ok(Self(tree))
+    }
 
-            let schema_item = Arc::new(TreeItem::schema(
-                row.try_get("schema")?,
-                Arc::clone(&db_item),
-            ));
+    pub fn has_children(&self, index: usize) -> bool {
+        let Some(item) = self.0.get(index) else {
+            return false;
+        };
 
-            tree.push(Arc::clone(&schema_item));
+        self.0
+            .get(index + 1)
+            .is_some_and(|next| next.indent > item.indent)
+    }
 
-            let json_value: serde_json::Value = row.try_get("tables")?;
-            let tables: Vec<String> = serde_json::from_value(json_value)?;
+    pub fn parent_index(&self, index: usize) -> Option<usize> {
+        let indent = self.0.get(index)?.indent;
+        self.0[..index].iter().rposition(|x| x.indent < indent)
+    }
 
-            for table in tables {
-                let table_item = Arc::new(TreeItem::table(
-                    table,
-                    Arc::clone(&schema_item),
-                    Arc::clone(&db_item),
-                ));
+    pub fn next_sibling(&self, index: usize) -> Option<usize> {
+        let indent = self.0.get(index)?.indent;
+        let next = index
+            + 1
+            + self.0[index + 1..]
+                .iter()
+                .position(|x| x.indent <= indent)?;
 
-                tree.push(table_item);
-            }
+        (self.0[next].indent == indent).then_some(next)
+    }
+
+    pub fn prev_sibling(&self, index: usize) -> Option<usize> {
+        let indent = self.0.get(index)?.indent;
+        let prev = self.0[..index].iter().rposition(|x| x.indent <= indent)?;
+
+        (self.0[prev].indent == indent).then_some(prev)
+    }
+
+    pub fn collapse(&self, index: usize) {
+        if let Some(item) = self.0.get(index).filter(|x| !x.is_leaf()) {
+            item.collapsed.set(true);
         }
+    }
 
-        Ok(Self(tree))
+    pub fn expand_loaded(&self, index: usize) -> bool {
+        let loaded = self.0.get(index).is_some_and(|item| item.loaded.get());
+
+        if !loaded && !self.has_children(index) {
+            return false;
+        }
+
+        self.0[index].collapsed.set(false);
+        true
     }
-}
 
-fn new_db_tree(item: &Arc<TreeItem>, db: &mut Weak<TreeItem>, tree: &mut Vec<Arc<TreeItem>>) {
-    fn inner(item: &Arc<TreeItem>, db: &mut Weak<TreeItem>, tree: &mut Vec<Arc<TreeItem>>) {
-        *db = Arc::downgrade(item);
-        tree.push(Arc::clone(item));
+    pub fn expand_all(&self) {
+        for index in 0..self.0.len() {
+            self.expand_loaded(index);
+        }
+    }
+
+    pub fn collapse_all(&self) {
+        // @note: keep databases open, so schemas stay reachable
+        for item in self.0.iter().filter(|x| x.indent > 0 && !x.is_leaf()) {
+            item.collapsed.set(true);
+        }
+    }
+
+    pub async fn expand(&mut self, conn: &dyn Connection, index: usize) -> anyhow::Result<()> {
+        if self.expand_loaded(index) {
+            return Ok(());
+        }
+
+        let Some(item) = self.0.get(index).cloned() else {
+            return Ok(());
+        };
+
+        let children = conn.children(&item).await?;
+        self.insert_children(&item, children);
+
+        Ok(())
     }
 
-    if let Some(db_ref) = db.upgrade() {
-        if db_ref.name != item.name {
-            inner(item, db, tree);
+    // @note: the item is looked up again, as the children are loaded by a background task
+    // and the items before it can be expanded in the meantime
+    pub fn insert_children(&mut self, item: &Arc<TreeItem>, children: Vec<Arc<TreeItem>>) {
+        let Some(index) = self.0.iter().position(|x| Arc::ptr_eq(x, item)) else {
+            return;
+        };
+
+        if !item.loaded.get() {
+            self.0.splice(index + 1..index + 1, children);
+            item.loaded.set();
         }
+
+        item.collapsed.set(false);
+    }
+}
+
+#[cfg(feature = "postgres")]
+pub(super) async fn load_children(
+    client: &Client,
+    item: &Arc<TreeItem>,
+) -> anyhow::Result<Vec<Arc<TreeItem>>> {
+    let children = match item.kind {
+        TreeItemKind::Schema { .. } => TreeItem::groups(&TreeGroup::SCHEMA, item),
+        TreeItemKind::Table { .. } => TreeItem::groups(&TreeGroup::TABLE, item),
+        TreeItemKind::Group { group, .. } => load_group(client, group, item).await?,
+        _ => vec![],
+    };
+
+    Ok(children)
+}
+
+#[cfg(feature = "postgres")]
+async fn load_group(
+    client: &Client,
+    group: TreeGroup,
+    item: &Arc<TreeItem>,
+) -> anyhow::Result<Vec<Arc<TreeItem>>> {
+    let schema = item.schema_name().context("group should have a schema")?;
+    let stmt = client.prepare_cached(group.query()).await?;
+
+    let rows = if group.is_schema_level() {
+        client.query(&stmt, &[&schema]).await?
     } else {
-        inner(item, db, tree);
+        let table = item.parent().context("group should have a parent")?;
+        client.query(&stmt, &[&schema, &table.name]).await?
+    };
+
+    let mut children = Vec::with_capacity(rows.len());
+
+    for row in rows {
+        let child = match group {
+            TreeGroup::Tables => TreeItem::table(row.try_get("name")?, Arc::clone(item)),
+            TreeGroup::Columns => {
+                let column = ColumnInfo {
+                    name: row.try_get("name")?,
+                    data_type: row.try_get("data_type")?,
+                    nullable: row.try_get("nullable")?,
+                    default: row.try_get("default")?,
+                };
+
+                TreeItem::column(column, Arc::clone(item))
+            }
+            _ => TreeItem::object(
+                row.try_get("name")?,
+                row.try_get("definition")?,
+                Arc::clone(item),
+            ),
+        };
+
+        children.push(Arc::new(child));
+    }
+
+    Ok(children)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> DatabaseTree {
+        let db = Arc::new(TreeItem::database("db".to_owned()));
+        let foo = Arc::new(TreeItem::schema("foo".to_owned(), Arc::clone(&db)));
+        let tables = Arc::new(TreeItem::group(TreeGroup::Tables, Arc::clone(&foo)));
+        let bar = Arc::new(TreeItem::table("bar".to_owned(), Arc::clone(&tables)));
+        let views = Arc::new(TreeItem::group(TreeGroup::Views, Arc::clone(&foo)));
+        let public = Arc::new(TreeItem::schema("public".to_owned(), Arc::clone(&db)));
+
+        DatabaseTree(vec![db, foo, tables, bar, views, public])
+    }
+
+    #[test]
+    fn test_visibility() {
+        let tree = tree();
+        let items = tree.as_ref();
+
+        assert!(items[1].is_visible());
+        assert!(!items[2].is_visible());
+
+        assert!(tree.expand_loaded(1));
+        assert!(items[2].is_visible());
+        assert!(!items[3].is_visible());
+
+        assert!(tree.expand_loaded(2));
+        assert!(items[3].is_visible());
+
+        // @note: collapsing an ancestor hides the whole subtree
+        tree.collapse(1);
+        assert!(!items[2].is_visible());
+        assert!(!items[3].is_visible());
+        assert!(!items[2].is_collapsed());
+
+        tree.expand_all();
+        assert!(items[3].is_visible());
+
+        tree.collapse_all();
+        assert!(items[1].is_visible());
+        assert!(!items[2].is_visible());
+    }
+
+    #[test]
+    fn test_navigation() {
+        let tree = tree();
+
+        assert_eq!(tree.parent_index(3), Some(2));
+        assert_eq!(tree.parent_index(4), Some(1));
+        assert_eq!(tree.parent_index(0), None);
+
+        assert_eq!(tree.next_sibling(1), Some(5));
+        assert_eq!(tree.next_sibling(2), Some(4));
+        assert_eq!(tree.next_sibling(4), None);
+        assert_eq!(tree.prev_sibling(5), Some(1));
+        assert_eq!(tree.prev_sibling(2), None);
+
+        assert!(!tree.expand_loaded(4));
+
+        // @note: an empty group is not fetched again
+        tree.as_ref()[4].loaded.set();
+        assert!(tree.expand_loaded(4));
+        assert!(!tree.as_ref()[4].is_collapsed());
+    }
+
+    #[test]
+    fn test_insert_children() {
+        let mut tree = tree();
+        let public = Arc::clone(&tree.as_ref()[5]);
+        let tables = Arc::new(TreeItem::group(TreeGroup::Tables, Arc::clone(&public)));
+
+        // @note: the children of `views` arrive first and move `public` down
+        let views = Arc::clone(&tree.as_ref()[4]);
+        let baz = Arc::new(TreeItem::table("baz".to_owned(), Arc::clone(&views)));
+        tree.insert_children(&views, vec![baz]);
+
+        tree.insert_children(&public, vec![Arc::clone(&tables)]);
+        assert!(Arc::ptr_eq(&tree.as_ref()[7], &tables));
+        assert!(!public.is_collapsed());
+
+        // @note: a second answer for a loaded item is dropped
+        tree.insert_children(&public, vec![Arc::clone(&tables)]);
+        assert_eq!(tree.as_ref().len(), 8);
     }
 }
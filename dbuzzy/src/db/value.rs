@@ -0,0 +1,340 @@
+use std::fmt::Write;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+#[cfg(feature = "postgres")]
+use tokio_postgres::types::{FromSql, Kind, Type};
+
+type FromSqlResult<T> = Result<T, Box<dyn std::error::Error + Sync + Send>>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Numeric(String),
+    Text(String),
+    Json(serde_json::Value),
+    Bytes(Vec<u8>),
+    Date(NaiveDate),
+    Time(NaiveTime),
+    Timestamp(NaiveDateTime),
+    TimestampTz(DateTime<Utc>),
+    Array(Vec<Self>),
+    Unsupported(String),
+}
+
+impl Value {
+    pub const fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    pub const fn is_numeric(&self) -> bool {
+        matches!(self, Self::Int(_) | Self::Float(_) | Self::Numeric(_))
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::Value as Json;
+
+        match self {
+            Self::Null => Json::Null,
+            Self::Bool(v) => Json::Bool(*v),
+            Self::Int(v) => Json::from(*v),
+            Self::Float(v) => serde_json::Number::from_f64(*v).map_or(Json::Null, Json::Number),
+            // @note: kept as a string, so no precision is lost
+            Self::Numeric(v) | Self::Text(v) => Json::String(v.to_owned()),
+            Self::Json(v) => v.clone(),
+            Self::Array(values) => Json::Array(values.iter().map(Self::to_json).collect()),
+            _ => Json::String(self.to_string()),
+        }
+    }
+
+    pub fn to_sql_literal(&self) -> String {
+        match self {
+            Self::Null => "NULL".to_owned(),
+            Self::Bool(v) => v.to_string().to_uppercase(),
+            Self::Int(_) => self.to_string(),
+            Self::Float(v) if v.is_finite() => self.to_string(),
+            Self::Numeric(v)
+                if v.chars()
+                    .all(|c| c.is_ascii_digit() || c == '-' || c == '.') =>
+            {
+                v.to_owned()
+            }
+            _ => super::quote_literal(&self.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Null => write!(f, "NULL"),
+            Self::Bool(v) => write!(f, "{v}"),
+            Self::Int(v) => write!(f, "{v}"),
+            Self::Float(v) => write!(f, "{v}"),
+            Self::Numeric(v) | Self::Text(v) => write!(f, "{v}"),
+            Self::Json(v) => write!(f, "{v}"),
+            Self::Bytes(v) => {
+                write!(f, "\\x")?;
+                v.iter().try_for_each(|b| write!(f, "{b:02x}"))
+            }
+            Self::Date(v) => write!(f, "{}", v.format("%Y-%m-%d")),
+            Self::Time(v) => write!(f, "{}", v.format("%H:%M:%S%.f")),
+            Self::Timestamp(v) => write!(f, "{}", v.format("%Y-%m-%d %H:%M:%S%.f")),
+            Self::TimestampTz(v) => write!(f, "{}", v.format("%Y-%m-%d %H:%M:%S%.f%:z")),
+            Self::Array(values) => {
+                write!(f, "{{")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, "}}")
+            }
+            Self::Unsupported(type_name) => write!(f, "<{type_name}>"),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> FromSql<'a> for Value {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> FromSqlResult<Self> {
+        let value = match *ty {
+            Type::BOOL => Self::Bool(bool::from_sql(ty, raw)?),
+            Type::CHAR => Self::Text((i8::from_sql(ty, raw)? as u8 as char).to_string()),
+            Type::INT2 => Self::Int(i16::from_sql(ty, raw)?.into()),
+            Type::INT4 => Self::Int(i32::from_sql(ty, raw)?.into()),
+            Type::INT8 => Self::Int(i64::from_sql(ty, raw)?),
+            Type::OID => Self::Int(u32::from_sql(ty, raw)?.into()),
+            Type::FLOAT4 => Self::Float(f32::from_sql(ty, raw)?.into()),
+            Type::FLOAT8 => Self::Float(f64::from_sql(ty, raw)?),
+            Type::NUMERIC => Self::Numeric(numeric_to_string(raw)?),
+            Type::JSON | Type::JSONB => Self::Json(serde_json::Value::from_sql(ty, raw)?),
+            Type::BYTEA => Self::Bytes(raw.to_vec()),
+            Type::UUID => Self::Text(uuid_to_string(raw)?),
+            Type::INTERVAL => Self::Text(interval_to_string(raw)?),
+            Type::DATE => Self::Date(NaiveDate::from_sql(ty, raw)?),
+            Type::TIME => Self::Time(NaiveTime::from_sql(ty, raw)?),
+            Type::TIMESTAMP => Self::Timestamp(NaiveDateTime::from_sql(ty, raw)?),
+            Type::TIMESTAMPTZ => Self::TimestampTz(DateTime::<Utc>::from_sql(ty, raw)?),
+            Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME | Type::UNKNOWN => {
+                Self::Text(String::from_sql(ty, raw)?)
+            }
+            _ => match ty.kind() {
+                Kind::Array(_) => Self::Array(Vec::<Self>::from_sql(ty, raw)?),
+                Kind::Domain(inner) => Self::from_sql(inner, raw)?,
+                Kind::Enum(_) => Self::Text(String::from_utf8_lossy(raw).into_owned()),
+                _ => Self::Unsupported(ty.name().to_owned()),
+            },
+        };
+
+        Ok(value)
+    }
+
+    fn from_sql_null(_: &Type) -> FromSqlResult<Self> {
+        Ok(Self::Null)
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+}
+
+const NUMERIC_NEG: u16 = 0x4000;
+const NUMERIC_NAN: u16 = 0xC000;
+const NUMERIC_PINF: u16 = 0xD000;
+const NUMERIC_NINF: u16 = 0xF000;
+
+fn numeric_to_string(raw: &[u8]) -> FromSqlResult<String> {
+    let read_u16 = |i: usize| -> FromSqlResult<u16> {
+        let bytes = raw.get(i..i + 2).ok_or("invalid numeric")?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    };
+
+    let ndigits = read_u16(0)? as usize;
+    let weight = read_u16(2)? as i16 as isize;
+    let sign = read_u16(4)?;
+    let dscale = read_u16(6)? as usize;
+
+    match sign {
+        NUMERIC_NAN => return Ok("NaN".to_owned()),
+        NUMERIC_PINF => return Ok("Infinity".to_owned()),
+        NUMERIC_NINF => return Ok("-Infinity".to_owned()),
+        _ => (),
+    }
+
+    let digits = (0..ndigits)
+        .map(|i| read_u16(8 + i * 2))
+        .collect::<FromSqlResult<Vec<_>>>()?;
+
+    // @note: digit at index `i` has the base-10000 position `weight - i`
+    let digit = |pos: isize| -> u16 {
+        usize::try_from(weight - pos)
+            .ok()
+            .and_then(|i| digits.get(i).copied())
+            .unwrap_or(0)
+    };
+
+    let mut out = String::new();
+    if sign == NUMERIC_NEG {
+        out.push('-');
+    }
+
+    if weight < 0 {
+        out.push('0');
+    } else {
+        write!(out, "{}", digit(weight))?;
+        for pos in (0..weight).rev() {
+            write!(out, "{:04}", digit(pos))?;
+        }
+    }
+
+    if dscale > 0 {
+        let mut fraction = String::new();
+        let groups = dscale.div_ceil(4) as isize;
+
+        for pos in 1..=groups {
+            write!(fraction, "{:04}", digit(-pos))?;
+        }
+
+        fraction.truncate(dscale);
+        write!(out, ".{fraction}")?;
+    }
+
+    Ok(out)
+}
+
+fn uuid_to_string(raw: &[u8]) -> FromSqlResult<String> {
+    if raw.len() != 16 {
+        return Err("invalid uuid".into());
+    }
+
+    let mut out = String::with_capacity(36);
+    for (i, b) in raw.iter().enumerate() {
+        if matches!(i, 4 | 6 | 8 | 10) {
+            out.push('-');
+        }
+        write!(out, "{b:02x}")?;
+    }
+
+    Ok(out)
+}
+
+fn interval_to_string(raw: &[u8]) -> FromSqlResult<String> {
+    let raw: &[u8; 16] = raw.try_into().map_err(|_| "invalid interval")?;
+
+    let micros = i64::from_be_bytes(raw[0..8].try_into()?);
+    let days = i32::from_be_bytes(raw[8..12].try_into()?);
+    let months = i32::from_be_bytes(raw[12..16].try_into()?);
+
+    let plural = |n: i32, unit: &str| {
+        let suffix = if n.abs() == 1 { "" } else { "s" };
+        format!("{n} {unit}{suffix}")
+    };
+
+    let mut parts = vec![];
+
+    if months / 12 != 0 {
+        parts.push(plural(months / 12, "year"));
+    }
+    if months % 12 != 0 {
+        parts.push(plural(months % 12, "mon"));
+    }
+    if days != 0 {
+        parts.push(plural(days, "day"));
+    }
+
+    if micros != 0 || parts.is_empty() {
+        let sign = if micros < 0 { "-" } else { "" };
+        let micros = micros.unsigned_abs();
+        let secs = micros / 1_000_000;
+
+        let mut time = format!(
+            "{sign}{:02}:{:02}:{:02}",
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60
+        );
+
+        let fraction = micros % 1_000_000;
+        if fraction != 0 {
+            let fraction = format!("{fraction:06}");
+            write!(time, ".{}", fraction.trim_end_matches('0'))?;
+        }
+
+        parts.push(time);
+    }
+
+    Ok(parts.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numeric(weight: i16, sign: u16, dscale: u16, digits: &[u16]) -> Vec<u8> {
+        let mut raw = vec![];
+        raw.extend((digits.len() as u16).to_be_bytes());
+        raw.extend(weight.to_be_bytes());
+        raw.extend(sign.to_be_bytes());
+        raw.extend(dscale.to_be_bytes());
+        digits.iter().for_each(|d| raw.extend(d.to_be_bytes()));
+        raw
+    }
+
+    #[test]
+    fn test_numeric_to_string() -> FromSqlResult<()> {
+        assert_eq!(
+            numeric_to_string(&numeric(1, 0, 2, &[12, 3456, 7800]))?,
+            "123456.78"
+        );
+        assert_eq!(
+            numeric_to_string(&numeric(-2, NUMERIC_NEG, 6, &[1200]))?,
+            "-0.000012"
+        );
+        assert_eq!(numeric_to_string(&numeric(2, 0, 0, &[1]))?, "100000000");
+        assert_eq!(numeric_to_string(&numeric(0, 0, 0, &[]))?, "0");
+        assert_eq!(numeric_to_string(&numeric(0, NUMERIC_NAN, 0, &[]))?, "NaN");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_interval_to_string() -> FromSqlResult<()> {
+        let interval = |micros: i64, days: i32, months: i32| {
+            let mut raw = vec![];
+            raw.extend(micros.to_be_bytes());
+            raw.extend(days.to_be_bytes());
+            raw.extend(months.to_be_bytes());
+            raw
+        };
+
+        assert_eq!(interval_to_string(&interval(0, 0, 0))?, "00:00:00");
+        assert_eq!(
+            interval_to_string(&interval(3_723_500_000, 1, 14))?,
+            "1 year 2 mons 1 day 01:02:03.5"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_sql_literal() {
+        assert_eq!(Value::Null.to_sql_literal(), "NULL");
+        assert_eq!(Value::Bool(true).to_sql_literal(), "TRUE");
+        assert_eq!(Value::Float(f64::NAN).to_sql_literal(), "'NaN'");
+        assert_eq!(Value::Numeric("-1.50".to_owned()).to_sql_literal(), "-1.50");
+        assert_eq!(Value::Text("it's".to_owned()).to_sql_literal(), "'it''s'");
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Value::Bytes(vec![0xde, 0xad]).to_string(), "\\xdead");
+        assert_eq!(
+            Value::Array(vec![Value::Int(1), Value::Null]).to_string(),
+            "{1,NULL}"
+        );
+    }
+}
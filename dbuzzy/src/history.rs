@@ -0,0 +1,176 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::Context;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::db::QueryResult;
+
+// @note: older entries are dropped, when the file is loaded
+const MAX_ENTRIES: usize = 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub sql: String,
+    pub conn: String,
+    pub at: DateTime<Local>,
+    pub elapsed_ms: u64,
+    // @note: fetched or affected rows, `None` on error
+    pub rows: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl HistoryEntry {
+    pub fn new(
+        sql: &str,
+        conn: String,
+        elapsed: Duration,
+        result: &anyhow::Result<QueryResult>,
+    ) -> Self {
+        let rows = result
+            .as_ref()
+            .map(|result| result.affected.unwrap_or(result.rows.len() as u64));
+
+        Self::with_rows(sql, conn, elapsed, rows)
+    }
+
+    // @note: for the statements that don't come with a `QueryResult`
+    pub fn with_rows(
+        sql: &str,
+        conn: String,
+        elapsed: Duration,
+        rows: Result<u64, &anyhow::Error>,
+    ) -> Self {
+        let (rows, error) = match rows {
+            Ok(rows) => (Some(rows), None),
+            Err(e) => (None, Some(format!("{e:#}"))),
+        };
+
+        Self {
+            sql: sql.to_owned(),
+            conn,
+            at: Local::now(),
+            elapsed_ms: elapsed.as_millis() as u64,
+            rows,
+            error,
+        }
+    }
+}
+
+// @note: one json object per line, so a run appends without rewriting the file
+pub struct History {
+    path: PathBuf,
+    entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    pub fn load() -> anyhow::Result<Self> {
+        let mut path = duzzy_lib::ensure_config_dir(std::env!("CARGO_PKG_NAME"))?;
+        path.push("history");
+        path.set_extension("jsonl");
+
+        Self::open(path)
+    }
+
+    fn open(path: PathBuf) -> anyhow::Result<Self> {
+        let content = match std::fs::read_to_string(&path) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            content => content.with_context(|| format!("failed to read {}", path.display()))?,
+        };
+
+        // @note: a line cut by a crash is skipped
+        let mut entries = content
+            .lines()
+            .filter_map(|line| serde_json::from_str::<HistoryEntry>(line).ok())
+            .collect::<Vec<_>>();
+
+        if entries.len() > MAX_ENTRIES {
+            entries.drain(..entries.len() - MAX_ENTRIES);
+            rewrite(&path, &entries)?;
+        }
+
+        Ok(Self { path, entries })
+    }
+
+    // @note: oldest first
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    pub fn push(&mut self, entry: HistoryEntry) -> anyhow::Result<()> {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+
+        self.entries.push(entry);
+        Ok(())
+    }
+}
+
+fn rewrite(path: &Path, entries: &[HistoryEntry]) -> anyhow::Result<()> {
+    let mut content = String::new();
+
+    for entry in entries {
+        content.push_str(&serde_json::to_string(entry)?);
+        content.push('\n');
+    }
+
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history() -> anyhow::Result<()> {
+        let mut path = std::env::temp_dir();
+        path.push(format!("dbuzzy-history-{}.jsonl", std::process::id()));
+
+        let mut history = History::open(path.clone())?;
+        assert!(history.entries().is_empty());
+
+        let failed = Err(anyhow::anyhow!("syntax error"));
+        history.push(HistoryEntry::new(
+            "selec 1",
+            "local".to_owned(),
+            Duration::from_millis(3),
+            &failed,
+        ))?;
+
+        let mut content = std::fs::read_to_string(&path)?;
+        content.push_str("{\"sql\": \"cut\n");
+
+        for i in 0..MAX_ENTRIES {
+            let mut entry = history.entries()[0].clone();
+            entry.sql = format!("select {i}");
+            entry.error = None;
+            entry.rows = Some(1);
+            content.push_str(&serde_json::to_string(&entry)?);
+            content.push('\n');
+        }
+
+        std::fs::write(&path, content)?;
+
+        let history = History::open(path.clone())?;
+        assert_eq!(history.entries().len(), MAX_ENTRIES);
+        assert_eq!(history.entries()[0].sql, "select 0");
+        assert_eq!(history.entries()[0].conn, "local");
+        assert_eq!(history.entries()[0].elapsed_ms, 3);
+
+        let history = History::open(path.clone())?;
+        assert_eq!(history.entries().len(), MAX_ENTRIES);
+
+        std::fs::remove_file(path)?;
+
+        Ok(())
+    }
+}
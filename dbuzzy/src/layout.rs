@@ -0,0 +1,194 @@
+use ratatui::layout::{Constraint, Layout, Rect};
+
+use crate::widgets::AppWidgetName;
+
+const MIN_SIZE: u16 = 10;
+const MAX_SIZE: u16 = 90;
+const STEP: u16 = 5;
+// @note: borders, the status line of the editor and a line of text
+const MIN_ROWS: u16 = 4;
+
+// @note: splits of a connection tab, the sizes are percents of the screen
+pub struct PaneLayout {
+    tree: u16,
+    editor: u16,
+    zoomed: bool,
+}
+
+impl Default for PaneLayout {
+    fn default() -> Self {
+        Self {
+            tree: 25,
+            editor: 60,
+            zoomed: false,
+        }
+    }
+}
+
+impl PaneLayout {
+    pub const fn resize_tree(&mut self, grow: bool) {
+        self.tree = resize(self.tree, grow);
+    }
+
+    pub const fn resize_editor(&mut self, grow: bool) {
+        self.editor = resize(self.editor, grow);
+    }
+
+    pub const fn toggle_zoom(&mut self) {
+        self.zoomed = !self.zoomed;
+    }
+
+    // @note: the tree on the left, the editor over the results on the right,
+    // a table, an import or a plan takes the whole right side while focused
+    pub fn split(&self, area: Rect, focus: AppWidgetName) -> Vec<(AppWidgetName, Rect)> {
+        if self.zoomed {
+            return vec![(focus, area)];
+        }
+
+        let [tree_area, right_area] =
+            Layout::horizontal([Constraint::Percentage(self.tree), Constraint::Min(0)]).areas(area);
+
+        let left = match focus {
+            AppWidgetName::Snippets => AppWidgetName::Snippets,
+            _ => AppWidgetName::DatabaseTree,
+        };
+
+        let mut panes = vec![(left, tree_area)];
+
+        match focus {
+            AppWidgetName::TableData | AppWidgetName::Import | AppWidgetName::Plan => {
+                panes.push((focus, right_area));
+            }
+            _ => {
+                let [editor_area, results_area] = Layout::vertical([
+                    Constraint::Length(self.editor_rows(right_area.height)),
+                    Constraint::Min(0),
+                ])
+                .areas(right_area);
+
+                panes.push((AppWidgetName::Editor, editor_area));
+                panes.push((AppWidgetName::ResultGrid, results_area));
+            }
+        }
+
+        panes
+    }
+
+    // @note: the editor and the results keep `MIN_ROWS` each, as far as they fit
+    fn editor_rows(&self, height: u16) -> u16 {
+        let rows = (u32::from(height) * u32::from(self.editor) + 50) / 100;
+        let rows = u16::try_from(rows).unwrap_or(height);
+
+        rows.min(height.saturating_sub(MIN_ROWS))
+            .max(MIN_ROWS)
+            .min(height)
+    }
+}
+
+// @note: the pane after `focus` for Tab and Ctrl-w, `panes` are the open ones in order
+pub fn next_pane(focus: AppWidgetName, panes: &[AppWidgetName]) -> AppWidgetName {
+    let next = panes
+        .iter()
+        .position(|pane| *pane == focus)
+        .map_or(0, |i| i + 1);
+
+    panes
+        .get(next % panes.len().max(1))
+        .copied()
+        .unwrap_or(focus)
+}
+
+const fn resize(size: u16, grow: bool) -> u16 {
+    if grow {
+        if size + STEP > MAX_SIZE {
+            MAX_SIZE
+        } else {
+            size + STEP
+        }
+    } else if size < MIN_SIZE + STEP {
+        MIN_SIZE
+    } else {
+        size - STEP
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split() {
+        let area = Rect::new(0, 0, 100, 50);
+        let mut layout = PaneLayout::default();
+
+        let panes = layout.split(area, AppWidgetName::Editor);
+        assert_eq!(panes.len(), 3);
+        assert!(panes[0].0 == AppWidgetName::DatabaseTree);
+        assert_eq!(panes[0].1, Rect::new(0, 0, 25, 50));
+        assert_eq!(panes[1].1, Rect::new(25, 0, 75, 30));
+        assert_eq!(panes[2].1, Rect::new(25, 30, 75, 20));
+
+        let panes = layout.split(area, AppWidgetName::Snippets);
+        assert!(panes[0].0 == AppWidgetName::Snippets);
+
+        let panes = layout.split(area, AppWidgetName::TableData);
+        assert_eq!(panes.len(), 2);
+        assert!(panes[1].0 == AppWidgetName::TableData);
+        assert_eq!(panes[1].1, Rect::new(25, 0, 75, 50));
+
+        for _ in 0..20 {
+            layout.resize_tree(false);
+        }
+        layout.resize_editor(true);
+
+        let panes = layout.split(area, AppWidgetName::Editor);
+        assert_eq!(panes[0].1.width, MIN_SIZE);
+        assert_eq!(panes[1].1.height, 33);
+
+        layout.toggle_zoom();
+        let panes = layout.split(area, AppWidgetName::ResultGrid);
+        assert_eq!(panes.len(), 1);
+        assert_eq!(panes[0].1, area);
+    }
+
+    #[test]
+    fn test_split_small() {
+        let mut layout = PaneLayout::default();
+
+        for _ in 0..20 {
+            layout.resize_editor(false);
+        }
+
+        // @note: 24 rows without the tab bar
+        let panes = layout.split(Rect::new(0, 0, 80, 23), AppWidgetName::Editor);
+        assert_eq!(panes[1].1.height, MIN_ROWS);
+        assert_eq!(panes[2].1.height, 19);
+
+        for _ in 0..20 {
+            layout.resize_editor(true);
+        }
+
+        let panes = layout.split(Rect::new(0, 0, 80, 23), AppWidgetName::Editor);
+        assert_eq!(panes[1].1.height, 19);
+        assert_eq!(panes[2].1.height, MIN_ROWS);
+
+        let panes = layout.split(Rect::new(0, 0, 80, 3), AppWidgetName::Editor);
+        assert_eq!(panes[1].1.height, 3);
+        assert_eq!(panes[2].1.height, 0);
+    }
+
+    #[test]
+    fn test_next_pane() {
+        let panes = [
+            AppWidgetName::DatabaseTree,
+            AppWidgetName::Editor,
+            AppWidgetName::ResultGrid,
+            AppWidgetName::Plan,
+        ];
+
+        assert!(next_pane(AppWidgetName::Editor, &panes) == AppWidgetName::ResultGrid);
+        assert!(next_pane(AppWidgetName::ResultGrid, &panes) == AppWidgetName::Plan);
+        assert!(next_pane(AppWidgetName::Plan, &panes) == AppWidgetName::DatabaseTree);
+        assert!(next_pane(AppWidgetName::Snippets, &panes) == AppWidgetName::DatabaseTree);
+    }
+}
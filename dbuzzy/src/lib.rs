@@ -8,7 +8,11 @@
 mod app;
 mod config;
 pub mod db;
+mod history;
+mod layout;
+mod snippets;
 mod widgets;
 
 pub use app::App;
 pub use config::Config;
+pub use history::History;
@@ -0,0 +1,198 @@
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+const TAGS_PREFIX: &str = "-- tags:";
+
+// @note: `snippets/<folders>/<name>.sql` are global,
+// `snippets/@<connection>/<folders>/<name>.sql` belong to a connection
+pub struct Snippets {
+    root: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snippet {
+    // @note: folders and the file name without `.sql`, like `reports/daily`
+    pub name: String,
+    // @note: `None` for a global snippet
+    pub conn: Option<String>,
+    pub tags: Vec<String>,
+    pub sql: String,
+    pub path: PathBuf,
+}
+
+impl Snippets {
+    pub fn open() -> anyhow::Result<Self> {
+        let mut root = duzzy_lib::ensure_config_dir(std::env!("CARGO_PKG_NAME"))?;
+        root.push("snippets");
+        Ok(Self { root })
+    }
+
+    // @note: snippets of the connection first, then the global ones
+    pub fn load(&self, conn: &str) -> anyhow::Result<Vec<Snippet>> {
+        let mut snippets = vec![];
+
+        let conn_dir = self.root.join(conn_dir(conn));
+        read_dir(&conn_dir, &conn_dir, Some(conn), &mut snippets)?;
+        read_dir(&self.root, &self.root, None, &mut snippets)?;
+
+        Ok(snippets)
+    }
+
+    pub fn save(
+        &self,
+        conn: Option<&str>,
+        name: &str,
+        tags: &[String],
+        sql: &str,
+    ) -> anyhow::Result<PathBuf> {
+        let valid = name
+            .split('/')
+            .all(|part| !part.is_empty() && part != "." && part != ".." && !part.starts_with('@'));
+        anyhow::ensure!(valid, "invalid snippet name: {name}");
+
+        let mut path = match conn {
+            Some(conn) => self.root.join(conn_dir(conn)),
+            None => self.root.clone(),
+        };
+        path.push(format!("{name}.sql"));
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let mut content = String::new();
+        if !tags.is_empty() {
+            content = format!("{TAGS_PREFIX} {}\n", tags.join(", "));
+        }
+        content.push_str(sql.trim_end());
+        content.push('\n');
+
+        std::fs::write(&path, content)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+
+        Ok(path)
+    }
+}
+
+// @note: the connection name as a folder name
+fn conn_dir(conn: &str) -> String {
+    let name = conn
+        .chars()
+        .map(|ch| match ch.is_alphanumeric() || "-_.".contains(ch) {
+            true => ch,
+            false => '_',
+        })
+        .collect::<String>();
+
+    format!("@{name}")
+}
+
+fn read_dir(
+    root: &Path,
+    dir: &Path,
+    conn: Option<&str>,
+    snippets: &mut Vec<Snippet>,
+) -> anyhow::Result<()> {
+    let entries = match std::fs::read_dir(dir) {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        entries => entries.with_context(|| format!("failed to read {}", dir.display()))?,
+    };
+
+    let mut paths = entries
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.sort();
+
+    for path in paths {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+
+        if path.is_dir() {
+            // @note: folders of the connections are read separately
+            if !file_name.starts_with('@') {
+                read_dir(root, &path, conn, snippets)?;
+            }
+            continue;
+        }
+
+        if path.extension() != Some(OsStr::new("sql")) {
+            continue;
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+
+        let (tags, sql) = match content.strip_prefix(TAGS_PREFIX) {
+            Some(rest) => {
+                let (tags, sql) = rest.split_once('\n').unwrap_or((rest, ""));
+                let tags = tags
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|tag| !tag.is_empty())
+                    .map(ToOwned::to_owned)
+                    .collect();
+                (tags, sql.to_owned())
+            }
+            None => (vec![], content),
+        };
+
+        let name = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .with_extension("")
+            .to_string_lossy()
+            .into_owned();
+
+        snippets.push(Snippet {
+            name,
+            conn: conn.map(ToOwned::to_owned),
+            tags,
+            sql,
+            path,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snippets() -> anyhow::Result<()> {
+        let mut root = std::env::temp_dir();
+        root.push(format!("dbuzzy-snippets-{}", std::process::id()));
+
+        let snippets = Snippets { root: root.clone() };
+        assert!(snippets.load("local")?.is_empty());
+
+        let tags = vec!["report".to_owned(), "daily".to_owned()];
+        snippets.save(None, "reports/daily", &tags, "select :day\n\n")?;
+        snippets.save(Some("local/db"), "cleanup", &[], "delete from t")?;
+        snippets.save(Some("other"), "hidden", &[], "select 1")?;
+        assert!(snippets.save(None, "../escape", &[], "select 1").is_err());
+        assert!(snippets.save(None, "@other/x", &[], "select 1").is_err());
+
+        let loaded = snippets.load("local/db")?;
+        assert_eq!(loaded.len(), 2);
+
+        assert_eq!(loaded[0].name, "cleanup");
+        assert_eq!(loaded[0].conn.as_deref(), Some("local/db"));
+        assert!(loaded[0].tags.is_empty());
+        assert_eq!(loaded[0].sql, "delete from t\n");
+
+        assert_eq!(loaded[1].name, "reports/daily");
+        assert_eq!(loaded[1].conn, None);
+        assert_eq!(loaded[1].tags, tags);
+        assert_eq!(loaded[1].sql, "select :day\n");
+        assert!(loaded[1].path.ends_with("reports/daily.sql"));
+
+        std::fs::remove_dir_all(root)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use duzzy_lib::{
+    colors,
+    event::{Event, Input},
+    DuzzyWidget, EventOutcome,
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::Stylize,
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
+};
+
+use super::{AppEventOutcome, AppWidgetData, AppWidgetName};
+
+const WIDTH: u16 = 60;
+
+// @note: drawn over everything like an error, `y` applies the data
+pub struct ConfirmWidget {
+    message: String,
+    data: Option<AppWidgetData>,
+    focus: AppWidgetName,
+}
+
+impl ConfirmWidget {
+    pub fn new(message: impl Into<String>, data: AppWidgetData, focus: AppWidgetName) -> Self {
+        Self {
+            message: message.into(),
+            data: Some(data),
+            focus,
+        }
+    }
+}
+
+impl DuzzyWidget for ConfirmWidget {
+    type Outcome = AppEventOutcome;
+
+    fn input(&mut self, input: Input) -> Self::Outcome {
+        match input.event {
+            Event::Char('y') => match self.data.take() {
+                Some(data) => AppEventOutcome::Apply(data),
+                None => AppEventOutcome::Focus(self.focus),
+            },
+            Event::Esc | Event::Char('n' | 'q') => AppEventOutcome::Focus(self.focus),
+            _ => EventOutcome::Ignore.into(),
+        }
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        // @note: a rough height, long lines are wrapped
+        let width = WIDTH.min(area.width).saturating_sub(2).max(1) as usize;
+        let height = (self.message.chars().count() / width + 3) as u16;
+
+        let [area] = Layout::horizontal([Constraint::Length(WIDTH)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(area);
+
+        let block = Block::default()
+            .title("Confirm")
+            .title_bottom("y to confirm, n or Esc to cancel")
+            .borders(Borders::ALL)
+            .fg(colors::ENERGY_YELLOW);
+
+        Clear.render(area, buf);
+
+        Paragraph::new(Line::from(self.message.as_str()).red())
+            .wrap(Wrap { trim: false })
+            .block(block)
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,272 @@
+use duzzy_lib::{
+    colors,
+    event::{Event, Input, Modifiers},
+    DuzzyWidget, EventOutcome,
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use super::{text_input::TextInput, AppEventOutcome, AppWidgetData, AppWidgetName};
+use crate::db::{self, ConnectionConfig, DriverKind, ServerInfo, SslMode};
+
+// @note: option names of `config.toml`, the other options of the connection are kept
+const FIELDS: [&str; 10] = [
+    "name", "color", "url", "driver", "host", "port", "dbname", "user", "path", "sslmode",
+];
+
+const LABEL_WIDTH: u16 = 10;
+
+enum FormStatus {
+    Testing,
+    Tested(ServerInfo),
+    Error(String),
+}
+
+pub struct ConnFormWidget {
+    // @note: `None` for a new connection
+    index: Option<usize>,
+    config: Box<ConnectionConfig>,
+    inputs: Vec<TextInput>,
+    selected: usize,
+    status: Option<FormStatus>,
+}
+
+impl ConnFormWidget {
+    pub fn new(index: Option<usize>, config: ConnectionConfig) -> Self {
+        let inputs = FIELDS
+            .iter()
+            .map(|key| TextInput::new(&field_value(&config, key)))
+            .collect();
+
+        Self {
+            index,
+            config: Box::new(config),
+            inputs,
+            selected: 0,
+            status: None,
+        }
+    }
+
+    pub fn set_testing(&mut self) {
+        self.status = Some(FormStatus::Testing);
+    }
+
+    pub fn set_tested(&mut self, info: anyhow::Result<ServerInfo>) {
+        self.status = Some(match info {
+            Ok(info) => FormStatus::Tested(info),
+            Err(e) => FormStatus::Error(format!("{e:#}")),
+        });
+    }
+
+    pub fn set_error(&mut self, e: impl std::fmt::Display) {
+        self.status = Some(FormStatus::Error(e.to_string()));
+    }
+
+    // @note: the edited connection, with the fields of the form replaced
+    fn config(&self) -> anyhow::Result<ConnectionConfig> {
+        let mut config = ConnectionConfig {
+            name: None,
+            color: None,
+            url: None,
+            driver: DriverKind::default(),
+            host: String::new(),
+            port: None,
+            dbname: None,
+            user: String::new(),
+            path: None,
+            sslmode: SslMode::default(),
+            ..(*self.config).clone()
+        };
+
+        for (key, input) in FIELDS.iter().zip(&self.inputs) {
+            let value = input.value().trim().to_owned();
+
+            if value.is_empty() {
+                continue;
+            }
+
+            match *key {
+                "name" => config.name = Some(value),
+                "color" => config.color = Some(value),
+                "url" => config.url = Some(value),
+                key => db::set_option(&mut config, key, value)?,
+            }
+        }
+
+        // @note: fails early on a bad url
+        config.clone().resolve()?;
+
+        Ok(config)
+    }
+
+    fn apply(&mut self, data: impl FnOnce(ConnectionConfig) -> AppWidgetData) -> AppEventOutcome {
+        match self.config() {
+            Ok(config) => AppEventOutcome::Apply(data(config)),
+            Err(e) => {
+                self.set_error(format!("{e:#}"));
+                EventOutcome::Render.into()
+            }
+        }
+    }
+}
+
+impl DuzzyWidget for ConnFormWidget {
+    type Outcome = AppEventOutcome;
+
+    fn input(&mut self, input: Input) -> Self::Outcome {
+        match input {
+            Input {
+                event: Event::Char('t'),
+                modifiers: Modifiers { ctr: true, .. },
+            } => self.apply(|config| AppWidgetData::TestConnection(Box::new(config))),
+            Input { event, .. } => match event {
+                Event::Enter => {
+                    let index = self.index;
+                    self.apply(|config| AppWidgetData::SaveConnection(index, Box::new(config)))
+                }
+                Event::Esc => AppEventOutcome::Focus(AppWidgetName::ConnectionList),
+                Event::Down | Event::Tab => {
+                    self.selected = (self.selected + 1) % FIELDS.len();
+                    EventOutcome::Render.into()
+                }
+                Event::Up => {
+                    self.selected = self.selected.checked_sub(1).unwrap_or(FIELDS.len() - 1);
+                    EventOutcome::Render.into()
+                }
+                _ => self.inputs[self.selected].input(input).into(),
+            },
+        }
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let [area] = Layout::horizontal([Constraint::Length(70)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [area] = Layout::vertical([Constraint::Length(FIELDS.len() as u16 + 4)])
+            .flex(Flex::Center)
+            .areas(area);
+
+        let title = match self.index {
+            Some(_) => format!("Edit {}", self.config),
+            None => "New connection".to_owned(),
+        };
+
+        let block = Block::default()
+            .title(title)
+            .title_bottom("Enter to save, Ctrl-t to test, Esc to cancel")
+            .borders(Borders::ALL)
+            .fg(colors::ENERGY_YELLOW);
+
+        let inner = block.inner(area);
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        let [fields_area, _, status_area] = Layout::vertical([
+            Constraint::Length(FIELDS.len() as u16),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
+        let rows = Layout::vertical([Constraint::Length(1); FIELDS.len()]).split(fields_area);
+
+        for (i, (key, input)) in FIELDS.iter().zip(self.inputs.iter_mut()).enumerate() {
+            let [label_area, input_area] =
+                Layout::horizontal([Constraint::Length(LABEL_WIDTH), Constraint::Min(0)])
+                    .areas(rows[i]);
+
+            let label = Line::styled(*key, colors::LIGHT_GOLDENROD_YELLOW);
+
+            if i == self.selected {
+                label.bold().render(label_area, buf);
+                input.render(input_area, buf);
+            } else {
+                label.render(label_area, buf);
+                buf.set_stringn(
+                    input_area.x,
+                    input_area.y,
+                    input.value(),
+                    input_area.width as usize,
+                    Style::default(),
+                );
+            }
+        }
+
+        let status = match self.status.as_ref() {
+            Some(FormStatus::Testing) => Line::from("testing..."),
+            Some(FormStatus::Tested(info)) => Line::from(format!(
+                "connected in {:.1?}: {}",
+                info.latency, info.version
+            ))
+            .green(),
+            Some(FormStatus::Error(e)) => Line::from(e.as_str()).red(),
+            None => return,
+        };
+
+        status.render(status_area, buf);
+    }
+}
+
+fn field_value(config: &ConnectionConfig, key: &str) -> String {
+    match key {
+        "name" => config.name.clone().unwrap_or_default(),
+        "color" => config.color.clone().unwrap_or_default(),
+        "url" => config.url.clone().unwrap_or_default(),
+        "driver" => config.driver.scheme().to_owned(),
+        "host" => config.host.clone(),
+        "port" => config.port.map(|port| port.to_string()).unwrap_or_default(),
+        "dbname" => config.dbname.clone().unwrap_or_default(),
+        "user" => config.user.clone(),
+        "path" => config
+            .path
+            .as_ref()
+            .map(|path| path.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        "sslmode" => config.sslmode.as_str().to_owned(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_form_config() -> anyhow::Result<()> {
+        let config = ConnectionConfig {
+            name: Some("foo".to_owned()),
+            host: "localhost".to_owned(),
+            port: Some(5432),
+            password_env: Some("FOO_PASSWORD".to_owned()),
+            ..Default::default()
+        };
+
+        let mut form = ConnFormWidget::new(Some(0), config);
+
+        // @note: clear the port, then type a database name
+        form.inputs[5] = TextInput::default();
+        form.inputs[6] = TextInput::new("bar");
+
+        let config = form.config()?;
+        assert_eq!(config.name.as_deref(), Some("foo"));
+        assert_eq!(config.port, None);
+        assert_eq!(config.dbname.as_deref(), Some("bar"));
+        assert_eq!(config.sslmode, SslMode::Prefer);
+        assert_eq!(config.password_env.as_deref(), Some("FOO_PASSWORD"));
+
+        form.inputs[9] = TextInput::new("sometimes");
+        assert!(form.config().is_err());
+
+        form.inputs[9] = TextInput::default();
+        form.inputs[2] = TextInput::new("postgres://foo@bar:port");
+        assert!(form.config().is_err());
+
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Instant;
+
 use duzzy_lib::{
     colors,
     event::{Event, Input},
@@ -11,29 +13,43 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, StatefulWidget, Widget},
 };
 
-use crate::db::{ConnectionConfig, PgPool};
+use crate::db::ConnectionConfig;
+
+use super::spinner;
 
 pub struct ConnListWidget {
     state: ListState,
-    configs: &'static [ConnectionConfig],
-    pool: Option<PgPool>,
+    configs: Vec<ConnectionConfig>,
+    // @note: waits for `y` to delete the selected connection
+    confirm_delete: bool,
+    // @note: the connection being opened in the background
+    connecting: Option<(String, Instant)>,
 }
 
 impl ConnListWidget {
-    pub fn new(conns: &'static [ConnectionConfig]) -> Self {
+    pub fn new(conns: Vec<ConnectionConfig>, selected: usize) -> Self {
         let mut state = ListState::default();
 
         if !conns.is_empty() {
-            state.select(Some(0));
+            state.select(Some(selected.min(conns.len() - 1)));
         }
 
         Self {
             configs: conns,
             state,
-            pool: None,
+            confirm_delete: false,
+            connecting: None,
         }
     }
 
+    pub const fn connecting(&self) -> Option<&(String, Instant)> {
+        self.connecting.as_ref()
+    }
+
+    pub fn set_connecting(&mut self, connecting: Option<(String, Instant)>) {
+        self.connecting = connecting;
+    }
+
     pub fn next_connection(&mut self) {
         let i = self.state.selected().map(|i| {
             if i >= self.configs.len() - 1 {
@@ -58,14 +74,8 @@ impl ConnListWidget {
         self.state.select(i);
     }
 
-    pub fn select_connection(&mut self) {
-        if let Some(config) = self.state.selected().and_then(|i| self.configs.get(i)) {
-            self.pool = PgPool::create(config).ok();
-        };
-    }
-
-    pub const fn pool(&self) -> Option<&PgPool> {
-        self.pool.as_ref()
+    pub fn selected_connection(&self) -> Option<&ConnectionConfig> {
+        self.state.selected().and_then(|i| self.configs.get(i))
     }
 }
 
@@ -75,16 +85,37 @@ impl DuzzyWidget for ConnListWidget {
     fn input(&mut self, input: Input) -> Self::Outcome {
         let mut outcome = EventOutcome::Render;
 
+        if std::mem::take(&mut self.confirm_delete) {
+            if let (Event::Char('y'), Some(i)) = (input.event, self.state.selected()) {
+                return super::AppEventOutcome::Apply(super::AppWidgetData::DeleteConnection(i));
+            }
+
+            return outcome.into();
+        }
+
         match input.event {
+            Event::Esc if self.connecting.is_some() => {
+                return super::AppEventOutcome::Apply(super::AppWidgetData::CancelConnect);
+            }
             Event::Char('q') | Event::Esc => outcome = EventOutcome::Exit,
             Event::Char('j') | Event::Down => self.next_connection(),
             Event::Char('k') | Event::Up => self.prev_connection(),
             Event::Char('l') | Event::Right | Event::Enter => {
-                self.select_connection();
-                if let Some(pool) = self.pool().cloned() {
-                    return super::AppEventOutcome::Apply(super::AppWidgetData::Connection(pool));
+                if let Some(config) = self.selected_connection() {
+                    let config = Box::new(config.clone());
+                    return super::AppEventOutcome::Apply(super::AppWidgetData::Connect(config));
+                }
+            }
+            Event::Char('a') => {
+                return super::AppEventOutcome::Apply(super::AppWidgetData::EditConnection(None));
+            }
+            Event::Char('e') => {
+                if let Some(i) = self.state.selected() {
+                    let data = super::AppWidgetData::EditConnection(Some(i));
+                    return super::AppEventOutcome::Apply(data);
                 }
             }
+            Event::Char('d') => self.confirm_delete = self.state.selected().is_some(),
             _ => outcome = EventOutcome::Ignore,
         }
 
@@ -96,7 +127,18 @@ impl DuzzyWidget for ConnListWidget {
 
         let [conn_area, info_area] = vertical.areas(area);
 
-        Paragraph::new("\nUse j/k to move. Enter to select connection")
+        let info = match (self.selected_connection(), self.connecting.as_ref()) {
+            (Some(config), _) if self.confirm_delete => {
+                Line::from(format!("Delete {config}? y to confirm")).red()
+            }
+            (_, Some((name, started))) => {
+                let frame = spinner(started.elapsed());
+                Line::from(format!("{frame} Connecting to {name}... Esc to cancel"))
+            }
+            _ => Line::from("Use j/k to move. Enter to select, a to add, e to edit, d to delete"),
+        };
+
+        Paragraph::new(vec![Line::default(), info])
             .centered()
             .render(info_area, buf);
 
@@ -1,42 +1,189 @@
+use std::sync::Arc;
+
 use duzzy_lib::{colors, event::Event, DuzzyWidget, EventOutcome};
 use ratatui::{
     layout::{Constraint, Direction, Layout},
-    style::Stylize,
+    style::{Style, Stylize},
     text::Line,
-    widgets::{Block, Borders, List, ListItem, Widget},
+    widgets::{Block, Borders, List, ListItem, ListState, StatefulWidget},
+};
+
+use crate::db::{
+    tree::{DatabaseTree, TreeItem},
+    DbPool, TableRef,
 };
 
-use crate::db::{tree::DatabaseTree, PgPool};
+use super::{AppEventOutcome, AppWidgetData, AppWidgetName};
 
 #[derive(Default)]
 pub struct DbTreeWidget {
     inner: DatabaseTree,
+    selected: usize,
 }
 
 impl DbTreeWidget {
-    pub async fn new(pool: &PgPool) -> anyhow::Result<Self> {
+    pub async fn new(pool: &DbPool) -> anyhow::Result<Self> {
         let mut widget = Self::default();
         widget.update(pool).await?;
         Ok(widget)
     }
 
-    pub async fn update(&mut self, pool: &PgPool) -> anyhow::Result<()> {
+    pub async fn update(&mut self, pool: &DbPool) -> anyhow::Result<()> {
         let conn = pool.acquire().await?;
-        self.inner = DatabaseTree::load(&conn).await?;
+        self.inner = conn.load_tree().await?;
+        self.selected = 0;
         Ok(())
     }
+
+    pub fn item(&self, index: usize) -> Option<Arc<TreeItem>> {
+        self.inner.as_ref().get(index).cloned()
+    }
+
+    pub fn set_children(&mut self, item: &Arc<TreeItem>, children: Vec<Arc<TreeItem>>) {
+        self.inner.insert_children(item, children);
+    }
+
+    fn visible(&self) -> impl Iterator<Item = usize> + '_ {
+        self.inner
+            .as_ref()
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| x.is_visible())
+            .map(|(i, _)| i)
+    }
+
+    fn next_item(&mut self) {
+        let next = self.visible().find(|i| *i > self.selected);
+        self.selected = next.unwrap_or(self.selected);
+    }
+
+    fn prev_item(&mut self) {
+        let prev = self.visible().filter(|i| *i < self.selected).last();
+        self.selected = prev.unwrap_or(self.selected);
+    }
+
+    const fn first_item(&mut self) {
+        self.selected = 0;
+    }
+
+    fn last_item(&mut self) {
+        let last = self.visible().last();
+        self.selected = last.unwrap_or(self.selected);
+    }
+
+    fn parent_item(&mut self) {
+        if let Some(parent) = self.inner.parent_index(self.selected) {
+            self.selected = parent;
+        }
+    }
+
+    fn next_sibling(&mut self) {
+        if let Some(next) = self.inner.next_sibling(self.selected) {
+            self.selected = next;
+        }
+    }
+
+    fn prev_sibling(&mut self) {
+        if let Some(prev) = self.inner.prev_sibling(self.selected) {
+            self.selected = prev;
+        }
+    }
+
+    fn selected_item(&self) -> Option<&crate::db::tree::TreeItem> {
+        self.inner.as_ref().get(self.selected).map(|x| x.as_ref())
+    }
+
+    fn expand_item(&mut self) -> AppEventOutcome {
+        let Some(item) = self.selected_item() else {
+            return EventOutcome::Ignore.into();
+        };
+
+        if item.is_leaf() {
+            return EventOutcome::Ignore.into();
+        }
+
+        if !item.is_collapsed() {
+            self.next_item();
+        } else if !self.inner.expand_loaded(self.selected) {
+            return AppEventOutcome::Apply(AppWidgetData::ExpandTree(self.selected));
+        }
+
+        EventOutcome::Render.into()
+    }
+
+    fn collapse_item(&mut self) {
+        match self.selected_item() {
+            Some(item) if !item.is_leaf() && !item.is_collapsed() => {
+                self.inner.collapse(self.selected);
+            }
+            _ => self.parent_item(),
+        }
+    }
+
+    fn open_table(&self) -> Option<TableRef> {
+        let item = self.selected_item().filter(|item| item.is_table())?;
+        let schema = item.schema_name()?;
+
+        Some(TableRef::new(schema.to_owned(), item.name.to_owned()))
+    }
+
+    fn toggle_item(&mut self) -> AppEventOutcome {
+        if let Some(table) = self.open_table() {
+            return AppEventOutcome::Apply(AppWidgetData::OpenTable(table));
+        }
+
+        match self.selected_item() {
+            Some(item) if !item.is_collapsed() => {
+                self.inner.collapse(self.selected);
+                EventOutcome::Render.into()
+            }
+            _ => self.expand_item(),
+        }
+    }
+
+    fn collapse_all(&mut self) {
+        self.inner.collapse_all();
+
+        while self.selected_item().is_some_and(|item| !item.is_visible()) {
+            self.parent_item();
+        }
+    }
 }
 
-const OPEN_INDENT_ICON: &str = "├──";
-const CLOSE_INDENT_ICON: &str = "└──";
+pub(super) const OPEN_INDENT_ICON: &str = "├──";
+pub(super) const CLOSE_INDENT_ICON: &str = "└──";
+pub(super) const EXPANDED_ICON: &str = "▾ ";
+pub(super) const COLLAPSED_ICON: &str = "▸ ";
 
 impl DuzzyWidget for DbTreeWidget {
-    type Outcome = super::AppEventOutcome;
+    type Outcome = AppEventOutcome;
 
     fn input(&mut self, input: duzzy_lib::event::Input) -> Self::Outcome {
-        let outcome = match input.event {
-            Event::Char('q') | Event::Esc => EventOutcome::Exit,
-            _ => EventOutcome::Ignore,
+        let mut outcome = EventOutcome::Render;
+
+        match input.event {
+            Event::Char('q') | Event::Esc => outcome = EventOutcome::Exit,
+            Event::Char('e') => return AppEventOutcome::Focus(AppWidgetName::Editor),
+            Event::Char('j') | Event::Down => self.next_item(),
+            Event::Char('k') | Event::Up => self.prev_item(),
+            Event::Char('g') | Event::Home => self.first_item(),
+            Event::Char('G') | Event::End => self.last_item(),
+            Event::Char('J') => self.next_sibling(),
+            Event::Char('K') => self.prev_sibling(),
+            Event::Char('p') => self.parent_item(),
+            Event::Char('l') | Event::Right => return self.expand_item(),
+            Event::Char('h') | Event::Left => self.collapse_item(),
+            Event::Enter | Event::Space => return self.toggle_item(),
+            Event::Char('d') if self.selected_item().is_some() => {
+                return AppEventOutcome::Apply(AppWidgetData::ShowDdl(self.selected));
+            }
+            Event::Char('I') => match self.open_table() {
+                Some(table) => return AppEventOutcome::Apply(AppWidgetData::ImportTable(table)),
+                None => outcome = EventOutcome::Ignore,
+            },
+            Event::Char('E') => self.inner.expand_all(),
+            Event::Char('C') => self.collapse_all(),
+            _ => outcome = EventOutcome::Ignore,
         };
 
         outcome.into()
@@ -49,30 +196,35 @@ impl DuzzyWidget for DbTreeWidget {
             .split(area)[0];
 
         let mut items = vec![];
-        let tree_list = self
-            .inner
-            .as_ref()
-            .iter()
-            .filter(|x| x.is_visible())
-            .enumerate()
-            .collect::<Vec<_>>();
+        let mut state = ListState::default();
 
-        let tree_len = tree_list.len() - 1;
+        let tree = self.inner.as_ref();
 
-        for (i, tree_item) in tree_list {
-            let is_close = i == tree_len || !tree_item.is_collapsed();
+        for (i, index) in self.visible().enumerate() {
+            let tree_item = &tree[index];
+            let is_last = self.inner.next_sibling(index).is_none();
 
-            let indent_icon = if is_close {
+            let indent_icon = if is_last {
                 CLOSE_INDENT_ICON
             } else {
                 OPEN_INDENT_ICON
             };
 
+            let expand_icon = match tree_item.is_leaf() {
+                true => "",
+                false if tree_item.is_collapsed() => COLLAPSED_ICON,
+                false => EXPANDED_ICON,
+            };
+
+            if index == self.selected {
+                state.select(Some(i));
+            }
+
             let item = ListItem::new(Line::styled(
                 format!(
-                    "{}{indent_icon} {}",
+                    "{}{indent_icon} {expand_icon}{}",
                     " ".repeat(tree_item.indent as usize),
-                    tree_item.name
+                    tree_item.label()
                 ),
                 colors::LIGHT_GOLDENROD_YELLOW,
             ));
@@ -80,13 +232,15 @@ impl DuzzyWidget for DbTreeWidget {
             items.push(item);
         }
 
-        let tree = List::new(items).block(
-            Block::default()
-                .title("Database Tree")
-                .borders(Borders::ALL)
-                .fg(colors::ENERGY_YELLOW),
-        );
+        let tree = List::new(items)
+            .block(
+                Block::default()
+                    .title("Database Tree")
+                    .borders(Borders::ALL)
+                    .fg(colors::ENERGY_YELLOW),
+            )
+            .highlight_style(Style::default().bg(colors::ALOE_GREEN));
 
-        Widget::render(tree, tree_area, buf);
+        StatefulWidget::render(tree, tree_area, buf, &mut state);
     }
 }
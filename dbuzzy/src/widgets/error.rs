@@ -0,0 +1,84 @@
+use duzzy_lib::{
+    colors,
+    event::{Event, Input},
+    DuzzyWidget, EventOutcome,
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::Stylize,
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
+};
+
+use super::{AppEventOutcome, AppWidgetName};
+
+const WIDTH: u16 = 80;
+
+// @note: drawn over everything, closing it gives the focus back
+pub struct ErrorWidget {
+    // @note: the error and its sources, outermost first
+    chain: Vec<String>,
+    focus: AppWidgetName,
+}
+
+impl ErrorWidget {
+    pub fn new(e: &anyhow::Error, focus: AppWidgetName) -> Self {
+        Self {
+            chain: e.chain().map(ToString::to_string).collect(),
+            focus,
+        }
+    }
+}
+
+impl DuzzyWidget for ErrorWidget {
+    type Outcome = AppEventOutcome;
+
+    fn input(&mut self, input: Input) -> Self::Outcome {
+        match input.event {
+            Event::Esc | Event::Enter | Event::Char('q') => AppEventOutcome::Focus(self.focus),
+            _ => EventOutcome::Ignore.into(),
+        }
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let lines = self
+            .chain
+            .iter()
+            .enumerate()
+            .map(|(i, e)| match i {
+                0 => Line::from(e.as_str()).red(),
+                _ => Line::from(format!("caused by: {e}")),
+            })
+            .collect::<Vec<_>>();
+
+        // @note: a rough height, long lines are wrapped
+        let width = WIDTH.min(area.width).saturating_sub(2).max(1) as usize;
+        let height = self
+            .chain
+            .iter()
+            .map(|e| (e.chars().count() + 11) / width + 1)
+            .sum::<usize>() as u16
+            + 2;
+
+        let [area] = Layout::horizontal([Constraint::Length(WIDTH)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(area);
+
+        let block = Block::default()
+            .title("Error")
+            .title_bottom("Enter or Esc to close")
+            .borders(Borders::ALL)
+            .fg(colors::ENERGY_YELLOW);
+
+        Clear.render(area, buf);
+
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(block)
+            .render(area, buf);
+    }
+}
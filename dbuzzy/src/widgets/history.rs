@@ -0,0 +1,215 @@
+use duzzy_lib::{
+    colors,
+    event::{Event, Input, Modifiers},
+    DuzzyWidget, EventOutcome,
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, StatefulWidget, Widget},
+};
+
+use super::{text_input::TextInput, AppEventOutcome, AppWidgetData, AppWidgetName};
+use crate::history::HistoryEntry;
+
+pub struct HistoryWidget {
+    // @note: newest first
+    entries: Vec<HistoryEntry>,
+    filter: TextInput,
+    // @note: indexes of `entries`, the best match first
+    matches: Vec<usize>,
+    state: ListState,
+}
+
+impl HistoryWidget {
+    pub fn new(entries: &[HistoryEntry]) -> Self {
+        let mut widget = Self {
+            entries: entries.iter().rev().cloned().collect(),
+            filter: TextInput::default(),
+            matches: vec![],
+            state: ListState::default(),
+        };
+
+        widget.update_matches();
+        widget
+    }
+
+    fn update_matches(&mut self) {
+        let pattern = self.filter.value();
+
+        let mut scored = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| fuzzy_score(&pattern, &entry.sql).map(|score| (score, i)))
+            .collect::<Vec<_>>();
+
+        // @note: newer entries win on the same score
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        self.matches = scored.into_iter().map(|(_, i)| i).collect();
+        self.state.select((!self.matches.is_empty()).then_some(0));
+    }
+
+    fn selected_sql(&self) -> Option<String> {
+        let i = self.state.selected()?;
+        let entry = self.entries.get(*self.matches.get(i)?)?;
+        Some(entry.sql.clone())
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        let Some(i) = self.state.selected() else {
+            return;
+        };
+
+        let i = match down {
+            true => (i + 1).min(self.matches.len() - 1),
+            false => i.saturating_sub(1),
+        };
+
+        self.state.select(Some(i));
+    }
+}
+
+impl DuzzyWidget for HistoryWidget {
+    type Outcome = AppEventOutcome;
+
+    fn input(&mut self, input: Input) -> Self::Outcome {
+        match input {
+            Input {
+                event: Event::Char('e'),
+                modifiers: Modifiers { ctr: true, .. },
+            } => match self.selected_sql() {
+                Some(sql) => AppEventOutcome::Apply(AppWidgetData::RerunQuery(sql)),
+                None => EventOutcome::Ignore.into(),
+            },
+            Input { event, .. } => match event {
+                Event::Enter => match self.selected_sql() {
+                    Some(sql) => AppEventOutcome::Apply(AppWidgetData::LoadQuery(sql)),
+                    None => EventOutcome::Ignore.into(),
+                },
+                Event::Esc => AppEventOutcome::Focus(AppWidgetName::Editor),
+                Event::Down => {
+                    self.move_selection(true);
+                    EventOutcome::Render.into()
+                }
+                Event::Up => {
+                    self.move_selection(false);
+                    EventOutcome::Render.into()
+                }
+                _ => {
+                    let outcome = self.filter.input(input);
+                    if outcome == EventOutcome::Render {
+                        self.update_matches();
+                    }
+                    outcome.into()
+                }
+            },
+        }
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let [area] = Layout::horizontal([Constraint::Percentage(80)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [area] = Layout::vertical([Constraint::Percentage(80)])
+            .flex(Flex::Center)
+            .areas(area);
+
+        let block = Block::default()
+            .title(format!(
+                "History {}/{}",
+                self.matches.len(),
+                self.entries.len()
+            ))
+            .title_bottom("Enter to load, Ctrl-e to run, Esc to close")
+            .borders(Borders::ALL)
+            .fg(colors::ENERGY_YELLOW);
+
+        let inner = block.inner(area);
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        let [filter_area, list_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(inner);
+        let [prompt_area, input_area] =
+            Layout::horizontal([Constraint::Length(2), Constraint::Min(0)]).areas(filter_area);
+
+        Line::from("> ").render(prompt_area, buf);
+        self.filter.render(input_area, buf);
+
+        let items = self
+            .matches
+            .iter()
+            .map(|&i| ListItem::new(entry_line(&self.entries[i])))
+            .collect::<Vec<_>>();
+
+        let list = List::new(items)
+            .highlight_symbol(">")
+            .highlight_style(Style::default().bg(colors::ALOE_GREEN));
+
+        StatefulWidget::render(list, list_area, buf, &mut self.state);
+    }
+}
+
+fn entry_line(entry: &HistoryEntry) -> Line<'static> {
+    let status = match (entry.rows, entry.error.as_ref()) {
+        (Some(rows), _) => Span::from(format!("{rows} rows")).green(),
+        (_, Some(_)) => Span::from("error").red(),
+        _ => Span::default(),
+    };
+
+    // @note: statements are shown on one line
+    let sql = entry.sql.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    Line::from(vec![
+        Span::from(entry.at.format("%Y-%m-%d %H:%M ").to_string()).dim(),
+        Span::from(format!("{} ", entry.conn)).fg(colors::LIGHT_GOLDENROD_YELLOW),
+        status,
+        Span::from(format!(" {} ms ", entry.elapsed_ms)).dim(),
+        Span::from(sql),
+    ])
+}
+
+// @note: characters of the pattern in order, consecutive and leading matches score higher
+fn fuzzy_score(pattern: &str, text: &str) -> Option<usize> {
+    let mut pattern = pattern.chars().flat_map(char::to_lowercase).peekable();
+    let mut score = 0;
+    let mut streak = 0;
+
+    for (i, ch) in text.chars().flat_map(char::to_lowercase).enumerate() {
+        let Some(&next) = pattern.peek() else {
+            break;
+        };
+
+        if ch == next {
+            pattern.next();
+            streak += 1;
+            score += streak + usize::from(i == 0) * 2;
+        } else {
+            streak = 0;
+        }
+    }
+
+    pattern.peek().is_none().then_some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert_eq!(fuzzy_score("", "select 1"), Some(0));
+        assert_eq!(fuzzy_score("slt", "select 1"), Some(5));
+        assert_eq!(fuzzy_score("SEL", "select 1"), Some(8));
+        assert_eq!(fuzzy_score("upd", "select 1"), None);
+
+        let consecutive = fuzzy_score("from", "select * from foo");
+        let scattered = fuzzy_score("from", "select foo, bar, mom");
+        assert!(consecutive > scattered);
+    }
+}
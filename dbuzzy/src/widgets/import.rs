@@ -0,0 +1,441 @@
+use std::path::{Path, PathBuf};
+
+use duzzy_lib::{
+    colors,
+    event::{Event, Input},
+    DuzzyWidget, EventOutcome,
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, Borders, Cell, Gauge, Paragraph, Row, Table, Widget, Wrap},
+};
+
+use crate::db::{
+    auto_mapping, Column, CsvImport, CsvPreview, ImportProgress, ImportSummary, TableRef,
+};
+
+use super::{
+    result_grid::expand_home, text_input::TextInput, AppEventOutcome, AppWidgetData, AppWidgetName,
+};
+
+const PREVIEW_ROWS: usize = 5;
+
+enum ImportStage {
+    Path(TextInput),
+    Mapping {
+        path: PathBuf,
+        preview: CsvPreview,
+        mapping: Vec<Option<usize>>,
+        selected: usize,
+    },
+    Running {
+        path: PathBuf,
+        progress: ImportProgress,
+    },
+    Done {
+        path: PathBuf,
+        summary: ImportSummary,
+    },
+}
+
+pub struct ImportWidget {
+    table: TableRef,
+    columns: Option<Vec<Column>>,
+    stage: ImportStage,
+    message: Option<String>,
+}
+
+impl ImportWidget {
+    pub fn new(table: TableRef) -> Self {
+        Self {
+            table,
+            columns: None,
+            stage: ImportStage::Path(TextInput::default()),
+            message: Some("loading columns…".to_owned()),
+        }
+    }
+
+    pub const fn table(&self) -> &TableRef {
+        &self.table
+    }
+
+    pub fn set_columns(&mut self, columns: Vec<Column>) {
+        self.columns = Some(columns);
+        self.message = None;
+    }
+
+    pub fn set_error(&mut self, error: impl std::fmt::Display) {
+        self.message = Some(error.to_string());
+    }
+
+    pub const fn set_progress(&mut self, progress: ImportProgress) {
+        if let ImportStage::Running { progress: p, .. } = &mut self.stage {
+            *p = progress;
+        }
+    }
+
+    pub fn set_finished(&mut self, summary: anyhow::Result<ImportSummary>) {
+        let path = match &self.stage {
+            ImportStage::Running { path, .. } => path.to_owned(),
+            _ => return,
+        };
+
+        match summary {
+            Ok(summary) => {
+                self.message = Some(format!(
+                    "imported {} rows, {} failed",
+                    summary.rows,
+                    summary.errors.len()
+                ));
+                self.stage = ImportStage::Done { path, summary };
+            }
+            Err(e) => {
+                // @note: everything was rolled back, allow to fix the mapping and retry
+                self.message = Some(format!("import failed: {e}"));
+                self.stage = ImportStage::Path(TextInput::new(&path.to_string_lossy()));
+            }
+        }
+    }
+
+    fn open(&mut self, path: &str) {
+        let Some(columns) = self.columns.as_ref() else {
+            return;
+        };
+
+        let path = expand_home(path.trim());
+        match CsvPreview::read(&path, PREVIEW_ROWS) {
+            Ok(preview) => {
+                let mapping = auto_mapping(columns, &preview.headers);
+                self.message = match mapping.iter().all(Option::is_none) {
+                    true => Some("no header matches a column, map them by hand".to_owned()),
+                    false => None,
+                };
+
+                self.stage = ImportStage::Mapping {
+                    path,
+                    preview,
+                    mapping,
+                    selected: 0,
+                };
+            }
+            Err(e) => self.message = Some(format!("{}: {e}", path.display())),
+        }
+    }
+
+    fn path_input(&mut self, input: Input) -> AppEventOutcome {
+        let ImportStage::Path(prompt) = &mut self.stage else {
+            return EventOutcome::Ignore.into();
+        };
+
+        match input.event {
+            Event::Esc => return AppEventOutcome::Focus(AppWidgetName::DatabaseTree),
+            Event::Enter => {
+                let path = prompt.value();
+                if !path.trim().is_empty() {
+                    self.open(&path);
+                }
+            }
+            _ => return prompt.input(input).into(),
+        }
+
+        EventOutcome::Render.into()
+    }
+
+    fn mapping_input(&mut self, input: Input) -> AppEventOutcome {
+        let ImportStage::Mapping {
+            path,
+            preview,
+            mapping,
+            selected,
+        } = &mut self.stage
+        else {
+            return EventOutcome::Ignore.into();
+        };
+
+        // @note: `None` skips the column, sources cycle through it
+        let sources = preview.headers.len() + 1;
+        let to_index = |source: Option<usize>| source.map_or(0, |i| i + 1);
+        let from_index = |index: usize| index.checked_sub(1);
+
+        match input.event {
+            Event::Esc => {
+                self.message = None;
+                self.stage = ImportStage::Path(TextInput::new(&path.to_string_lossy()));
+            }
+            Event::Char('j') | Event::Down => {
+                *selected = (*selected + 1).min(mapping.len().saturating_sub(1));
+            }
+            Event::Char('k') | Event::Up => *selected = selected.saturating_sub(1),
+            Event::Char('l') | Event::Right => {
+                if let Some(source) = mapping.get_mut(*selected) {
+                    *source = from_index((to_index(*source) + 1) % sources);
+                }
+            }
+            Event::Char('h') | Event::Left => {
+                if let Some(source) = mapping.get_mut(*selected) {
+                    *source = from_index((to_index(*source) + sources - 1) % sources);
+                }
+            }
+            Event::Char('x') => {
+                if let Some(source) = mapping.get_mut(*selected) {
+                    *source = None;
+                }
+            }
+            Event::Enter if mapping.iter().all(Option::is_none) => {
+                self.message = Some("map at least one column".to_owned());
+            }
+            Event::Enter => {
+                let columns = self.columns.as_deref().unwrap_or_default();
+                let import = CsvImport {
+                    table: self.table.clone(),
+                    path: path.to_owned(),
+                    delimiter: preview.delimiter,
+                    mapping: columns
+                        .iter()
+                        .zip(mapping.iter())
+                        .filter_map(|(column, source)| Some((column.name.to_owned(), (*source)?)))
+                        .collect(),
+                };
+
+                self.message = None;
+                self.stage = ImportStage::Running {
+                    progress: ImportProgress::default(),
+                    path: import.path.to_owned(),
+                };
+
+                return AppEventOutcome::Apply(AppWidgetData::StartImport(import));
+            }
+            _ => return EventOutcome::Ignore.into(),
+        }
+
+        EventOutcome::Render.into()
+    }
+
+    fn render_path(prompt: &mut TextInput, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .title("CSV file")
+            .borders(Borders::ALL)
+            .fg(colors::ENERGY_YELLOW);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+        prompt.render(inner, buf);
+    }
+
+    fn render_mapping(
+        columns: &[Column],
+        preview: &CsvPreview,
+        mapping: &[Option<usize>],
+        selected: usize,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let preview_height = (preview.rows.len() + 3) as u16;
+        let [preview_area, mapping_area] =
+            Layout::vertical([Constraint::Length(preview_height), Constraint::Min(0)]).areas(area);
+
+        let widths = preview
+            .headers
+            .iter()
+            .enumerate()
+            .map(|(i, header)| {
+                let width = preview
+                    .rows
+                    .iter()
+                    .filter_map(|row| row.get(i))
+                    .chain(std::iter::once(header))
+                    .map(|cell| cell.chars().count())
+                    .max()
+                    .unwrap_or_default();
+
+                Constraint::Length(width.min(30) as u16)
+            })
+            .collect::<Vec<_>>();
+
+        let delimiter = match preview.delimiter {
+            b'\t' => "tab".to_owned(),
+            delimiter => format!("'{}'", delimiter as char),
+        };
+
+        let header = Row::new(preview.headers.iter().map(String::as_str))
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        let rows = preview
+            .rows
+            .iter()
+            .map(|row| Row::new(row.iter().map(String::as_str)).fg(colors::LIGHT_GOLDENROD_YELLOW));
+
+        Table::new(rows, widths)
+            .header(header)
+            .column_spacing(2)
+            .block(
+                Block::default()
+                    .title(format!("Preview, delimiter {delimiter}"))
+                    .borders(Borders::ALL)
+                    .fg(colors::ENERGY_YELLOW),
+            )
+            .render(preview_area, buf);
+
+        let name_width = columns
+            .iter()
+            .map(|c| c.name.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let rows = columns
+            .iter()
+            .zip(mapping)
+            .enumerate()
+            .map(|(i, (c, source))| {
+                let source = match source.and_then(|s| preview.headers.get(s)) {
+                    Some(header) => Cell::from(format!("← {header}")),
+                    None => Cell::from("skip").add_modifier(Modifier::DIM | Modifier::ITALIC),
+                };
+
+                let row = Row::new([
+                    Cell::from(c.name.as_str()),
+                    Cell::from(c.type_name.as_str()),
+                    source,
+                ]);
+                match i == selected {
+                    true => row.bg(colors::ALOE_GREEN),
+                    false => row,
+                }
+            });
+
+        let widths = [
+            Constraint::Length(name_width as u16),
+            Constraint::Length(16),
+            Constraint::Min(0),
+        ];
+
+        Table::new(rows, widths)
+            .column_spacing(2)
+            .block(
+                Block::default()
+                    .title("Columns")
+                    .title_bottom("h/l source, x skip, Enter import, Esc back")
+                    .borders(Borders::ALL)
+                    .fg(colors::ENERGY_YELLOW),
+            )
+            .render(mapping_area, buf);
+    }
+
+    fn render_progress(
+        path: &Path,
+        progress: &ImportProgress,
+        summary: Option<&ImportSummary>,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let [gauge_area, errors_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(area);
+
+        let ratio = match progress.total_bytes {
+            0 => 1.0,
+            total => (progress.bytes as f64 / total as f64).min(1.0),
+        };
+
+        Gauge::default()
+            .block(
+                Block::default()
+                    .title(path.display().to_string())
+                    .borders(Borders::ALL)
+                    .fg(colors::ENERGY_YELLOW),
+            )
+            .gauge_style(Style::default().fg(colors::ALOE_GREEN))
+            .label(format!(
+                "{} rows, {} failed",
+                progress.rows, progress.failed
+            ))
+            .ratio(ratio)
+            .render(gauge_area, buf);
+
+        let errors = summary
+            .map(|summary| {
+                summary
+                    .errors
+                    .iter()
+                    .map(|e| Line::from(format!("line {}: {}", e.line, e.message)))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        Paragraph::new(errors)
+            .block(
+                Block::default()
+                    .title("Rejected rows")
+                    .title_bottom(match summary {
+                        Some(_) => "Esc close",
+                        None => "Esc hide, the import keeps running",
+                    })
+                    .borders(Borders::ALL)
+                    .fg(colors::ENERGY_YELLOW),
+            )
+            .wrap(Wrap { trim: false })
+            .fg(colors::LIGHT_GOLDENROD_YELLOW)
+            .render(errors_area, buf);
+    }
+}
+
+impl DuzzyWidget for ImportWidget {
+    type Outcome = AppEventOutcome;
+
+    fn input(&mut self, input: Input) -> Self::Outcome {
+        match self.stage {
+            ImportStage::Path(_) => self.path_input(input),
+            ImportStage::Mapping { .. } => self.mapping_input(input),
+            ImportStage::Running { .. } | ImportStage::Done { .. } => match input.event {
+                Event::Char('q') | Event::Esc => {
+                    AppEventOutcome::Focus(AppWidgetName::DatabaseTree)
+                }
+                _ => EventOutcome::Ignore.into(),
+            },
+        }
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let mut block = Block::default()
+            .title(format!("Import into {}", self.table))
+            .borders(Borders::ALL)
+            .fg(colors::ENERGY_YELLOW);
+
+        if let Some(message) = self.message.as_deref() {
+            block = block.title_bottom(message.to_owned());
+        }
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        match &mut self.stage {
+            ImportStage::Path(prompt) => {
+                let [prompt_area, _] =
+                    Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(inner);
+                Self::render_path(prompt, prompt_area, buf);
+            }
+            ImportStage::Mapping {
+                preview,
+                mapping,
+                selected,
+                ..
+            } => {
+                let columns = self.columns.as_deref().unwrap_or_default();
+                Self::render_mapping(columns, preview, mapping, *selected, inner, buf);
+            }
+            ImportStage::Running { path, progress } => {
+                Self::render_progress(path, progress, None, inner, buf);
+            }
+            ImportStage::Done { path, summary } => {
+                let progress = ImportProgress {
+                    rows: summary.rows,
+                    failed: summary.errors.len() as u64,
+                    bytes: 1,
+                    total_bytes: 1,
+                };
+                Self::render_progress(path, &progress, Some(summary), inner, buf);
+            }
+        }
+    }
+}
@@ -1,12 +1,44 @@
+mod confirm;
+mod conn_form;
 mod conn_list;
 mod db_tree;
+mod error;
+mod history;
+mod import;
+mod password;
+mod plan;
+mod prompt;
+mod result_grid;
+mod snippets;
+mod table_data;
+mod text_input;
 
+pub use confirm::ConfirmWidget;
+pub use conn_form::ConnFormWidget;
 pub use conn_list::ConnListWidget;
 pub use db_tree::DbTreeWidget;
+pub use error::ErrorWidget;
+pub use history::HistoryWidget;
+pub use import::ImportWidget;
+pub use password::PasswordWidget;
+pub use plan::PlanWidget;
+pub use prompt::{PromptKind, PromptWidget};
+pub use result_grid::ResultGridWidget;
+pub use snippets::SnippetsWidget;
+pub use table_data::{TableDataWidget, PAGE_SIZE};
+
+use std::{path::PathBuf, time::Duration};
 
 use duzzy_lib::EventOutcome;
 
-use crate::db::PgPool;
+use crate::db::{ConnectionConfig, CsvImport, TableRef};
+
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+// @note: the frame of a task running for `elapsed`, it turns every 100ms
+const fn spinner(elapsed: Duration) -> char {
+    SPINNER[elapsed.as_millis() as usize / 100 % SPINNER.len()]
+}
 
 // @todo:
 #[allow(dead_code)]
@@ -18,13 +50,74 @@ pub enum AppEventOutcome {
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AppWidgetName {
+    ConnectionForm,
     ConnectionList,
     DatabaseTree,
     Editor,
+    History,
+    Import,
+    Password,
+    Plan,
+    Prompt,
+    ResultGrid,
+    Snippets,
+    TableData,
+}
+
+impl AppWidgetName {
+    // @note: widgets, that belong to a connection tab
+    pub const fn is_session(&self) -> bool {
+        matches!(
+            self,
+            Self::DatabaseTree
+                | Self::Editor
+                | Self::ResultGrid
+                | Self::TableData
+                | Self::Import
+                | Self::Plan
+                | Self::Snippets
+        )
+    }
 }
 
 pub enum AppWidgetData {
-    Connection(PgPool),
+    Connect(Box<ConnectionConfig>),
+    CancelConnect,
+    SwitchSession(usize),
+    // @note: `true` rolls back an open transaction without asking
+    CloseSession(bool),
+    Exit,
+    // @note: `None` is a new connection
+    EditConnection(Option<usize>),
+    SaveConnection(Option<usize>, Box<ConnectionConfig>),
+    TestConnection(Box<ConnectionConfig>),
+    DeleteConnection(usize),
+    Query(String),
+    CancelQuery,
+    // @note: the statement, with ANALYZE
+    Explain(String, bool),
+    BeginTransaction,
+    CommitTransaction,
+    RollbackTransaction,
+    // @note: asks for the name of the savepoint
+    NewSavepoint,
+    ShowHistory,
+    LoadQuery(String),
+    RerunQuery(String),
+    ShowSnippets,
+    // @note: asks for the name of the statement
+    NewSnippet(String),
+    OpenSnippet(PathBuf),
+    Prompted(PromptKind, Vec<String>),
+    ExpandTree(usize),
+    ShowDdl(usize),
+    OpenTable(TableRef),
+    TablePage(usize),
+    CountRows,
+    CommitEdits,
+    Export(PathBuf),
+    ImportTable(TableRef),
+    StartImport(CsvImport),
 }
 
 impl From<EventOutcome> for AppEventOutcome {
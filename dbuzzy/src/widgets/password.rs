@@ -0,0 +1,78 @@
+use duzzy_lib::{
+    colors,
+    event::{Event, Input},
+    DuzzyWidget,
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::Stylize,
+    text::Line,
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use super::{text_input::TextInput, AppEventOutcome, AppWidgetData, AppWidgetName};
+use crate::db::ConnectionConfig;
+
+// @note: asks for the password, when it could not be resolved or was rejected
+pub struct PasswordWidget {
+    config: Box<ConnectionConfig>,
+    input: TextInput,
+    message: Option<String>,
+}
+
+impl PasswordWidget {
+    pub const fn new(config: Box<ConnectionConfig>, message: Option<String>) -> Self {
+        Self {
+            config,
+            input: TextInput::masked(),
+            message,
+        }
+    }
+}
+
+impl DuzzyWidget for PasswordWidget {
+    type Outcome = AppEventOutcome;
+
+    fn input(&mut self, input: Input) -> Self::Outcome {
+        match input.event {
+            Event::Esc => AppEventOutcome::Focus(AppWidgetName::ConnectionList),
+            Event::Enter => {
+                let mut config = self.config.clone();
+                config.password = Some(self.input.value());
+
+                AppEventOutcome::Apply(AppWidgetData::Connect(config))
+            }
+            _ => self.input.input(input).into(),
+        }
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let [area] = Layout::horizontal([Constraint::Length(60)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [area] = Layout::vertical([Constraint::Length(4)])
+            .flex(Flex::Center)
+            .areas(area);
+
+        let block = Block::default()
+            .title(format!("Password for {}", self.config))
+            .title_bottom("Enter to connect, Esc to cancel")
+            .borders(Borders::ALL)
+            .fg(colors::ENERGY_YELLOW);
+
+        let inner = block.inner(area);
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        let [input_area, message_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(inner);
+
+        self.input.render(input_area, buf);
+
+        if let Some(message) = self.message.as_deref() {
+            Line::from(message).red().render(message_area, buf);
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use duzzy_lib::{
+    colors,
+    event::{Event, Input},
+    DuzzyWidget, EventOutcome,
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, StatefulWidget},
+};
+
+use super::{
+    db_tree::{CLOSE_INDENT_ICON, COLLAPSED_ICON, EXPANDED_ICON, OPEN_INDENT_ICON},
+    AppEventOutcome, AppWidgetName,
+};
+use crate::db::{Plan, PlanNode};
+
+const INDENT: usize = 4;
+// @note: estimated and actual rows differ at least that many times
+const MISESTIMATE: f64 = 10.0;
+
+// @note: `EXPLAIN` output as a collapsible tree, drawn in place of the editor and the results
+pub struct PlanWidget {
+    title: String,
+    plan: Plan,
+    collapsed: Vec<bool>,
+    expensive: Option<usize>,
+    selected: usize,
+}
+
+impl PlanWidget {
+    pub fn new(title: impl Into<String>, plan: Plan) -> Self {
+        Self {
+            title: title.into(),
+            collapsed: vec![false; plan.nodes().len()],
+            expensive: plan.most_expensive(),
+            plan,
+            selected: 0,
+        }
+    }
+
+    fn is_visible(&self, index: usize) -> bool {
+        !self
+            .plan
+            .parent_index(index)
+            .is_some_and(|p| self.collapsed[p] || !self.is_visible(p))
+    }
+
+    fn visible(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.plan.nodes().len()).filter(|i| self.is_visible(*i))
+    }
+
+    fn next_item(&mut self) {
+        let next = self.visible().find(|i| *i > self.selected);
+        self.selected = next.unwrap_or(self.selected);
+    }
+
+    fn prev_item(&mut self) {
+        let prev = self.visible().filter(|i| *i < self.selected).last();
+        self.selected = prev.unwrap_or(self.selected);
+    }
+
+    fn last_item(&mut self) {
+        let last = self.visible().last();
+        self.selected = last.unwrap_or(self.selected);
+    }
+
+    fn expand_item(&mut self) {
+        match self.collapsed[self.selected] {
+            true => self.collapsed[self.selected] = false,
+            false => self.next_item(),
+        }
+    }
+
+    fn collapse_item(&mut self) {
+        if self.plan.has_children(self.selected) && !self.collapsed[self.selected] {
+            self.collapsed[self.selected] = true;
+        } else if let Some(parent) = self.plan.parent_index(self.selected) {
+            self.selected = parent;
+        }
+    }
+
+    fn toggle_item(&mut self) {
+        if self.plan.has_children(self.selected) {
+            self.collapsed[self.selected] = !self.collapsed[self.selected];
+        }
+    }
+
+    fn collapse_all(&mut self) {
+        for i in 0..self.collapsed.len() {
+            self.collapsed[i] = self.plan.has_children(i);
+        }
+        self.selected = 0;
+    }
+
+    fn item(&self, index: usize) -> ListItem<'_> {
+        let node = &self.plan.nodes()[index];
+
+        let indent_icon = match self.plan.next_sibling(index) {
+            Some(_) => OPEN_INDENT_ICON,
+            None => CLOSE_INDENT_ICON,
+        };
+
+        let expand_icon = match self.plan.has_children(index) {
+            false => "",
+            true if self.collapsed[index] => COLLAPSED_ICON,
+            true => EXPANDED_ICON,
+        };
+
+        let mut label = node.node_type.clone();
+        if let Some(detail) = &node.detail {
+            label = format!("{label} {detail}");
+        }
+
+        let mut spans = vec![Span::styled(
+            format!(
+                "{}{indent_icon} {expand_icon}{label}",
+                " ".repeat(node.depth as usize * INDENT),
+            ),
+            colors::LIGHT_GOLDENROD_YELLOW,
+        )];
+
+        if self.expensive == Some(index) {
+            spans.push(Span::from(" ◀ most expensive").red().bold());
+        }
+
+        spans.extend(node_stats(node));
+
+        ListItem::new(Line::from(spans))
+    }
+}
+
+fn node_stats(node: &PlanNode) -> Vec<Span<'_>> {
+    let mut spans = vec![Span::from(format!(
+        "  cost={:.2}..{:.2}",
+        node.startup_cost, node.total_cost
+    ))
+    .dim()];
+
+    if let Some(time) = node.actual_time {
+        spans.push(Span::from(format!(" time={time:.3}ms")));
+    }
+
+    let rows = match node.actual_rows {
+        Some(actual) => format!(" rows={}/{}", node.plan_rows, actual),
+        None => format!(" rows={}", node.plan_rows),
+    };
+
+    match node.misestimate() {
+        Some(factor) if factor >= MISESTIMATE => {
+            spans.push(Span::from(format!("{rows} (x{factor:.0})")).red());
+        }
+        _ => spans.push(Span::from(rows)),
+    }
+
+    if node.loops > 1 {
+        spans.push(Span::from(format!(" loops={}", node.loops)).dim());
+    }
+
+    if let (Some(hit), Some(read)) = (node.shared_hit, node.shared_read) {
+        spans.push(Span::from(format!(" hit={hit} read={read}")).dim());
+    }
+
+    spans
+}
+
+impl DuzzyWidget for PlanWidget {
+    type Outcome = AppEventOutcome;
+
+    fn input(&mut self, input: Input) -> Self::Outcome {
+        let mut outcome = EventOutcome::Render;
+
+        match input.event {
+            Event::Char('q') | Event::Esc => return AppEventOutcome::Focus(AppWidgetName::Editor),
+            Event::Char('j') | Event::Down => self.next_item(),
+            Event::Char('k') | Event::Up => self.prev_item(),
+            Event::Char('g') | Event::Home => self.selected = 0,
+            Event::Char('G') | Event::End => self.last_item(),
+            Event::Char('l') | Event::Right => self.expand_item(),
+            Event::Char('h') | Event::Left => self.collapse_item(),
+            Event::Enter | Event::Space => self.toggle_item(),
+            Event::Char('E') => self.collapsed.fill(false),
+            Event::Char('C') => self.collapse_all(),
+            _ => outcome = EventOutcome::Ignore,
+        }
+
+        outcome.into()
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let mut items = vec![];
+        let mut state = ListState::default();
+
+        for (i, index) in self.visible().enumerate() {
+            if index == self.selected {
+                state.select(Some(i));
+            }
+            items.push(self.item(index));
+        }
+
+        let times = [
+            ("planning", self.plan.planning_time),
+            ("execution", self.plan.execution_time),
+        ]
+        .into_iter()
+        .filter_map(|(name, time)| Some(format!("{name} {:.3}ms", time?)))
+        .collect::<Vec<_>>();
+
+        let mut block = Block::default()
+            .title(self.title.as_str())
+            .borders(Borders::ALL)
+            .fg(colors::ENERGY_YELLOW);
+
+        if !times.is_empty() {
+            block = block.title_bottom(times.join(", "));
+        }
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().bg(colors::ALOE_GREEN));
+
+        StatefulWidget::render(list, area, buf, &mut state);
+    }
+}
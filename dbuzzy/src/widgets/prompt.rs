@@ -0,0 +1,133 @@
+use duzzy_lib::{
+    colors,
+    event::{Event, Input},
+    DuzzyWidget, EventOutcome,
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+use super::{text_input::TextInput, AppEventOutcome, AppWidgetData, AppWidgetName};
+
+const LABEL_WIDTH: u16 = 14;
+
+// @note: what the values of a prompt are for
+#[derive(Clone)]
+pub enum PromptKind {
+    // @note: name, tags and scope of the statement
+    SaveSnippet(String),
+    // @note: values of the placeholders of the statement
+    QueryParams(String),
+    Savepoint,
+}
+
+// @note: a few labeled inputs over the focused widget, closing it gives the focus back
+pub struct PromptWidget {
+    title: String,
+    kind: PromptKind,
+    labels: Vec<String>,
+    inputs: Vec<TextInput>,
+    selected: usize,
+    focus: AppWidgetName,
+}
+
+impl PromptWidget {
+    pub fn new(
+        title: impl Into<String>,
+        kind: PromptKind,
+        fields: Vec<(String, String)>,
+        focus: AppWidgetName,
+    ) -> Self {
+        let (labels, inputs) = fields
+            .into_iter()
+            .map(|(label, value)| (label, TextInput::new(&value)))
+            .unzip();
+
+        Self {
+            title: title.into(),
+            kind,
+            labels,
+            inputs,
+            selected: 0,
+            focus,
+        }
+    }
+
+    pub const fn focus(&self) -> AppWidgetName {
+        self.focus
+    }
+}
+
+impl DuzzyWidget for PromptWidget {
+    type Outcome = AppEventOutcome;
+
+    fn input(&mut self, input: Input) -> Self::Outcome {
+        match input.event {
+            Event::Enter => {
+                let values = self.inputs.iter().map(TextInput::value).collect();
+                AppEventOutcome::Apply(AppWidgetData::Prompted(self.kind.clone(), values))
+            }
+            Event::Esc => AppEventOutcome::Focus(self.focus),
+            Event::Down | Event::Tab => {
+                self.selected = (self.selected + 1) % self.inputs.len();
+                EventOutcome::Render.into()
+            }
+            Event::Up => {
+                self.selected = self
+                    .selected
+                    .checked_sub(1)
+                    .unwrap_or(self.inputs.len() - 1);
+                EventOutcome::Render.into()
+            }
+            _ => self.inputs[self.selected].input(input).into(),
+        }
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let [area] = Layout::horizontal([Constraint::Length(60)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [area] = Layout::vertical([Constraint::Length(self.inputs.len() as u16 + 2)])
+            .flex(Flex::Center)
+            .areas(area);
+
+        let block = Block::default()
+            .title(self.title.as_str())
+            .title_bottom("Enter to submit, Esc to cancel")
+            .borders(Borders::ALL)
+            .fg(colors::ENERGY_YELLOW);
+
+        let inner = block.inner(area);
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        let rows = Layout::vertical(vec![Constraint::Length(1); self.inputs.len()]).split(inner);
+
+        for (i, (label, input)) in self.labels.iter().zip(self.inputs.iter_mut()).enumerate() {
+            let [label_area, input_area] =
+                Layout::horizontal([Constraint::Length(LABEL_WIDTH), Constraint::Min(0)])
+                    .areas(rows[i]);
+
+            let label = Line::styled(label.as_str(), colors::LIGHT_GOLDENROD_YELLOW);
+
+            if i == self.selected {
+                label.bold().render(label_area, buf);
+                input.render(input_area, buf);
+            } else {
+                label.render(label_area, buf);
+                buf.set_stringn(
+                    input_area.x,
+                    input_area.y,
+                    input.value(),
+                    input_area.width as usize,
+                    Style::default(),
+                );
+            }
+        }
+    }
+}
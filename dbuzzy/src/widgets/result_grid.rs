@@ -0,0 +1,340 @@
+use std::{path::PathBuf, sync::Arc, time::Instant};
+
+use duzzy_lib::{
+    colors,
+    event::{Event, Input},
+    DuzzyWidget, EventOutcome,
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Text},
+    widgets::{
+        Block, Borders, Cell, Paragraph, Row, StatefulWidget, Table, TableState, Widget, Wrap,
+    },
+};
+
+use crate::db::{QueryResult, Value};
+
+use super::{spinner, text_input::TextInput, AppEventOutcome, AppWidgetData, AppWidgetName};
+
+const MAX_COLUMN_WIDTH: usize = 40;
+pub(super) const COLUMN_SPACING: u16 = 2;
+
+#[derive(Default)]
+enum GridContent {
+    #[default]
+    Empty,
+    // @note: shared with the export task
+    Rows(Arc<QueryResult>),
+    Error(String),
+    // @note: since when the query runs
+    Running(Instant),
+}
+
+#[derive(Default)]
+pub struct ResultGridWidget {
+    content: GridContent,
+    widths: Vec<usize>,
+    selected: usize,
+    offset: usize,
+    column: usize,
+    height: usize,
+    prompt: Option<TextInput>,
+    message: Option<String>,
+}
+
+impl ResultGridWidget {
+    pub fn set_result(&mut self, result: QueryResult) {
+        self.widths = column_widths(&result);
+
+        self.content = GridContent::Rows(Arc::new(result));
+        self.message = None;
+        self.selected = 0;
+        self.offset = 0;
+        self.column = 0;
+    }
+
+    pub fn set_error(&mut self, error: impl std::fmt::Display) {
+        self.content = GridContent::Error(error.to_string());
+    }
+
+    pub fn set_running(&mut self, started: Instant) {
+        self.content = GridContent::Running(started);
+    }
+
+    pub fn clear(&mut self) {
+        self.content = GridContent::Empty;
+    }
+
+    pub fn set_message(&mut self, message: impl std::fmt::Display) {
+        self.message = Some(message.to_string());
+    }
+
+    pub fn result(&self) -> Option<Arc<QueryResult>> {
+        match &self.content {
+            GridContent::Rows(result) => Some(result.clone()),
+            _ => None,
+        }
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        matches!(self.content, GridContent::Empty)
+    }
+
+    fn rows_len(&self) -> usize {
+        match &self.content {
+            GridContent::Rows(result) => result.rows.len(),
+            _ => 0,
+        }
+    }
+
+    const fn columns_len(&self) -> usize {
+        self.widths.len()
+    }
+
+    fn move_down(&mut self, n: usize) {
+        let max = self.rows_len().saturating_sub(1);
+        self.selected = (self.selected + n).min(max);
+    }
+
+    const fn move_up(&mut self, n: usize) {
+        self.selected = self.selected.saturating_sub(n);
+    }
+
+    fn move_right(&mut self) {
+        let max = self.columns_len().saturating_sub(1);
+        self.column = (self.column + 1).min(max);
+    }
+
+    const fn move_left(&mut self) {
+        self.column = self.column.saturating_sub(1);
+    }
+
+    const fn update_offset(&mut self) {
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if self.height > 0 && self.selected >= self.offset + self.height {
+            self.offset = self.selected + 1 - self.height;
+        }
+    }
+
+    fn render_rows(&self, result: &QueryResult, area: Rect, buf: &mut Buffer) {
+        let widths = self.widths[self.column..]
+            .iter()
+            .map(|w| Constraint::Length(*w as u16))
+            .collect::<Vec<_>>();
+
+        let header = Row::new(
+            result.columns[self.column..]
+                .iter()
+                .map(|c| Cell::from(c.name.as_str())),
+        )
+        .style(Style::default().add_modifier(Modifier::BOLD));
+
+        let rows = result
+            .rows
+            .iter()
+            .skip(self.offset)
+            .take(self.height)
+            .map(|row| Row::new(row[self.column..].iter().map(value_cell)));
+
+        let summary = match self.message.as_deref() {
+            Some(message) => format!("{} | {message}", result.summary()),
+            None => result.summary(),
+        };
+
+        let block = Block::default()
+            .title("Results")
+            .title_bottom(summary)
+            .borders(Borders::ALL)
+            .fg(colors::ENERGY_YELLOW);
+
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(block)
+            .column_spacing(COLUMN_SPACING)
+            .highlight_style(Style::default().bg(colors::ALOE_GREEN));
+
+        let mut state = TableState::default().with_selected(Some(self.selected - self.offset));
+        StatefulWidget::render(table, area, buf, &mut state);
+    }
+}
+
+pub(super) fn column_widths(result: &QueryResult) -> Vec<usize> {
+    result
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            result
+                .rows
+                .iter()
+                .map(|row| cell_width(&row[i]))
+                .chain(std::iter::once(column.name.chars().count()))
+                .max()
+                .unwrap_or_default()
+                .min(MAX_COLUMN_WIDTH)
+        })
+        .collect()
+}
+
+fn cell_width(value: &Value) -> usize {
+    value.to_string().chars().count()
+}
+
+pub(super) fn value_cell(value: &Value) -> Cell<'static> {
+    let mut content = value.to_string();
+
+    if content.chars().count() > MAX_COLUMN_WIDTH {
+        content = content.chars().take(MAX_COLUMN_WIDTH - 1).collect();
+        content.push('…');
+    }
+
+    let mut text = Text::from(content);
+    if value.is_numeric() {
+        text = text.right_aligned();
+    }
+
+    let cell = Cell::from(text);
+    if value.is_null() {
+        cell.style(Style::default().add_modifier(Modifier::DIM | Modifier::ITALIC))
+    } else {
+        cell.fg(colors::LIGHT_GOLDENROD_YELLOW)
+    }
+}
+
+pub(super) fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+impl ResultGridWidget {
+    fn prompt_input(&mut self, input: Input) -> AppEventOutcome {
+        match input.event {
+            Event::Esc => self.prompt = None,
+            Event::Enter => {
+                let path = self.prompt.take().map(|prompt| prompt.value());
+                if let Some(path) = path.filter(|path| !path.trim().is_empty()) {
+                    self.message = Some(format!("exporting to {path}…"));
+                    let path = expand_home(path.trim());
+                    return AppEventOutcome::Apply(AppWidgetData::Export(path));
+                }
+            }
+            _ => {
+                if let Some(prompt) = self.prompt.as_mut() {
+                    return prompt.input(input).into();
+                }
+            }
+        }
+
+        EventOutcome::Render.into()
+    }
+
+    fn render_prompt(&mut self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .title("Export to (csv, tsv, json, ndjson, md, sql)")
+            .borders(Borders::ALL)
+            .fg(colors::ENERGY_YELLOW);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.render(inner, buf);
+        }
+    }
+}
+
+impl DuzzyWidget for ResultGridWidget {
+    type Outcome = AppEventOutcome;
+
+    fn input(&mut self, input: Input) -> Self::Outcome {
+        if self.prompt.is_some() {
+            return self.prompt_input(input);
+        }
+
+        let mut outcome = EventOutcome::Render;
+
+        match input.event {
+            Event::Char('q') | Event::Esc => return AppEventOutcome::Focus(AppWidgetName::Editor),
+            Event::Char('s') if matches!(self.content, GridContent::Rows(_)) => {
+                self.prompt = Some(TextInput::default());
+            }
+            Event::Char('j') | Event::Down | Event::MouseScrollDown => self.move_down(1),
+            Event::Char('k') | Event::Up | Event::MouseScrollUp => self.move_up(1),
+            Event::Char('l') | Event::Right => self.move_right(),
+            Event::Char('h') | Event::Left => self.move_left(),
+            Event::PageDown => self.move_down(self.height.max(1)),
+            Event::PageUp => self.move_up(self.height.max(1)),
+            Event::Home => self.selected = 0,
+            Event::End => self.selected = self.rows_len().saturating_sub(1),
+            _ => outcome = EventOutcome::Ignore,
+        }
+
+        outcome.into()
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let (area, prompt_area) = match self.prompt.is_some() {
+            true => {
+                let [grid, prompt] =
+                    Layout::vertical([Constraint::Min(0), Constraint::Length(3)]).areas(area);
+                (grid, Some(prompt))
+            }
+            false => (area, None),
+        };
+
+        if let Some(prompt_area) = prompt_area {
+            self.render_prompt(prompt_area, buf);
+        }
+
+        // @note: borders and header take 3 lines
+        self.height = area.height.saturating_sub(3) as usize;
+        self.update_offset();
+
+        match &self.content {
+            GridContent::Rows(result) => self.render_rows(result, area, buf),
+            GridContent::Error(error) => {
+                let block = Block::default()
+                    .title("Error")
+                    .borders(Borders::ALL)
+                    .fg(colors::ENERGY_YELLOW);
+
+                Paragraph::new(error.as_str())
+                    .block(block)
+                    .wrap(Wrap { trim: false })
+                    .fg(colors::LIGHT_GOLDENROD_YELLOW)
+                    .render(area, buf);
+            }
+            GridContent::Running(started) => {
+                let block = Block::default()
+                    .title("Results")
+                    .borders(Borders::ALL)
+                    .fg(colors::ENERGY_YELLOW);
+
+                let elapsed = started.elapsed();
+                let frame = spinner(elapsed);
+                let line = Line::from(format!(
+                    "{frame} Running for {:.1}s... Ctrl-C to cancel",
+                    elapsed.as_secs_f64()
+                ));
+
+                Paragraph::new(line)
+                    .centered()
+                    .block(block)
+                    .fg(colors::LIGHT_GOLDENROD_YELLOW)
+                    .render(area, buf);
+            }
+            // @note: the pane stays visible next to the editor
+            GridContent::Empty => Block::default()
+                .title("Results")
+                .borders(Borders::ALL)
+                .fg(colors::ENERGY_YELLOW)
+                .render(area, buf),
+        }
+    }
+}
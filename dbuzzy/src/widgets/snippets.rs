@@ -0,0 +1,115 @@
+use duzzy_lib::{
+    colors,
+    event::{Event, Input},
+    DuzzyWidget, EventOutcome,
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, StatefulWidget},
+};
+
+use super::{AppEventOutcome, AppWidgetData, AppWidgetName};
+use crate::snippets::Snippet;
+
+// @note: drawn in place of the database tree
+pub struct SnippetsWidget {
+    snippets: Vec<Snippet>,
+    state: ListState,
+}
+
+impl SnippetsWidget {
+    pub fn new(snippets: Vec<Snippet>) -> Self {
+        let mut state = ListState::default();
+        state.select((!snippets.is_empty()).then_some(0));
+
+        Self { snippets, state }
+    }
+
+    fn selected(&self) -> Option<&Snippet> {
+        self.state.selected().and_then(|i| self.snippets.get(i))
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        let Some(i) = self.state.selected() else {
+            return;
+        };
+
+        let i = match down {
+            true => (i + 1).min(self.snippets.len() - 1),
+            false => i.saturating_sub(1),
+        };
+
+        self.state.select(Some(i));
+    }
+}
+
+impl DuzzyWidget for SnippetsWidget {
+    type Outcome = AppEventOutcome;
+
+    fn input(&mut self, input: Input) -> Self::Outcome {
+        let mut outcome = EventOutcome::Render;
+
+        match input.event {
+            Event::Char('q') | Event::Esc => {
+                return AppEventOutcome::Focus(AppWidgetName::DatabaseTree)
+            }
+            Event::Char('j') | Event::Down => self.move_selection(true),
+            Event::Char('k') | Event::Up => self.move_selection(false),
+            Event::Enter | Event::Char('l') => {
+                if let Some(snippet) = self.selected() {
+                    let path = snippet.path.clone();
+                    return AppEventOutcome::Apply(AppWidgetData::OpenSnippet(path));
+                }
+            }
+            Event::Char('r') => {
+                if let Some(snippet) = self.selected() {
+                    let sql = snippet.sql.clone();
+                    return AppEventOutcome::Apply(AppWidgetData::Query(sql));
+                }
+            }
+            _ => outcome = EventOutcome::Ignore,
+        }
+
+        outcome.into()
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let items = self
+            .snippets
+            .iter()
+            .map(|snippet| {
+                let mut spans = vec![];
+
+                if snippet.conn.is_some() {
+                    spans.push(Span::from("@ ").dim());
+                }
+
+                spans.push(Span::styled(
+                    snippet.name.as_str(),
+                    colors::LIGHT_GOLDENROD_YELLOW,
+                ));
+
+                for tag in &snippet.tags {
+                    spans.push(Span::from(format!(" #{tag}")).dim());
+                }
+
+                ListItem::new(Line::from(spans))
+            })
+            .collect::<Vec<_>>();
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title("Snippets")
+                    .title_bottom("Enter to open, r to run")
+                    .borders(Borders::ALL)
+                    .fg(colors::ENERGY_YELLOW),
+            )
+            .highlight_style(Style::default().bg(colors::ALOE_GREEN));
+
+        StatefulWidget::render(list, area, buf, &mut self.state);
+    }
+}
@@ -0,0 +1,727 @@
+use duzzy_lib::{
+    colors,
+    event::{Event, Input},
+    DuzzyWidget, EventOutcome,
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    widgets::{
+        Block, Borders, Cell, Paragraph, Row, StatefulWidget, Table, TableState, Widget, Wrap,
+    },
+};
+
+use crate::db::{
+    DriverKind, EditBatch, QueryResult, RowCount, RowKey, TableInfo, TablePage, TableRef,
+};
+
+use super::{
+    result_grid::{column_widths, value_cell, COLUMN_SPACING},
+    text_input::TextInput,
+    AppEventOutcome, AppWidgetData, AppWidgetName,
+};
+
+pub const PAGE_SIZE: usize = 200;
+
+const DEFAULT_WIDTH: usize = 7;
+
+pub struct TableDataWidget {
+    table: TableRef,
+    info: Option<TableInfo>,
+    page: usize,
+    result: Option<QueryResult>,
+    error: Option<String>,
+    message: Option<String>,
+    loading: bool,
+    widths: Vec<usize>,
+    // @note: key columns stay on the left while scrolling
+    frozen: Vec<usize>,
+    scrollable: Vec<usize>,
+    edits: EditBatch,
+    // @note: with the value before the edit, so an untouched NULL is not staged as ''
+    prompt: Option<(TextInput, Option<String>)>,
+    selected: usize,
+    offset: usize,
+    cursor: usize,
+    scroll: usize,
+    height: usize,
+}
+
+impl TableDataWidget {
+    pub fn new(table: TableRef, kind: DriverKind) -> Self {
+        Self {
+            edits: EditBatch::new(table.clone(), kind),
+            table,
+            info: None,
+            page: 0,
+            result: None,
+            error: None,
+            message: None,
+            loading: true,
+            widths: vec![],
+            frozen: vec![],
+            scrollable: vec![],
+            prompt: None,
+            selected: 0,
+            offset: 0,
+            cursor: 0,
+            scroll: 0,
+            height: 0,
+        }
+    }
+
+    pub const fn table(&self) -> &TableRef {
+        &self.table
+    }
+
+    pub const fn info(&self) -> Option<&TableInfo> {
+        self.info.as_ref()
+    }
+
+    pub const fn edits(&self) -> &EditBatch {
+        &self.edits
+    }
+
+    pub const fn page(&self) -> usize {
+        self.page
+    }
+
+    pub fn set_info(&mut self, info: TableInfo) {
+        self.info = Some(info);
+    }
+
+    pub fn set_count(&mut self, count: RowCount) {
+        if let Some(info) = self.info.as_mut() {
+            info.rows = count;
+        }
+        self.message = None;
+    }
+
+    pub fn set_page(&mut self, page: TablePage) {
+        let key = self
+            .info
+            .as_ref()
+            .map(|info| info.key.as_slice())
+            .unwrap_or_default();
+
+        let (frozen, scrollable) = (0..page.result.columns.len())
+            .partition(|i| key.contains(&page.result.columns[*i].name));
+
+        self.frozen = frozen;
+        self.scrollable = scrollable;
+        self.widths = column_widths(&page.result)
+            .into_iter()
+            .map(|w| w.max(DEFAULT_WIDTH))
+            .collect();
+
+        // @note: moving back a page lands on its last row
+        self.selected = match page.page < self.page {
+            true => page.result.rows.len().saturating_sub(1),
+            false => 0,
+        };
+
+        self.cursor = self.cursor.min(page.result.columns.len().saturating_sub(1));
+        self.page = page.page;
+        self.result = Some(page.result);
+        self.error = None;
+        self.loading = false;
+    }
+
+    pub fn set_error(&mut self, error: impl std::fmt::Display) {
+        self.error = Some(error.to_string());
+        self.loading = false;
+    }
+
+    pub fn set_committed(&mut self, affected: u64) {
+        self.edits.clear();
+        self.message = Some(format!("{affected} rows affected"));
+    }
+
+    pub fn set_commit_error(&mut self, error: impl std::fmt::Display) {
+        self.message = Some(error.to_string());
+        self.loading = false;
+    }
+
+    fn fetched_len(&self) -> usize {
+        self.result.as_ref().map_or(0, |r| r.rows.len())
+    }
+
+    fn rows_len(&self) -> usize {
+        self.fetched_len() + self.edits.inserts().count()
+    }
+
+    fn has_next_page(&self) -> bool {
+        self.fetched_len() == PAGE_SIZE
+    }
+
+    fn columns(&self) -> Vec<usize> {
+        self.frozen
+            .iter()
+            .chain(self.scrollable.iter())
+            .copied()
+            .collect()
+    }
+
+    fn column_name(&self, column: usize) -> Option<&str> {
+        let result = self.result.as_ref()?;
+        Some(result.columns.get(column)?.name.as_str())
+    }
+
+    fn row_key(&self, row: usize) -> Option<RowKey> {
+        let result = self.result.as_ref()?;
+        let info = self.info.as_ref()?;
+        let values = result.rows.get(row)?;
+
+        info.key
+            .iter()
+            .map(|name| {
+                let i = result.columns.iter().position(|c| c.name == *name)?;
+                Some((name.to_owned(), values[i].clone()))
+            })
+            .collect()
+    }
+
+    fn request_page(&mut self, page: usize) -> AppEventOutcome {
+        if self.loading || self.info.is_none() {
+            return EventOutcome::Ignore.into();
+        }
+
+        self.loading = true;
+        AppEventOutcome::Apply(AppWidgetData::TablePage(page))
+    }
+
+    fn next_page(&mut self) -> AppEventOutcome {
+        match self.has_next_page() {
+            true => self.request_page(self.page + 1),
+            false => EventOutcome::Ignore.into(),
+        }
+    }
+
+    fn prev_page(&mut self) -> AppEventOutcome {
+        match self.page.checked_sub(1) {
+            Some(page) => self.request_page(page),
+            None => EventOutcome::Ignore.into(),
+        }
+    }
+
+    fn move_down(&mut self) -> AppEventOutcome {
+        if self.selected + 1 < self.rows_len() {
+            self.selected += 1;
+            return EventOutcome::Render.into();
+        }
+
+        self.next_page()
+    }
+
+    fn move_up(&mut self) -> AppEventOutcome {
+        if self.selected > 0 {
+            self.selected -= 1;
+            return EventOutcome::Render.into();
+        }
+
+        self.prev_page()
+    }
+
+    fn move_right(&mut self) {
+        let max = self.widths.len().saturating_sub(1);
+        self.cursor = (self.cursor + 1).min(max);
+    }
+
+    const fn move_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    const fn update_offset(&mut self) {
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if self.height > 0 && self.selected >= self.offset + self.height {
+            self.offset = self.selected + 1 - self.height;
+        }
+    }
+
+    fn update_scroll(&mut self, width: usize) {
+        let Some(cursor) = self.cursor.checked_sub(self.frozen.len()) else {
+            return;
+        };
+
+        self.scroll = self.scroll.min(cursor);
+
+        let column_width = |i: &usize| self.widths[*i] + COLUMN_SPACING as usize;
+        let frozen = self.frozen.iter().map(column_width).sum::<usize>();
+
+        while self.scroll < cursor
+            && frozen
+                + self.scrollable[self.scroll..=cursor]
+                    .iter()
+                    .map(column_width)
+                    .sum::<usize>()
+                > width
+        {
+            self.scroll += 1;
+        }
+    }
+
+    fn can_edit(&mut self) -> bool {
+        if self.result.is_none() || self.loading {
+            return false;
+        }
+
+        if self.info.as_ref().is_some_and(|info| info.key.is_empty()) {
+            self.message = Some(format!(
+                "{} has no primary or unique key, editing is disabled",
+                self.table
+            ));
+
+            return false;
+        }
+
+        true
+    }
+
+    // @note: the staged statements go to the editor of the session for review
+    fn preview(&self) -> AppEventOutcome {
+        match self.edits.is_empty() {
+            true => EventOutcome::Ignore.into(),
+            false => AppEventOutcome::Apply(AppWidgetData::LoadQuery(self.edits.preview())),
+        }
+    }
+
+    fn current_value(&self, row: usize, column: &str) -> Option<String> {
+        let fetched = self.fetched_len();
+
+        if row >= fetched {
+            let values = self.edits.inserts().nth(row - fetched)?;
+            return values
+                .iter()
+                .find(|(c, _)| c == column)
+                .and_then(|(_, value)| value.clone());
+        }
+
+        if let Some(value) = self
+            .row_key(row)
+            .and_then(|key| self.edits.changed_value(&key, column).cloned())
+        {
+            return value;
+        }
+
+        let result = self.result.as_ref()?;
+        let i = result.columns.iter().position(|c| c.name == column)?;
+        let value = &result.rows[row][i];
+
+        (!value.is_null()).then(|| value.to_string())
+    }
+
+    fn start_edit(&mut self) {
+        if !self.can_edit() {
+            return;
+        }
+
+        let Some(column) = self.columns().get(self.cursor).copied() else {
+            return;
+        };
+
+        let value = self
+            .column_name(column)
+            .and_then(|name| self.current_value(self.selected, name));
+
+        let input = TextInput::new(value.as_deref().unwrap_or_default());
+        self.prompt = Some((input, value));
+    }
+
+    fn stage(&mut self, value: Option<String>) {
+        let Some(column) = self
+            .columns()
+            .get(self.cursor)
+            .and_then(|i| self.column_name(*i))
+            .map(ToOwned::to_owned)
+        else {
+            return;
+        };
+
+        let fetched = self.fetched_len();
+
+        if self.selected >= fetched {
+            self.edits
+                .update_insert(self.selected - fetched, &column, value);
+        } else if let Some(key) = self.row_key(self.selected) {
+            self.edits.update(key, &column, value);
+        }
+    }
+
+    fn set_null(&mut self) {
+        if self.can_edit() {
+            self.stage(None);
+        }
+    }
+
+    fn insert_row(&mut self) {
+        if !self.can_edit() {
+            return;
+        }
+
+        self.edits.insert();
+        self.selected = self.rows_len() - 1;
+    }
+
+    fn toggle_delete(&mut self) {
+        if !self.can_edit() {
+            return;
+        }
+
+        let fetched = self.fetched_len();
+
+        if self.selected >= fetched {
+            self.edits.remove_insert(self.selected - fetched);
+            self.selected = self.selected.min(self.rows_len().saturating_sub(1));
+        } else if let Some(key) = self.row_key(self.selected) {
+            self.edits.toggle_delete(key);
+        }
+    }
+
+    fn discard(&mut self) {
+        self.edits.clear();
+        self.selected = self.selected.min(self.rows_len().saturating_sub(1));
+    }
+
+    fn count_rows(&mut self) -> AppEventOutcome {
+        if self.info.is_none() {
+            return EventOutcome::Ignore.into();
+        }
+
+        self.message = Some("counting rows…".to_owned());
+        AppEventOutcome::Apply(AppWidgetData::CountRows)
+    }
+
+    fn commit(&mut self) -> AppEventOutcome {
+        if self.edits.is_empty() || self.loading {
+            return EventOutcome::Ignore.into();
+        }
+
+        self.loading = true;
+        self.message = None;
+        AppEventOutcome::Apply(AppWidgetData::CommitEdits)
+    }
+
+    fn prompt_input(&mut self, input: Input) -> AppEventOutcome {
+        match input.event {
+            Event::Esc => self.prompt = None,
+            Event::Enter => {
+                if let Some((prompt, value)) = self.prompt.take() {
+                    let edited = prompt.value();
+                    if value.unwrap_or_default() != edited {
+                        self.stage(Some(edited));
+                    }
+                }
+            }
+            _ => {
+                if let Some((prompt, _)) = self.prompt.as_mut() {
+                    return prompt.input(input).into();
+                }
+            }
+        }
+
+        EventOutcome::Render.into()
+    }
+
+    fn status(&self) -> String {
+        let total = self
+            .info
+            .as_ref()
+            .map_or_else(|| "?".to_owned(), |info| info.rows.to_string());
+
+        let first = self.page * PAGE_SIZE;
+        let mut status = match self.fetched_len() {
+            0 => format!("0 of {total} rows"),
+            len => format!("rows {}-{} of {total}", first + 1, first + len),
+        };
+
+        if self.loading {
+            status.push_str(" (loading…)");
+        }
+
+        if self.info.as_ref().map(|info| info.rows) == Some(RowCount::Unknown) {
+            status.push_str(", C to count");
+        }
+
+        if !self.edits.is_empty() {
+            let staged = self.edits.statements().len();
+            status = format!("{status} | {staged} staged, v to review, c to commit");
+        }
+
+        if let Some(message) = self.message.as_deref() {
+            status = format!("{status} | {message}");
+        }
+
+        status
+    }
+
+    fn render_rows(&self, result: &QueryResult, block: Block<'_>, area: Rect, buf: &mut Buffer) {
+        let columns = self
+            .frozen
+            .iter()
+            .chain(self.scrollable.iter().skip(self.scroll))
+            .copied()
+            .collect::<Vec<_>>();
+
+        let cursor = self.columns().get(self.cursor).copied();
+
+        let widths = columns
+            .iter()
+            .map(|i| Constraint::Length(self.widths[*i] as u16));
+
+        let header = Row::new(columns.iter().map(|i| {
+            let cell = Cell::from(result.columns[*i].name.as_str());
+            match self.frozen.contains(i) {
+                true => cell.add_modifier(Modifier::UNDERLINED),
+                false => cell,
+            }
+        }))
+        .style(Style::default().add_modifier(Modifier::BOLD));
+
+        let fetched = result.rows.iter().enumerate().map(|(i, row)| {
+            let key = self.row_key(i);
+            let is_deleted = key.as_ref().is_some_and(|key| self.edits.is_deleted(key));
+
+            let cells = columns.iter().map(|c| {
+                let name = result.columns[*c].name.as_str();
+                let cell = match key
+                    .as_ref()
+                    .and_then(|key| self.edits.changed_value(key, name))
+                {
+                    Some(value) => staged_cell(value.as_deref()),
+                    None => value_cell(&row[*c]),
+                };
+
+                cursor_cell(cell, i == self.selected && Some(*c) == cursor)
+            });
+
+            match is_deleted {
+                true => Row::new(cells).add_modifier(Modifier::CROSSED_OUT | Modifier::DIM),
+                false => Row::new(cells),
+            }
+        });
+
+        let inserted = self.edits.inserts().enumerate().map(|(i, values)| {
+            let cells = columns.iter().map(|c| {
+                let name = result.columns[*c].name.as_str();
+                let cell = match values.iter().find(|(column, _)| column == name) {
+                    Some((_, value)) => staged_cell(value.as_deref()),
+                    None => Cell::from("DEFAULT").add_modifier(Modifier::DIM | Modifier::ITALIC),
+                };
+
+                let is_selected = result.rows.len() + i == self.selected;
+                cursor_cell(cell, is_selected && Some(*c) == cursor)
+            });
+
+            Row::new(cells)
+        });
+
+        let rows = fetched
+            .chain(inserted)
+            .skip(self.offset)
+            .take(self.height)
+            .collect::<Vec<_>>();
+
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(block)
+            .column_spacing(COLUMN_SPACING)
+            .highlight_style(Style::default().bg(colors::ALOE_GREEN));
+
+        let selected = self.selected.saturating_sub(self.offset);
+        let mut state = TableState::default().with_selected(Some(selected));
+
+        StatefulWidget::render(table, area, buf, &mut state);
+    }
+
+    fn render_prompt(&mut self, area: Rect, buf: &mut Buffer) {
+        let column = self
+            .columns()
+            .get(self.cursor)
+            .and_then(|i| self.column_name(*i))
+            .unwrap_or_default()
+            .to_owned();
+
+        let block = Block::default()
+            .title(format!("Edit {column}"))
+            .borders(Borders::ALL)
+            .fg(colors::ENERGY_YELLOW);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if let Some((prompt, _)) = self.prompt.as_mut() {
+            prompt.render(inner, buf);
+        }
+    }
+}
+
+fn staged_cell(value: Option<&str>) -> Cell<'static> {
+    Cell::from(value.unwrap_or("NULL").to_owned())
+        .fg(colors::ENERGY_YELLOW)
+        .add_modifier(Modifier::BOLD)
+}
+
+fn cursor_cell(cell: Cell<'_>, is_cursor: bool) -> Cell<'_> {
+    match is_cursor {
+        true => cell.add_modifier(Modifier::REVERSED),
+        false => cell,
+    }
+}
+
+impl DuzzyWidget for TableDataWidget {
+    type Outcome = AppEventOutcome;
+
+    fn input(&mut self, input: Input) -> Self::Outcome {
+        if self.prompt.is_some() {
+            return self.prompt_input(input);
+        }
+
+        let mut outcome = EventOutcome::Render;
+        self.message = None;
+
+        match input.event {
+            Event::Char('q') | Event::Esc => {
+                return AppEventOutcome::Focus(AppWidgetName::DatabaseTree)
+            }
+            Event::Char('j') | Event::Down | Event::MouseScrollDown => return self.move_down(),
+            Event::Char('k') | Event::Up | Event::MouseScrollUp => return self.move_up(),
+            Event::Char('l') | Event::Right => self.move_right(),
+            Event::Char('h') | Event::Left => self.move_left(),
+            Event::Char(']') | Event::PageDown => return self.next_page(),
+            Event::Char('[') | Event::PageUp => return self.prev_page(),
+            Event::Char('g') | Event::Home => self.selected = 0,
+            Event::Char('G') | Event::End => self.selected = self.rows_len().saturating_sub(1),
+            Event::Char('r') => return self.request_page(self.page),
+            Event::Char('i') | Event::Enter => self.start_edit(),
+            Event::Char('n') => self.set_null(),
+            Event::Char('o') => self.insert_row(),
+            Event::Char('d') => self.toggle_delete(),
+            Event::Char('u') => self.discard(),
+            Event::Char('v') => return self.preview(),
+            Event::Char('c') => return self.commit(),
+            Event::Char('C') => return self.count_rows(),
+            _ => outcome = EventOutcome::Ignore,
+        }
+
+        outcome.into()
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let (area, prompt_area) = match self.prompt.is_some() {
+            true => {
+                let [data, prompt] =
+                    Layout::vertical([Constraint::Min(0), Constraint::Length(3)]).areas(area);
+                (data, Some(prompt))
+            }
+            false => (area, None),
+        };
+
+        // @note: borders and header take 3 lines
+        self.height = area.height.saturating_sub(3) as usize;
+        self.update_offset();
+        self.update_scroll(area.width.saturating_sub(2) as usize);
+
+        let block = Block::default()
+            .title(self.table.to_string())
+            .title_bottom(self.status())
+            .borders(Borders::ALL)
+            .fg(colors::ENERGY_YELLOW);
+
+        if let Some(error) = self.error.as_deref() {
+            Paragraph::new(error)
+                .block(block)
+                .wrap(Wrap { trim: false })
+                .fg(colors::LIGHT_GOLDENROD_YELLOW)
+                .render(area, buf);
+        } else if let Some(result) = self.result.as_ref() {
+            self.render_rows(result, block, area, buf);
+        } else {
+            block.render(area, buf);
+        }
+
+        if let Some(prompt_area) = prompt_area {
+            self.render_prompt(prompt_area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use duzzy_lib::event::Modifiers;
+
+    use super::*;
+    use crate::db::{Column, RowCount, Value};
+
+    fn input(event: Event) -> Input {
+        Input {
+            event,
+            modifiers: Modifiers::default(),
+        }
+    }
+
+    #[test]
+    fn test_stage_null() {
+        let table = TableRef::new("foo".to_owned(), "bar".to_owned());
+        let mut widget = TableDataWidget::new(table, DriverKind::Postgres);
+
+        widget.set_info(TableInfo {
+            key: vec!["id".to_owned()],
+            rows: RowCount::Exact(1),
+        });
+        widget.set_page(TablePage {
+            page: 0,
+            result: QueryResult {
+                columns: ["id", "baz"]
+                    .map(|name| Column {
+                        name: name.to_owned(),
+                        type_name: "text".to_owned(),
+                    })
+                    .into(),
+                rows: vec![vec![Value::Int(1), Value::Null]],
+                ..Default::default()
+            },
+        });
+
+        // @note: an untouched NULL is not staged
+        widget.input(input(Event::Right));
+        widget.input(input(Event::Enter));
+        widget.input(input(Event::Enter));
+        assert!(widget.edits().is_empty());
+
+        widget.input(input(Event::Enter));
+        widget.input(input(Event::Char('x')));
+        widget.input(input(Event::Enter));
+        assert_eq!(
+            widget.edits().statements(),
+            vec![r#"UPDATE "foo"."bar" SET "baz" = 'x' WHERE "id" = '1';"#]
+        );
+
+        assert!(matches!(
+            widget.input(input(Event::Char('v'))),
+            AppEventOutcome::Apply(AppWidgetData::LoadQuery(sql)) if sql.starts_with("BEGIN;")
+        ));
+    }
+
+    #[test]
+    fn test_count_rows() {
+        let table = TableRef::new("foo".to_owned(), "bar".to_owned());
+        let mut widget = TableDataWidget::new(table, DriverKind::Postgres);
+
+        widget.set_info(TableInfo {
+            key: vec![],
+            rows: RowCount::Unknown,
+        });
+        assert!(widget.status().starts_with("0 of ? rows"));
+        assert!(widget.status().contains("C to count"));
+
+        assert!(matches!(
+            widget.input(input(Event::Char('C'))),
+            AppEventOutcome::Apply(AppWidgetData::CountRows)
+        ));
+
+        widget.set_count(RowCount::Exact(42));
+        assert!(widget.status().starts_with("0 of 42 rows"));
+    }
+}
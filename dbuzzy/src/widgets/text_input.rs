@@ -0,0 +1,155 @@
+use duzzy_lib::{
+    event::{Event, Input, Modifiers},
+    DuzzyWidget, EventOutcome,
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+};
+
+#[derive(Debug, Default)]
+pub struct TextInput {
+    value: Vec<char>,
+    cursor: usize,
+    // @note: render `*` instead of the value, for passwords
+    masked: bool,
+}
+
+impl TextInput {
+    pub fn new(value: &str) -> Self {
+        let value = value.chars().collect::<Vec<_>>();
+
+        Self {
+            cursor: value.len(),
+            value,
+            masked: false,
+        }
+    }
+
+    pub const fn masked() -> Self {
+        Self {
+            value: vec![],
+            cursor: 0,
+            masked: true,
+        }
+    }
+
+    pub fn value(&self) -> String {
+        self.value.iter().collect()
+    }
+
+    fn insert(&mut self, ch: char) {
+        self.value.insert(self.cursor, ch);
+        self.cursor += 1;
+    }
+}
+
+impl DuzzyWidget for TextInput {
+    type Outcome = EventOutcome;
+
+    fn input(&mut self, input: Input) -> Self::Outcome {
+        let mut outcome = EventOutcome::Render;
+
+        match input {
+            Input {
+                event: Event::Char('u'),
+                modifiers: Modifiers { ctr: true, .. },
+            } => {
+                self.value.drain(..self.cursor);
+                self.cursor = 0;
+            }
+            Input {
+                modifiers: Modifiers { ctr: true, .. },
+                ..
+            } => outcome = EventOutcome::Ignore,
+            Input { event, .. } => match event {
+                Event::Char(ch) => self.insert(ch),
+                Event::Space => self.insert(' '),
+                Event::Backspace if self.cursor > 0 => {
+                    self.cursor -= 1;
+                    self.value.remove(self.cursor);
+                }
+                Event::Delete if self.cursor < self.value.len() => {
+                    self.value.remove(self.cursor);
+                }
+                Event::Left => self.cursor = self.cursor.saturating_sub(1),
+                Event::Right => self.cursor = (self.cursor + 1).min(self.value.len()),
+                Event::Home => self.cursor = 0,
+                Event::End => self.cursor = self.value.len(),
+                _ => outcome = EventOutcome::Ignore,
+            },
+        }
+
+        outcome
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        // @note: scroll horizontally, so the cursor is always visible
+        let width = area.width as usize;
+        let skip = (self.cursor + 1).saturating_sub(width);
+
+        let text = self
+            .value
+            .iter()
+            .skip(skip)
+            .take(width)
+            .map(|ch| if self.masked { '*' } else { *ch })
+            .collect::<String>();
+
+        buf.set_string(area.x, area.y, text, Style::default());
+
+        let x = area.x + (self.cursor - skip) as u16;
+        buf.get_mut(x, area.y)
+            .set_style(Style::default().add_modifier(Modifier::REVERSED));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(event: Event) -> Input {
+        Input {
+            event,
+            modifiers: Modifiers::default(),
+        }
+    }
+
+    #[test]
+    fn test_text_input() {
+        let mut text = TextInput::new("ac");
+
+        text.input(input(Event::Left));
+        text.input(input(Event::Char('b')));
+        assert_eq!(text.value(), "abc");
+
+        text.input(input(Event::Home));
+        text.input(input(Event::Delete));
+        text.input(input(Event::End));
+        text.input(input(Event::Backspace));
+        text.input(input(Event::Space));
+        assert_eq!(text.value(), "b ");
+
+        let outcome = text.input(input(Event::Enter));
+        assert_eq!(outcome, EventOutcome::Ignore);
+    }
+
+    #[test]
+    fn test_masked() {
+        let mut text = TextInput::masked();
+        text.input(input(Event::Char('a')));
+        text.input(input(Event::Char('b')));
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 1));
+        text.render(buf.area, &mut buf);
+
+        assert_eq!(text.value(), "ab");
+        assert_eq!(buf.get(0, 0).symbol(), "*");
+        assert_eq!(buf.get(1, 0).symbol(), "*");
+    }
+}
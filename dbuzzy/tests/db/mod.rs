@@ -1,7 +1,12 @@
-use dbuzzy::db::{ConnectionConfig, PgPool};
+use std::{
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use dbuzzy::db::{ConnectionConfig, DriverKind, PgPool, SshConfig, SslMode};
 use deadpool_postgres::GenericClient;
 use testcontainers::{
-    core::{IntoContainerPort, WaitFor},
+    core::{IntoContainerPort, Mount, WaitFor},
     runners::AsyncRunner,
     ContainerAsync, GenericImage, ImageExt,
 };
@@ -9,6 +14,22 @@ use testcontainers::{
 const NAME: &str = "postgres";
 const TAG: &str = "16-alpine";
 
+// @note: of the container started by `setup`
+pub fn config() -> ConnectionConfig {
+    ConnectionConfig {
+        name: None,
+        driver: DriverKind::Postgres,
+        host: "127.0.0.1".to_owned(),
+        port: Some(5432),
+        user: "postgres".to_owned(),
+        dbname: Some("postgres".to_owned()),
+        password: Some("postgres".to_owned()),
+        path: None,
+        sslmode: SslMode::Disable,
+        ..Default::default()
+    }
+}
+
 pub async fn setup() -> anyhow::Result<(ContainerAsync<GenericImage>, PgPool)> {
     let image = GenericImage::new(NAME, TAG)
         .with_wait_for(WaitFor::message_on_stderr(
@@ -20,17 +41,7 @@ pub async fn setup() -> anyhow::Result<(ContainerAsync<GenericImage>, PgPool)> {
         .with_mapped_port(5432, 5432.tcp());
 
     let container = image.start().await?;
-
-    let config = ConnectionConfig {
-        name: None,
-        host: "127.0.0.1".to_owned(),
-        port: 5432,
-        user: "postgres".to_owned(),
-        dbname: Some("postgres".to_owned()),
-        password: Some("postgres".to_owned()),
-    };
-
-    let pool = PgPool::create(&config)?;
+    let pool = PgPool::create(&config())?;
 
     let connection = pool.acquire().await?;
 
@@ -68,3 +79,200 @@ pub async fn setup() -> anyhow::Result<(ContainerAsync<GenericImage>, PgPool)> {
 
     Ok((container, pool))
 }
+
+// @note: a test CA and a server certificate, that is valid for `localhost` only
+fn create_certs(dir: &Path) -> anyhow::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(dir.join("san.ext"), "subjectAltName = DNS:localhost\n")?;
+
+    let openssl = |args: &[&str]| -> anyhow::Result<()> {
+        let status = Command::new("openssl")
+            .args(args)
+            .current_dir(dir)
+            .stderr(Stdio::null())
+            .status()?;
+        anyhow::ensure!(status.success(), "openssl {} failed", args[0]);
+        Ok(())
+    };
+
+    #[rustfmt::skip]
+    openssl(&[
+        "req", "-x509", "-newkey", "rsa:2048", "-nodes", "-days", "1",
+        "-subj", "/CN=dbuzzy test CA", "-keyout", "ca.key", "-out", "ca.crt",
+    ])?;
+    #[rustfmt::skip]
+    openssl(&[
+        "req", "-newkey", "rsa:2048", "-nodes",
+        "-subj", "/CN=localhost", "-keyout", "server.key", "-out", "server.csr",
+    ])?;
+    #[rustfmt::skip]
+    openssl(&[
+        "x509", "-req", "-in", "server.csr", "-CA", "ca.crt", "-CAkey", "ca.key",
+        "-CAcreateserial", "-days", "1", "-extfile", "san.ext", "-out", "server.crt",
+    ])?;
+
+    Ok(())
+}
+
+pub struct SslSetup {
+    pub container: ContainerAsync<GenericImage>,
+    pub config: ConnectionConfig,
+    pub root_cert: PathBuf,
+}
+
+// @note: the key is copied, postgres refuses a key file it doesn't own
+pub async fn setup_ssl() -> anyhow::Result<SslSetup> {
+    let dir = std::env::temp_dir().join(format!("dbuzzy-ssl-{}", std::process::id()));
+    create_certs(&dir)?;
+
+    let script = "cp /certs/server.crt /certs/server.key /tmp \
+        && chown postgres /tmp/server.key && chmod 600 /tmp/server.key \
+        && exec docker-entrypoint.sh postgres -c ssl=on \
+        -c ssl_cert_file=/tmp/server.crt -c ssl_key_file=/tmp/server.key";
+
+    let image = GenericImage::new(NAME, TAG)
+        .with_wait_for(WaitFor::message_on_stderr(
+            "database system is ready to accept connections",
+        ))
+        .with_entrypoint("sh")
+        .with_env_var("POSTGRES_PASSWORD", "postgres")
+        .with_mapped_port(5432, 5432.tcp())
+        .with_mount(Mount::bind_mount(dir.to_string_lossy(), "/certs"))
+        .with_cmd(["-c", script]);
+
+    let container = image.start().await?;
+
+    let config = ConnectionConfig {
+        name: None,
+        driver: DriverKind::Postgres,
+        host: "127.0.0.1".to_owned(),
+        port: Some(5432),
+        user: "postgres".to_owned(),
+        dbname: Some("postgres".to_owned()),
+        password: Some("postgres".to_owned()),
+        path: None,
+        sslmode: SslMode::Require,
+        ..Default::default()
+    };
+
+    Ok(SslSetup {
+        container,
+        config,
+        root_cert: dir.join("ca.crt"),
+    })
+}
+
+pub struct SshSetup {
+    pub sshd: ContainerAsync<GenericImage>,
+    pub postgres: ContainerAsync<GenericImage>,
+    pub config: ConnectionConfig,
+}
+
+// @note: postgres is only reachable from the sshd container, by its name on a shared network
+pub async fn setup_ssh() -> anyhow::Result<SshSetup> {
+    let network = "dbuzzy-ssh";
+
+    let postgres = GenericImage::new(NAME, TAG)
+        .with_wait_for(WaitFor::message_on_stderr(
+            "database system is ready to accept connections",
+        ))
+        .with_env_var("POSTGRES_PASSWORD", "postgres")
+        .with_network(network)
+        .with_container_name("dbuzzy-ssh-postgres")
+        .start()
+        .await?;
+
+    let key = std::env::temp_dir().join(format!("dbuzzy-ssh-{}", std::process::id()));
+    let _ = std::fs::remove_file(&key);
+
+    let status = std::process::Command::new("ssh-keygen")
+        .args(["-q", "-t", "ed25519", "-N", "", "-f"])
+        .arg(&key)
+        .status()?;
+    anyhow::ensure!(status.success(), "ssh-keygen failed");
+
+    let public_key = std::fs::read_to_string(key.with_extension("pub"))?;
+
+    let sshd = GenericImage::new("linuxserver/openssh-server", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("[ls.io-init] done."))
+        .with_env_var("PUBLIC_KEY", public_key.trim())
+        .with_env_var("USER_NAME", "dbuzzy")
+        // @note: the image disables tcp forwarding by default
+        .with_env_var("DOCKER_MODS", "linuxserver/mods:openssh-server-ssh-tunnel")
+        .with_network(network)
+        .with_mapped_port(2222, 2222.tcp())
+        .start()
+        .await?;
+
+    let config = ConnectionConfig {
+        driver: DriverKind::Postgres,
+        host: "dbuzzy-ssh-postgres".to_owned(),
+        port: Some(5432),
+        user: "postgres".to_owned(),
+        dbname: Some("postgres".to_owned()),
+        password: Some("postgres".to_owned()),
+        sslmode: SslMode::Disable,
+        ssh: Some(SshConfig {
+            host: "127.0.0.1".to_owned(),
+            port: Some(2222),
+            user: Some("dbuzzy".to_owned()),
+            key: Some(key),
+            options: vec![
+                "StrictHostKeyChecking=no".to_owned(),
+                "UserKnownHostsFile=/dev/null".to_owned(),
+            ],
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    Ok(SshSetup {
+        sshd,
+        postgres,
+        config,
+    })
+}
+
+#[cfg(feature = "mysql")]
+pub async fn setup_mysql() -> anyhow::Result<(ContainerAsync<GenericImage>, dbuzzy::db::DbPool)> {
+    // @note: the init scripts run on a temporary server with port 0
+    let image = GenericImage::new("mysql", "8.4")
+        .with_wait_for(WaitFor::message_on_stderr(
+            "port: 3306  MySQL Community Server",
+        ))
+        .with_env_var("MYSQL_ROOT_PASSWORD", "mysql")
+        .with_env_var("MYSQL_DATABASE", "test")
+        .with_mapped_port(3306, 3306.tcp());
+
+    let container = image.start().await?;
+
+    let config = ConnectionConfig {
+        name: None,
+        driver: DriverKind::Mysql,
+        host: "127.0.0.1".to_owned(),
+        port: Some(3306),
+        user: "root".to_owned(),
+        dbname: Some("test".to_owned()),
+        password: Some("mysql".to_owned()),
+        path: None,
+        sslmode: SslMode::Disable,
+        ..Default::default()
+    };
+
+    let pool = dbuzzy::db::connect(&config).await?;
+
+    let connection = pool.acquire().await?;
+
+    connection
+        .fetch(
+            r#"
+                CREATE TABLE IF NOT EXISTS bar (
+                    id INTEGER PRIMARY KEY,
+                    baz varchar(50) NOT NULL DEFAULT 'x'
+                )
+            "#,
+        )
+        .await?;
+
+    Ok((container, pool))
+}
@@ -1,4 +1,14 @@
-use dbuzzy::db::tree::{DatabaseTree, TreeItem, TreeItemKind};
+// @note: the containers are set up through the postgres driver
+#![cfg(feature = "postgres")]
+
+use std::sync::Arc;
+
+use dbuzzy::db::{
+    tree::{Collapsed, Loaded, TreeItem, TreeItemKind},
+    ConnectionConfig, DbPool, Driver, DriverKind, EditBatch, ExportFormat, PgPool, RowCount,
+    SslMode, TableRef, Transaction, TransactionState, Value,
+};
+use deadpool_postgres::GenericClient;
 
 mod db;
 
@@ -7,8 +17,8 @@ mod db;
 async fn test_db_tree() -> anyhow::Result<()> {
     let (container, pool) = db::setup().await?;
 
-    let connection = pool.acquire().await?;
-    let tree = DatabaseTree::load(&connection).await?;
+    let connection = Driver::acquire(&pool).await?;
+    let mut tree = connection.load_tree().await?;
 
     let tree_list = tree.as_ref();
 
@@ -19,7 +29,9 @@ async fn test_db_tree() -> anyhow::Result<()> {
         TreeItem {
             indent: 0,
             name: "postgres".to_owned(),
-            kind: TreeItemKind::Database { collapsed: false }
+            kind: TreeItemKind::Database,
+            collapsed: Collapsed::new(false),
+            loaded: Loaded::new(),
         }
     );
 
@@ -29,24 +41,426 @@ async fn test_db_tree() -> anyhow::Result<()> {
             indent: 4,
             name: "foo".to_owned(),
             kind: TreeItemKind::Schema {
-                collapsed: false,
                 database: tree_list[0].clone()
             },
+            collapsed: Collapsed::new(true),
+            loaded: Loaded::new(),
         }
     );
 
+    assert_eq!(tree_list[2].name, "public");
+
+    tree.expand(connection.as_ref(), 1).await?;
+
+    let groups = tree.as_ref()[2..9]
+        .iter()
+        .map(|x| x.name.as_str())
+        .collect::<Vec<_>>();
+
     assert_eq!(
-        *tree.as_ref()[2],
+        groups,
+        vec![
+            "Tables",
+            "Views",
+            "Materialized Views",
+            "Functions",
+            "Sequences",
+            "Types",
+            "Extensions"
+        ]
+    );
+
+    assert!(tree.as_ref()[2].is_visible());
+
+    tree.collapse(1);
+    assert!(!tree.as_ref()[2].is_visible());
+    assert!(tree.as_ref()[9].is_visible());
+
+    tree.expand(connection.as_ref(), 1).await?;
+    assert_eq!(tree.as_ref().len(), 10);
+
+    tree.expand(connection.as_ref(), 2).await?;
+
+    let tree_list = tree.as_ref();
+
+    assert_eq!(
+        *tree_list[3],
         TreeItem {
-            indent: 8,
+            indent: 12,
             name: "bar".to_owned(),
             kind: TreeItemKind::Table {
-                schema: tree_list[1].clone(),
-                database: tree_list[0].clone()
-            }
+                group: tree_list[2].clone()
+            },
+            collapsed: Collapsed::new(true),
+            loaded: Loaded::new(),
         }
     );
 
     container.stop().await?;
     Ok(())
 }
+
+#[tokio::test]
+#[ignore]
+async fn test_db_tree_expand() -> anyhow::Result<()> {
+    let (container, pool) = db::setup().await?;
+
+    let connection = Driver::acquire(&pool).await?;
+    let mut tree = connection.load_tree().await?;
+
+    // @note: foo -> Tables -> bar
+    tree.expand(connection.as_ref(), 1).await?;
+    tree.expand(connection.as_ref(), 2).await?;
+    tree.expand(connection.as_ref(), 3).await?;
+
+    let groups = tree.as_ref()[4..9]
+        .iter()
+        .map(|x| x.name.as_str())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        groups,
+        vec![
+            "Columns",
+            "Indexes",
+            "Constraints",
+            "Foreign Keys",
+            "Triggers"
+        ]
+    );
+
+    tree.expand(connection.as_ref(), 4).await?;
+
+    let columns = tree.as_ref()[5..7]
+        .iter()
+        .map(|x| x.label())
+        .collect::<Vec<_>>();
+
+    assert_eq!(columns, vec!["id integer", "baz character varying(50)"]);
+    assert!(tree.as_ref()[7].is_group());
+
+    container.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_transaction() -> anyhow::Result<()> {
+    let (container, pool) = db::setup().await?;
+    let pool: DbPool = Arc::new(pool);
+    let table = TableRef::new("foo".to_owned(), "bar".to_owned());
+
+    let mut tx = Transaction::begin(&pool).await?;
+    tx.fetch("INSERT INTO foo.bar VALUES (1, 'a')").await?;
+    tx.savepoint("sp1").await?;
+    tx.fetch("INSERT INTO foo.bar VALUES (2, 'b')").await?;
+
+    // @note: every statement fails after a failed one, until the rollback
+    assert!(tx.fetch("SELECT 1 / 0").await.is_err());
+    assert_eq!(tx.state(), TransactionState::Failed);
+    assert!(tx.fetch("SELECT 1").await.is_err());
+    assert!(tx.table_info(&table).await.is_err());
+
+    tx.fetch("ROLLBACK TO SAVEPOINT sp1").await?;
+    assert_eq!(tx.state(), TransactionState::Active);
+
+    // @note: the table views see the changes of the transaction
+    let info = tx.table_info(&table).await?;
+    assert_eq!(info.rows, RowCount::Exact(1));
+
+    let page = tx.fetch_page(&table, &info, 0, 10).await?;
+    assert_eq!(
+        page.result.rows,
+        vec![vec![Value::Int(1), Value::Text("a".to_owned())]]
+    );
+
+    // @note: a failing edit is rolled back to its savepoint only
+    let mut edits = EditBatch::new(table, DriverKind::Postgres);
+    edits.toggle_delete(vec![("id".to_owned(), Value::Int(2))]);
+    assert!(tx.commit_edits(&edits).await.is_err());
+    assert_eq!(tx.state(), TransactionState::Active);
+
+    let connection = pool.acquire().await?;
+    let result = connection.fetch("SELECT count(*) FROM foo.bar").await?;
+    assert_eq!(result.rows, vec![vec![Value::Int(0)]]);
+
+    tx.rollback().await?;
+    assert!(tx.is_finished());
+
+    container.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_export_stream() -> anyhow::Result<()> {
+    let (container, pool) = db::setup().await?;
+
+    let connection = Driver::acquire(&pool).await?;
+    let rows = 100_000;
+    let sql = "SELECT n, 'row ' || n AS label FROM generate_series(1, $1::int) AS n";
+    let values = [Some(rows.to_string())];
+
+    // @note: the rows are written as they arrive, none of them is held in a result
+    let mut out = Vec::new();
+    let written = connection
+        .export(sql, Some(&values), ExportFormat::Ndjson, &mut out)
+        .await
+        .expect("postgres streams the rows")?;
+    assert_eq!(written, rows);
+
+    let out = String::from_utf8(out)?;
+    assert_eq!(out.lines().count(), rows as usize);
+    assert_eq!(
+        out.lines().last(),
+        Some(r#"{"n":100000,"label":"row 100000"}"#)
+    );
+
+    // @note: the statement runs again, so it must not change anything
+    let mut out = Vec::new();
+    let e = connection
+        .export(
+            "CREATE TABLE exported (id int)",
+            None,
+            ExportFormat::Ndjson,
+            &mut out,
+        )
+        .await
+        .expect("postgres streams the rows")
+        .unwrap_err();
+    assert!(format!("{e:#}").contains("does not return rows"));
+
+    let e = connection
+        .export(
+            "DELETE FROM foo.bar RETURNING id",
+            None,
+            ExportFormat::Ndjson,
+            &mut out,
+        )
+        .await
+        .expect("postgres streams the rows")
+        .unwrap_err();
+    assert!(format!("{e:#}").contains("read-only transaction"));
+
+    container.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_statement_timeout() -> anyhow::Result<()> {
+    let (container, _) = db::setup().await?;
+
+    let config = ConnectionConfig {
+        statement_timeout: Some(1500),
+        ..db::config()
+    };
+
+    let pool = PgPool::create(&config)?;
+    let connection = pool.acquire().await?;
+
+    let row = connection.query_one("SHOW statement_timeout", &[]).await?;
+    assert_eq!(row.get::<_, String>(0), "1500ms");
+
+    let connection = Driver::acquire(&pool).await?;
+    let e = connection.fetch("SELECT pg_sleep(5)").await.unwrap_err();
+    assert!(format!("{e:#}").contains("statement timeout"));
+
+    container.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_cancel_query() -> anyhow::Result<()> {
+    let (container, pool) = db::setup().await?;
+
+    let connection = Driver::acquire(&pool).await?;
+    let cancel = connection
+        .cancel_handle()
+        .expect("postgres cancels queries");
+
+    let task = tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        cancel.cancel().await
+    });
+
+    let e = connection.fetch("SELECT pg_sleep(10)").await.unwrap_err();
+    assert!(format!("{e:#}").contains("canceling statement due to user request"));
+    task.await??;
+
+    // @note: the connection is usable after the cancelled query
+    let result = connection.fetch("SELECT 1").await?;
+    assert_eq!(result.rows, vec![vec![Value::Int(1)]]);
+
+    container.stop().await?;
+    Ok(())
+}
+
+async fn is_ssl(config: &ConnectionConfig) -> anyhow::Result<bool> {
+    let pool = PgPool::create(config)?;
+    let connection = pool.acquire().await?;
+
+    let row = connection
+        .query_one(
+            "SELECT ssl FROM pg_stat_ssl WHERE pid = pg_backend_pid()",
+            &[],
+        )
+        .await?;
+
+    Ok(row.get(0))
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_ssl_mode() -> anyhow::Result<()> {
+    let db::SslSetup {
+        container,
+        mut config,
+        root_cert,
+    } = db::setup_ssl().await?;
+
+    assert!(is_ssl(&config).await?);
+
+    config.sslmode = SslMode::Disable;
+    assert!(!is_ssl(&config).await?);
+
+    // @note: the test CA can't be verified without `sslrootcert`
+    config.sslmode = SslMode::VerifyFull;
+    assert!(is_ssl(&config).await.is_err());
+
+    config.sslrootcert = Some(root_cert);
+    config.sslmode = SslMode::VerifyCa;
+    assert!(is_ssl(&config).await?);
+
+    // @note: the certificate is for `localhost`, not for 127.0.0.1
+    config.sslmode = SslMode::VerifyFull;
+    assert!(is_ssl(&config).await.is_err());
+
+    config.host = "localhost".to_owned();
+    config.hostaddr = Some("127.0.0.1".parse()?);
+    assert!(is_ssl(&config).await?);
+
+    container.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_ssh_tunnel() -> anyhow::Result<()> {
+    let setup = db::setup_ssh().await?;
+
+    let pool = dbuzzy::db::connect(&setup.config).await?;
+    let connection = pool.acquire().await?;
+
+    let result = connection.fetch("SELECT current_user").await?;
+    assert_eq!(result.rows, vec![vec![Value::Text("postgres".to_owned())]]);
+
+    drop(connection);
+    drop(pool);
+
+    // @note: a bad key fails with the ssh error, instead of hanging
+    let mut config = setup.config.clone();
+    if let Some(ssh) = config.ssh.as_mut() {
+        ssh.key = Some("/nonexistent".into());
+        ssh.options.push("IdentitiesOnly=yes".to_owned());
+    }
+    assert!(dbuzzy::db::connect(&config).await.is_err());
+
+    setup.sshd.stop().await?;
+    setup.postgres.stop().await?;
+    Ok(())
+}
+
+#[cfg(feature = "mysql")]
+#[tokio::test]
+#[ignore]
+async fn test_mysql() -> anyhow::Result<()> {
+    use dbuzzy::db::tree::DatabaseTree;
+
+    let (container, pool) = db::setup_mysql().await?;
+
+    let connection = pool.acquire().await?;
+    let mut tree = connection.load_tree().await?;
+
+    let names = |tree: &DatabaseTree| tree.as_ref().iter().map(|x| x.label()).collect::<Vec<_>>();
+
+    assert_eq!(names(&tree), vec!["test", "test"]);
+
+    // @note: test -> Tables -> bar -> Columns
+    tree.expand(connection.as_ref(), 1).await?;
+    tree.expand(connection.as_ref(), 2).await?;
+    tree.expand(connection.as_ref(), 3).await?;
+    tree.expand(connection.as_ref(), 4).await?;
+    tree.expand(connection.as_ref(), 7).await?;
+
+    assert_eq!(
+        names(&tree)[2..10],
+        vec![
+            "Tables",
+            "bar",
+            "Columns",
+            "id int not null",
+            "baz varchar(50) not null default x",
+            "Indexes",
+            "PRIMARY: UNIQUE BTREE (id)",
+            "Foreign Keys",
+        ]
+    );
+
+    let result = connection
+        .fetch("INSERT INTO bar (id) VALUES (1), (2)")
+        .await?;
+    assert_eq!(result.affected, Some(2));
+
+    let result = connection
+        .fetch("SELECT id, baz, CAST(1.50 AS DECIMAL(4, 2)) AS d, NULL AS n FROM bar")
+        .await?;
+
+    assert_eq!(result.columns[0].type_name, "int");
+    assert_eq!(
+        result.rows[1],
+        vec![
+            Value::Int(2),
+            Value::Text("x".to_owned()),
+            Value::Numeric("1.50".to_owned()),
+            Value::Null
+        ]
+    );
+
+    let values = [Some("1".to_owned())];
+    let result = connection
+        .fetch_params(
+            "SELECT baz FROM bar WHERE id > :id AND :id IS NOT NULL",
+            &values,
+        )
+        .await?;
+    assert_eq!(result.rows, vec![vec![Value::Text("x".to_owned())]]);
+
+    let table = TableRef::new("test".to_owned(), "bar".to_owned());
+    let info = connection.table_info(&table).await?;
+    assert_eq!(info.key, vec!["id"]);
+    assert_eq!(info.rows, RowCount::Exact(2));
+
+    assert_eq!(
+        connection.ddl(&tree.as_ref()[8]).await?,
+        "ALTER TABLE `test`.`bar` ADD PRIMARY KEY (`id`);"
+    );
+
+    // @note: an update to the same value still matches its row
+    let mut edits = EditBatch::new(table, DriverKind::Mysql);
+    edits.update(
+        vec![("id".to_owned(), Value::Int(1))],
+        "baz",
+        Some("x".to_owned()),
+    );
+    assert_eq!(edits.commit(connection.as_ref()).await?, 1);
+
+    let plan = connection
+        .explain("SELECT * FROM bar WHERE id = 1", false)
+        .await?;
+    assert!(!plan.nodes().is_empty());
+
+    container.stop().await?;
+    Ok(())
+}
@@ -19,7 +19,7 @@ impl Buffer {
         self.mode
     }
 
-    pub fn set_mode(&mut self, mode: Mode) {
+    pub const fn set_mode(&mut self, mode: Mode) {
         self.mode = mode;
     }
 
@@ -27,7 +27,7 @@ impl Buffer {
         &self.text
     }
 
-    pub fn text_mut(&mut self) -> &mut Rope {
+    pub const fn text_mut(&mut self) -> &mut Rope {
         &mut self.text
     }
 
@@ -43,7 +43,7 @@ impl Buffer {
         self.offset
     }
 
-    pub fn set_offset(&mut self, offset: usize) {
+    pub const fn set_offset(&mut self, offset: usize) {
         self.offset = offset;
     }
 
@@ -51,7 +51,7 @@ impl Buffer {
         (self.index, self.offset)
     }
 
-    pub fn set_pos(&mut self, pos: Pos) {
+    pub const fn set_pos(&mut self, pos: Pos) {
         self.index = pos.0;
         self.offset = pos.1;
     }
@@ -71,7 +71,9 @@ impl Buffer {
         self.vscroll
     }
 
-    pub fn update_vscroll(&mut self, max: usize) {
+    pub const fn update_vscroll(&mut self, max: usize) {
+        // @note: a pane without rows still shows the line of the cursor
+        let max = if max == 0 { 1 } else { max };
         let upper_bound = self.vscroll + max - 1;
 
         if self.index < self.vscroll {
@@ -85,17 +87,17 @@ impl Buffer {
         self.selection.as_ref()
     }
 
-    pub fn update_selection(&mut self, pos: usize) {
+    pub const fn update_selection(&mut self, pos: usize) {
         if let Some(selection) = self.selection.as_mut() {
             selection.update(pos);
         }
     }
 
-    pub fn new_selection(&mut self, pos: usize) {
+    pub const fn new_selection(&mut self, pos: usize) {
         self.selection = Some(Selection::new(pos));
     }
 
-    pub fn reset_selection(&mut self) {
+    pub const fn reset_selection(&mut self) {
         self.selection = None;
     }
 
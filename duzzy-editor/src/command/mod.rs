@@ -12,6 +12,7 @@ use std::{collections::HashMap, sync::Arc};
 use duzzy_lib::event::Input;
 pub use input::on_key as input_on_key;
 pub use search::on_key as search_on_key;
+pub(crate) use select::selected_text;
 
 use clip::*;
 use modify::*;
@@ -60,6 +61,8 @@ pub enum CmdType {
     SearchMode,
     SearchNext,
     SearchPrev,
+    NextDocument,
+    PrevDocument,
 }
 
 pub struct Command {
@@ -113,6 +116,8 @@ impl CommandRegistry {
             Command::new(CmdType::SearchMode, search_mode),
             Command::new(CmdType::SearchNext, search_next),
             Command::new(CmdType::SearchPrev, search_prev),
+            Command::new(CmdType::NextDocument, next_document),
+            Command::new(CmdType::PrevDocument, prev_document),
         ];
 
         let mut map = HashMap::new();
@@ -141,7 +146,7 @@ pub struct CommandFinder {
 }
 
 impl CommandFinder {
-    pub fn reset(&mut self) {
+    pub const fn reset(&mut self) {
         self.current = None;
     }
 
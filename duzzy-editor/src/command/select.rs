@@ -25,7 +25,7 @@ pub(super) fn select_line(ws: &mut Workspace) {
     buf.set_offset(ofs);
 }
 
-pub(super) fn selected_text(buf: &Buffer) -> Option<Cow<str>> {
+pub(crate) fn selected_text(buf: &Buffer) -> Option<Cow<'_, str>> {
     let slice = buf.selection()?.slice(buf.text());
 
     let text = match slice.as_str() {
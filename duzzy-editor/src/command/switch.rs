@@ -30,7 +30,7 @@ fn insert_to_normal_impl(doc: &mut Document) {
     });
 }
 
-pub(super) fn visual_to_normal_impl(buf: &mut Buffer) {
+pub(super) const fn visual_to_normal_impl(buf: &mut Buffer) {
     buf.reset_selection();
     buf.set_mode(Mode::Normal);
 }
@@ -47,6 +47,14 @@ pub(super) fn search_mode(ws: &mut Workspace) {
     ws.cur_mut().buf_mut().set_mode(Mode::Search);
 }
 
+pub(super) fn next_document(ws: &mut Workspace) {
+    ws.next_doc();
+}
+
+pub(super) fn prev_document(ws: &mut Workspace) {
+    ws.prev_doc();
+}
+
 pub(super) fn insert_mode_inplace(ws: &mut Workspace) {
     switch_mode(ws, Switch::Inplace);
 }
@@ -155,4 +163,17 @@ mod tests {
         assert_eq!((0, 0), buf.pos());
         assert_eq!(&buf.text().to_string(), "\n\n");
     }
+
+    #[test]
+    fn test_switch_document() {
+        let mut ws = Workspace::default();
+        ws.add_doc(Document::from_text("a"));
+        ws.add_doc(Document::from_text("b"));
+
+        next_document(&mut ws);
+        assert_eq!(&ws.cur().buf().text().to_string(), "a");
+
+        prev_document(&mut ws);
+        assert_eq!(&ws.cur().buf().text().to_string(), "b");
+    }
 }
@@ -18,7 +18,7 @@ use crate::{
 pub struct DocumentId(NonZeroUsize);
 
 impl DocumentId {
-    pub const MAX: Self = Self(unsafe { NonZeroUsize::new_unchecked(usize::MAX) });
+    pub const MAX: Self = Self(NonZeroUsize::new(usize::MAX).unwrap());
 
     pub fn next() -> Self {
         pub static IDS: AtomicUsize = AtomicUsize::new(1);
@@ -81,6 +81,12 @@ impl Document {
         Ok(document)
     }
 
+    pub fn from_text(text: &str) -> Self {
+        let mut document = Self::default();
+        document.buffer.set_text(Rope::from_str(text));
+        document
+    }
+
     pub const fn id(&self) -> DocumentId {
         self.id
     }
@@ -89,11 +95,11 @@ impl Document {
         &self.buffer
     }
 
-    pub fn buf_mut(&mut self) -> &mut Buffer {
+    pub const fn buf_mut(&mut self) -> &mut Buffer {
         &mut self.buffer
     }
 
-    pub fn transaction(&mut self) -> Option<&mut Transaction> {
+    pub const fn transaction(&mut self) -> Option<&mut Transaction> {
         self.transaction.as_mut()
     }
 
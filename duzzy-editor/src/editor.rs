@@ -4,10 +4,11 @@ use duzzy_lib::{event::Input, DuzzyWidget, EventOutcome};
 
 use crate::{
     clipboard::Clipboard,
-    command::{input_on_key, search_on_key, CommandFinder},
+    command::{input_on_key, search_on_key, selected_text, CommandFinder},
     document::{Document, DocumentId},
     keymap::Keymaps,
     search::SearchRegistry,
+    sql,
     widget::{Cursor, EditorWidget, Viewport},
     SmartString,
 };
@@ -48,15 +49,65 @@ impl Editor {
         Self::new(workspace)
     }
 
+    pub fn new_text(text: &str) -> Self {
+        let mut workspace = Workspace::default();
+        workspace.add_doc(Document::from_text(text));
+        Self::new(workspace)
+    }
+
+    // @note: opens the text in a new document next to the current ones
+    pub fn open_text(&mut self, text: &str) {
+        self.workspace.add_doc(Document::from_text(text));
+    }
+
+    // @note: opens the file in a new document next to the current ones
+    pub fn open_file(&mut self, filepath: impl AsRef<Path>) -> anyhow::Result<()> {
+        self.workspace.add_doc(Document::from_path(filepath)?);
+        Ok(())
+    }
+
+    pub fn text(&self) -> String {
+        self.workspace.cur().buf().text().to_string()
+    }
+
+    pub fn selected_text(&self) -> Option<String> {
+        let buf = self.workspace.cur().buf();
+
+        if !buf.is_visual() {
+            return None;
+        }
+
+        selected_text(buf).map(|x| x.into_owned())
+    }
+
+    // @note: the splitter works in chars, the cursor and the selection in bytes
+    pub fn select_statement(&mut self) -> Option<String> {
+        let buf = self.workspace.cur_mut().buf_mut();
+        let text = buf.text();
+
+        let (start, end) = sql::statement_at(text, text.byte_to_char(buf.byte_pos()))?;
+        let statement = text.slice(start..=end).to_string();
+        let (start, end) = (text.char_to_byte(start), text.char_to_byte(end));
+
+        buf.new_selection(start);
+        buf.update_selection(end);
+
+        Some(statement)
+    }
+
+    pub fn is_insert(&self) -> bool {
+        self.workspace.cur().buf().is_insert()
+    }
+
     pub fn cursor(&self) -> Cursor {
         let buf = self.workspace.cur().buf();
         let mode = buf.mode();
 
         let (mut y, mut x) = buf.pos();
-        x = x.min(self.viewport.width - 1);
+        x = x.min(self.viewport.width.saturating_sub(1));
         y = y
             .saturating_sub(buf.vscroll())
-            .min(self.viewport.height - 1);
+            .min(self.viewport.height.saturating_sub(1));
 
         Cursor {
             x: x as _,
@@ -130,7 +181,23 @@ impl Workspace {
         self.current = id;
     }
 
-    pub fn clipboard(&mut self) -> &mut Clipboard {
+    pub fn next_doc(&mut self) {
+        let mut ids = self.documents.keys().copied().collect::<Vec<_>>();
+        ids.sort();
+
+        let pos = ids.iter().position(|id| *id == self.current).unwrap_or(0);
+        self.current = ids[(pos + 1) % ids.len()];
+    }
+
+    pub fn prev_doc(&mut self) {
+        let mut ids = self.documents.keys().copied().collect::<Vec<_>>();
+        ids.sort();
+
+        let pos = ids.iter().position(|id| *id == self.current).unwrap_or(0);
+        self.current = ids[(pos + ids.len() - 1) % ids.len()];
+    }
+
+    pub const fn clipboard(&mut self) -> &mut Clipboard {
         &mut self.clipboard
     }
 
@@ -155,3 +222,19 @@ impl Workspace {
             .expect("current mut doc")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_statement() {
+        let mut editor = Editor::new_text("select 'привет мир';\nselect 2;\nselect 3;");
+        editor.workspace.cur_mut().buf_mut().set_pos((1, 0));
+
+        assert_eq!(editor.select_statement().as_deref(), Some("select 2;"));
+
+        let buf = editor.workspace.cur().buf();
+        assert_eq!(buf.selection().map(|s| s.range()), Some((30, 38)));
+    }
+}
@@ -67,6 +67,8 @@ impl Keymaps {
             ("ge", CmdType::GoToBottomLine),
             ("gl", CmdType::GoToLineEnd),
             ("gh", CmdType::GoToLineStart),
+            ("gn", CmdType::NextDocument),
+            ("gp", CmdType::PrevDocument),
             ("w", CmdType::MoveNextWordStart),
             ("e", CmdType::MoveNextWordEnd),
             ("b", CmdType::MovePrevWordStart),
@@ -13,6 +13,7 @@ mod history;
 mod keymap;
 mod search;
 mod selection;
+mod sql;
 mod transaction;
 mod widget;
 
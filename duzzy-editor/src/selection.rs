@@ -38,7 +38,7 @@ impl Selection {
         rope.slice(start..=end)
     }
 
-    pub fn update(&mut self, pos: usize) {
+    pub const fn update(&mut self, pos: usize) {
         self.head = pos;
     }
 }
@@ -113,7 +113,7 @@ pub fn selection_spans(
     max_len: usize,
     line: RopeSlice<'_>,
     selection: SelectedRange,
-) -> Vec<SelectionSpan> {
+) -> Vec<SelectionSpan<'_>> {
     let (start, end) = selection;
     let overlaps = start < line_idx + max_len && line_idx <= end;
 
@@ -129,9 +129,11 @@ pub fn selection_spans(
         end.saturating_sub(line_idx).min(max_len),
     );
 
-    overlaps
-        .then(|| SpanIter::new(line, in_line_range).collect())
-        .unwrap_or_default()
+    if overlaps {
+        SpanIter::new(line, in_line_range).collect()
+    } else {
+        Default::default()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,262 @@
+use ropey::Rope;
+
+use crate::selection::SelectedRange;
+
+pub fn statements(text: &Rope) -> Vec<SelectedRange> {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut splitter = Splitter::new(&chars);
+
+    let mut i = 0;
+    while i < chars.len() {
+        i = match chars[i] {
+            '\'' => splitter.skip_quoted(i, '\'', is_escape_string(&chars, i)),
+            '"' => splitter.skip_quoted(i, '"', false),
+            '-' if chars.get(i + 1) == Some(&'-') => splitter.skip_line_comment(i),
+            '/' if chars.get(i + 1) == Some(&'*') => splitter.skip_block_comment(i),
+            '$' => match dollar_tag(&chars, i) {
+                Some(tag) => splitter.skip_dollar_quoted(i, &tag),
+                None => splitter.code(i),
+            },
+            ';' => splitter.split(i),
+            _ => splitter.code(i),
+        };
+    }
+
+    splitter.finish()
+}
+
+pub fn statement_at(text: &Rope, pos: usize) -> Option<SelectedRange> {
+    let ranges = statements(text);
+
+    // @note: in between statements the previous one wins
+    ranges
+        .iter()
+        .rev()
+        .find(|(start, _)| *start <= pos)
+        .or_else(|| ranges.first())
+        .copied()
+}
+
+struct Splitter<'a> {
+    chars: &'a [char],
+    start: usize,
+    has_code: bool,
+    ranges: Vec<SelectedRange>,
+}
+
+impl<'a> Splitter<'a> {
+    const fn new(chars: &'a [char]) -> Self {
+        Self {
+            chars,
+            start: 0,
+            has_code: false,
+            ranges: vec![],
+        }
+    }
+
+    fn code(&mut self, i: usize) -> usize {
+        self.has_code |= !self.chars[i].is_whitespace();
+        i + 1
+    }
+
+    fn split(&mut self, i: usize) -> usize {
+        self.push(i);
+        self.start = i + 1;
+        self.has_code = false;
+        i + 1
+    }
+
+    fn finish(mut self) -> Vec<SelectedRange> {
+        if let Some(end) = self.chars.len().checked_sub(1) {
+            if self.start <= end {
+                self.push(end);
+            }
+        }
+
+        self.ranges
+    }
+
+    fn push(&mut self, end: usize) {
+        if !self.has_code {
+            return;
+        }
+
+        let is_blank = |i: &usize| self.chars[*i].is_whitespace();
+
+        let start = (self.start..=end).find(|i| !is_blank(i));
+        let end = (self.start..=end).rev().find(|i| !is_blank(i));
+
+        if let (Some(start), Some(end)) = (start, end) {
+            self.ranges.push((start, end));
+        }
+    }
+
+    fn skip_quoted(&mut self, i: usize, quote: char, escapes: bool) -> usize {
+        self.has_code = true;
+
+        let mut j = i + 1;
+        while j < self.chars.len() {
+            match self.chars[j] {
+                '\\' if escapes => j += 2,
+                c if c == quote && self.chars.get(j + 1) == Some(&quote) => j += 2,
+                c if c == quote => return j + 1,
+                _ => j += 1,
+            }
+        }
+
+        self.chars.len()
+    }
+
+    fn skip_line_comment(&self, i: usize) -> usize {
+        self.chars[i..]
+            .iter()
+            .position(|c| *c == '\n')
+            .map_or(self.chars.len(), |p| i + p + 1)
+    }
+
+    fn skip_block_comment(&self, i: usize) -> usize {
+        let mut depth = 0;
+        let mut j = i;
+
+        while j < self.chars.len() {
+            match (self.chars[j], self.chars.get(j + 1)) {
+                ('/', Some('*')) => {
+                    depth += 1;
+                    j += 2;
+                }
+                ('*', Some('/')) => {
+                    depth -= 1;
+                    j += 2;
+
+                    if depth == 0 {
+                        return j;
+                    }
+                }
+                _ => j += 1,
+            }
+        }
+
+        self.chars.len()
+    }
+
+    fn skip_dollar_quoted(&mut self, i: usize, tag: &[char]) -> usize {
+        self.has_code = true;
+
+        let body = i + tag.len();
+        self.chars[body..]
+            .windows(tag.len())
+            .position(|w| w == tag)
+            .map_or(self.chars.len(), |p| body + p + tag.len())
+    }
+}
+
+const fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn is_escape_string(chars: &[char], quote: usize) -> bool {
+    let Some(prev) = quote.checked_sub(1) else {
+        return false;
+    };
+
+    let is_prefix = matches!(chars[prev], 'e' | 'E');
+    let is_word_start = prev == 0 || !is_ident_char(chars[prev - 1]);
+
+    is_prefix && is_word_start
+}
+
+fn dollar_tag(chars: &[char], i: usize) -> Option<Vec<char>> {
+    if i > 0 && is_ident_char(chars[i - 1]) {
+        return None;
+    }
+
+    let rest = chars.get(i + 1..)?;
+    let len = rest.iter().position(|c| !is_ident_char(*c))?;
+
+    if rest[len] != '$' || rest.first().is_some_and(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    Some(chars[i..i + len + 2].to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use ropey::Rope;
+
+    use super::*;
+
+    fn split(text: &str) -> Vec<String> {
+        let rope = Rope::from_str(text);
+
+        statements(&rope)
+            .into_iter()
+            .map(|(start, end)| rope.slice(start..=end).to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_statements() {
+        assert_eq!(
+            split("select 1;\n\n  select 2  ;select 3"),
+            vec!["select 1;", "select 2  ;", "select 3"]
+        );
+
+        assert_eq!(split("  \n;;  -- only comment\n"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_statements_quoted() {
+        assert_eq!(
+            split("select 'a;''b';select \"x;\"\"\" from t;select E'\\';';"),
+            vec![
+                "select 'a;''b';",
+                "select \"x;\"\"\" from t;",
+                "select E'\\';';"
+            ]
+        );
+
+        assert_eq!(
+            split("select e'\\\\';select 'a\\';"),
+            vec!["select e'\\\\';", "select 'a\\';"]
+        );
+    }
+
+    #[test]
+    fn test_statements_comments() {
+        assert_eq!(
+            split("-- a;b\nselect 1; /* c; /* nested; */ ; */ select 2;"),
+            vec!["-- a;b\nselect 1;", "/* c; /* nested; */ ; */ select 2;"]
+        );
+    }
+
+    #[test]
+    fn test_statements_dollar_quoted() {
+        let text = "create function f() returns int as $body$ select 1; $$ ; $body$ language sql;select $1;";
+
+        assert_eq!(
+            split(text),
+            vec![
+                "create function f() returns int as $body$ select 1; $$ ; $body$ language sql;",
+                "select $1;"
+            ]
+        );
+
+        assert_eq!(
+            split("select $$a;b$$;select 2"),
+            vec!["select $$a;b$$;", "select 2"]
+        );
+    }
+
+    #[test]
+    fn test_statement_at() {
+        let text = Rope::from_str("select 1;\n\nselect 2;\n");
+
+        assert_eq!(statement_at(&text, 0), Some((0, 8)));
+        assert_eq!(statement_at(&text, 8), Some((0, 8)));
+        assert_eq!(statement_at(&text, 10), Some((0, 8)));
+        assert_eq!(statement_at(&text, 11), Some((11, 19)));
+        assert_eq!(statement_at(&text, 21), Some((11, 19)));
+        assert_eq!(statement_at(&Rope::new(), 0), None);
+    }
+}
@@ -8,7 +8,7 @@ enum Action {
 }
 
 impl Action {
-    fn as_insert_mut(&mut self) -> Option<&mut Change> {
+    const fn as_insert_mut(&mut self) -> Option<&mut Change> {
         if let Self::Insert(change) = self {
             return Some(change);
         }
@@ -16,7 +16,7 @@ impl Action {
         None
     }
 
-    fn as_delete_mut(&mut self) -> Option<&mut Change> {
+    const fn as_delete_mut(&mut self) -> Option<&mut Change> {
         if let Self::Delete(change) = self {
             return Some(change);
         }
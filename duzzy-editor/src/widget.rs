@@ -49,7 +49,7 @@ impl<'a> EditorWidget<'a> {
         }
     }
 
-    fn update_viewport(&mut self, width: u16, height: u16) {
+    const fn update_viewport(&mut self, width: u16, height: u16) {
         self.editor.viewport.width = width as _;
         self.editor.viewport.height = height as _;
     }
@@ -89,7 +89,7 @@ impl<'a> EditorWidget<'a> {
     }
 
     #[inline]
-    pub fn text(&self) -> Option<Text> {
+    pub fn text(&self) -> Option<Text<'_>> {
         let buf = self.editor.workspace.cur().buf();
 
         let text = buf.text();
@@ -120,6 +120,12 @@ impl<'a> EditorWidget<'a> {
             Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
 
         self.update_viewport(main.width, main.height);
+        self.status.render(status, buf);
+
+        // @note: a pane that small has only room for the status line
+        if main.is_empty() {
+            return;
+        }
 
         if let Some(text) = self.text() {
             let inner = Paragraph::new(text);
@@ -127,10 +133,8 @@ impl<'a> EditorWidget<'a> {
         }
 
         let cursor = self.editor.cursor();
-        buf.get_mut(cursor.x, cursor.y)
+        buf.get_mut(main.x + cursor.x, main.y + cursor.y)
             .set_style(self.theme.cursor_style);
-
-        self.status.render(status, buf);
     }
 }
 
@@ -189,3 +193,33 @@ impl StatusLine {
         search_paragraph.render(right, buf);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use duzzy_lib::{
+        event::{Event, Input},
+        DuzzyWidget,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_render_small() {
+        let mut editor = Editor::new_text("SELECT 1;\nSELECT 2;\n");
+
+        for area in [
+            Rect::new(0, 0, 20, 1),
+            Rect::new(0, 0, 0, 5),
+            Rect::new(0, 0, 20, 0),
+        ] {
+            let mut buf = Buffer::empty(area);
+            editor.render(area, &mut buf);
+
+            editor.input(Input {
+                event: Event::Char('j'),
+                ..Default::default()
+            });
+            editor.render(area, &mut buf);
+        }
+    }
+}